@@ -0,0 +1,320 @@
+//! W3C DID Document representation of the `did:dock` DIDs as described in https://www.w3.org/TR/did-core/
+//! and the DID resolution result as described in https://w3c-ccg.github.io/did-resolution/.
+
+use core_mods::{
+    did::{
+        self, Config, DidKeyWithId, DidResolution, HistoricalDidResolution, OnChainDidDocument,
+        ServiceEndpointWithId, VerRelType,
+    },
+    keys_and_sigs::PublicKey,
+};
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use sp_runtime::SaturatedConversion;
+use std::collections::BTreeMap;
+
+/// JSON-LD context of the DID Document.
+pub const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+/// Method-specific prefix of the Dock DIDs.
+pub const DID_DOCK_PREFIX: &str = "did:dock:";
+/// Media type of the returned DID Document.
+pub const DID_LD_JSON: &str = "application/did+ld+json";
+
+/// Result of the DID resolution.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidResolutionResult {
+    pub did_document: Option<DidDocument>,
+    pub did_document_metadata: DidDocumentMetadata,
+    pub did_resolution_metadata: DidResolutionMetadata,
+}
+
+/// DID Document of the on-chain DID.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub controller: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verification_method: Vec<VerificationMethod>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authentication: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertion_method: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capability_invocation: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_agreement: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub service: Vec<Service>,
+}
+
+/// Verification method built from the `DidKey`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationMethod {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub controller: String,
+    pub public_key_base58: String,
+}
+
+/// Service built from the `ServiceEndpoint`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Service {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub typ: Vec<String>,
+    pub service_endpoint: Vec<String>,
+    /// Additional properties of the service such as `routingKeys` or `accept`.
+    #[serde(flatten)]
+    pub properties: BTreeMap<String, Vec<String>>,
+}
+
+/// Metadata about the resolved DID Document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocumentMetadata {
+    /// Current nonce of the on-chain DID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// Set if the DID was removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deactivated: Option<bool>,
+    /// Number of the block in which the DID was removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deactivated_at: Option<u64>,
+    /// Number of the block in which the DID was registered on chain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<u64>,
+    /// Number of the block in which the resolved version of the DID Document was produced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated: Option<u64>,
+    /// Identifier of the resolved version of the DID Document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+    /// Number of the block in which the next version of the DID Document was produced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_update: Option<u64>,
+    /// Identifier of the next version of the DID Document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_version_id: Option<String>,
+}
+
+/// Metadata about the resolution process.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DidResolutionMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Reference to the DID Document of the off-chain DID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub off_chain_did_doc_ref: Option<did::OffChainDidDocRef>,
+}
+
+impl DidResolutionResult {
+    /// Builds the resolution result for `did` from the data returned by the runtime.
+    pub fn new<T: Config>(did: did::Did, resolution: Option<DidResolution<T>>) -> Self {
+        match resolution {
+            Some(DidResolution::OnChain { document, nonce }) => Self {
+                did_document: Some(DidDocument::new(did, document)),
+                did_document_metadata: DidDocumentMetadata {
+                    nonce: Some(nonce.saturated_into()),
+                    ..Default::default()
+                },
+                did_resolution_metadata: DidResolutionMetadata {
+                    content_type: Some(DID_LD_JSON.into()),
+                    ..Default::default()
+                },
+            },
+            Some(DidResolution::OffChain(details)) => Self {
+                did_document: None,
+                did_document_metadata: Default::default(),
+                did_resolution_metadata: DidResolutionMetadata {
+                    off_chain_did_doc_ref: Some(details.doc_ref),
+                    ..Default::default()
+                },
+            },
+            Some(DidResolution::Deactivated { deactivated_at }) => Self {
+                did_document: None,
+                did_document_metadata: DidDocumentMetadata {
+                    deactivated: Some(true),
+                    deactivated_at: Some(deactivated_at.saturated_into()),
+                    ..Default::default()
+                },
+                did_resolution_metadata: Default::default(),
+            },
+            None => Self {
+                did_document: None,
+                did_document_metadata: Default::default(),
+                did_resolution_metadata: DidResolutionMetadata {
+                    error: Some("notFound".into()),
+                    ..Default::default()
+                },
+            },
+        }
+    }
+
+    /// Builds the resolution result for the version of `did` recorded in the history.
+    pub fn from_history<T: Config>(
+        did: did::Did,
+        resolution: Option<HistoricalDidResolution<T>>,
+    ) -> Self {
+        match resolution {
+            Some(HistoricalDidResolution {
+                document,
+                version_id,
+                created_at,
+                updated_at,
+                next_version,
+                deactivated,
+            }) => Self {
+                did_document: (!deactivated).then(|| DidDocument::new(did, document)),
+                did_document_metadata: DidDocumentMetadata {
+                    deactivated: Some(true).filter(|_| deactivated),
+                    deactivated_at: deactivated.then(|| updated_at.saturated_into()),
+                    created: Some(created_at.saturated_into()),
+                    updated: Some(updated_at.saturated_into()),
+                    version_id: Some(version_id.to_string()),
+                    next_update: next_version
+                        .as_ref()
+                        .map(|(_, updated_at)| (*updated_at).saturated_into()),
+                    next_version_id: next_version.map(|(version_id, _)| version_id.to_string()),
+                    ..Default::default()
+                },
+                did_resolution_metadata: DidResolutionMetadata {
+                    content_type: Some(DID_LD_JSON.into()),
+                    ..Default::default()
+                },
+            },
+            None => Self {
+                did_document: None,
+                did_document_metadata: Default::default(),
+                did_resolution_metadata: DidResolutionMetadata {
+                    error: Some("notFound".into()),
+                    ..Default::default()
+                },
+            },
+        }
+    }
+}
+
+impl DidDocument {
+    /// Builds DID Document of the on-chain `did`.
+    pub fn new(
+        did: did::Did,
+        OnChainDidDocument {
+            controllers,
+            keys,
+            service_endpoints,
+        }: OnChainDidDocument,
+    ) -> Self {
+        let id = did_uri(&did);
+        let mut doc = Self {
+            context: vec![DID_CONTEXT.into()],
+            controller: controllers.iter().map(|ctrl| did_uri(&ctrl.0)).collect(),
+            verification_method: Vec::with_capacity(keys.len()),
+            authentication: Vec::new(),
+            assertion_method: Vec::new(),
+            capability_invocation: Vec::new(),
+            key_agreement: Vec::new(),
+            service: service_endpoints
+                .into_iter()
+                .map(|endpoint| Service::new(&id, endpoint))
+                .collect(),
+            id,
+        };
+
+        for DidKeyWithId { id: key_id, key } in keys {
+            let method_id = format!("{}#keys-{}", doc.id, u32::from(key_id));
+            let mut relationships = [
+                (VerRelType::AUTHENTICATION, &mut doc.authentication),
+                (VerRelType::ASSERTION, &mut doc.assertion_method),
+                (
+                    VerRelType::CAPABILITY_INVOCATION,
+                    &mut doc.capability_invocation,
+                ),
+                (VerRelType::KEY_AGREEMENT, &mut doc.key_agreement),
+            ];
+            for (rel, refs) in relationships.iter_mut() {
+                if key.ver_rels.intersects(*rel) {
+                    refs.push(method_id.clone());
+                }
+            }
+
+            doc.verification_method.push(VerificationMethod {
+                id: method_id,
+                typ: verification_method_type(&key.public_key).into(),
+                controller: doc.id.clone(),
+                public_key_base58: bs58::encode(key.public_key.as_slice()).into_string(),
+            });
+        }
+
+        doc
+    }
+}
+
+impl Service {
+    fn new(did_uri: &str, ServiceEndpointWithId { id, endpoint }: ServiceEndpointWithId) -> Self {
+        let id = String::from_utf8_lossy(&id);
+        // Service ids are usually stored as absolute URIs, relative ones are resolved against the DID.
+        let id = if id.starts_with(DID_DOCK_PREFIX) {
+            id.into_owned()
+        } else {
+            format!("{}#{}", did_uri, id.trim_start_matches('#'))
+        };
+
+        Self {
+            id,
+            typ: endpoint
+                .types
+                .iter()
+                .map(|typ| String::from_utf8_lossy(typ.name()).into_owned())
+                .collect(),
+            service_endpoint: endpoint
+                .origins
+                .iter()
+                .map(|origin| String::from_utf8_lossy(origin).into_owned())
+                .collect(),
+            properties: endpoint
+                .properties
+                .iter()
+                .map(|did::ServiceEndpointProperty { name, values }| {
+                    (
+                        String::from_utf8_lossy(name).into_owned(),
+                        values
+                            .iter()
+                            .map(|value| String::from_utf8_lossy(value).into_owned())
+                            .collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns fully qualified `did:dock` identifier.
+pub fn did_uri(did: &did::Did) -> String {
+    format!(
+        "{}{}",
+        DID_DOCK_PREFIX,
+        AccountId32::from(did.0).to_ss58check()
+    )
+}
+
+fn verification_method_type(public_key: &PublicKey) -> &'static str {
+    match public_key {
+        PublicKey::Sr25519(_) => "Sr25519VerificationKey2020",
+        PublicKey::Ed25519(_) => "Ed25519VerificationKey2018",
+        PublicKey::Secp256k1(_) => "EcdsaSecp256k1VerificationKey2019",
+        PublicKey::X25519(_) => "X25519KeyAgreementKey2019",
+        PublicKey::Secp256r1(_) => "EcdsaSecp256r1VerificationKey2019",
+    }
+}
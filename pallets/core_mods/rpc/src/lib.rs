@@ -1,6 +1,11 @@
 pub use self::gen_client::Client as PriceFeedClient;
 use core::marker::PhantomData;
-use core_mods::{accumulator, bbs_plus, util::IncId};
+use core_mods::{
+    accumulator, bbs_plus,
+    keys_and_sigs::PublicKey,
+    revoke,
+    util::{IncId, WrappedBytes},
+};
 pub use core_mods::{
     did::{self, Config},
     runtime_api::CoreModsApi as CoreModsRuntimeApi,
@@ -12,6 +17,9 @@ use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::{collections::BTreeMap, sync::Arc};
 
+pub mod did_document;
+pub mod status_list;
+
 pub trait ConfigWrapper {
     type T: Config;
 }
@@ -46,6 +54,85 @@ where
         at: Option<BlockHash>,
     ) -> Result<Vec<Option<did::AggregatedDidDetailsResponse<T::T>>>>;
 
+    #[rpc(name = "core_mods_resolveDid")]
+    fn resolve_did(
+        &self,
+        did: did::Did,
+        at: Option<BlockHash>,
+    ) -> Result<did_document::DidResolutionResult>;
+
+    #[rpc(name = "core_mods_resolveDidAt")]
+    fn resolve_did_at(
+        &self,
+        did: did::Did,
+        query: did::DidVersionQuery<T::T>,
+        at: Option<BlockHash>,
+    ) -> Result<did_document::DidResolutionResult>;
+
+    #[rpc(name = "core_mods_didRecovery")]
+    fn did_recovery(
+        &self,
+        did: did::Did,
+        at: Option<BlockHash>,
+    ) -> Result<Option<did::DidRecoveryStatus<T::T>>>;
+
+    #[rpc(name = "core_mods_didsByAccount")]
+    fn dids_by_account(
+        &self,
+        account: <T::T as frame_system::Config>::AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<did::Did>>;
+
+    #[rpc(name = "core_mods_didsByPublicKey")]
+    fn dids_by_public_key(
+        &self,
+        public_key: PublicKey,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(did::Did, IncId)>>;
+
+    #[rpc(name = "core_mods_didKeys")]
+    fn did_keys(
+        &self,
+        did: did::Did,
+        cursor: Option<IncId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> Result<did::Page<IncId, did::DidKeyWithId>>;
+
+    #[rpc(name = "core_mods_didControllers")]
+    fn did_controllers(
+        &self,
+        did: did::Did,
+        cursor: Option<did::Controller>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> Result<did::Page<did::Controller, did::Controller>>;
+
+    #[rpc(name = "core_mods_didServiceEndpoints")]
+    fn did_service_endpoints(
+        &self,
+        did: did::Did,
+        cursor: Option<WrappedBytes>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> Result<did::Page<WrappedBytes, did::ServiceEndpointWithId>>;
+
+    #[rpc(name = "core_mods_didList")]
+    fn did_list(
+        &self,
+        did_type: Option<did::DidType>,
+        cursor: Option<did::Did>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> Result<did::Page<did::Did, did::Did>>;
+
+    #[rpc(name = "core_mods_didControllerPaths")]
+    fn did_controller_paths(
+        &self,
+        did: did::Did,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<did::ControllerPath>>;
+
     #[rpc(name = "core_mods_bbsPlusPublicKeyWithParams")]
     fn bbs_plus_public_key_with_params(
         &self,
@@ -80,6 +167,21 @@ where
         id: accumulator::AccumulatorId,
         at: Option<BlockHash>,
     ) -> Result<Option<(Vec<u8>, Option<accumulator::AccumPublicKeyWithParams>)>>;
+
+    #[rpc(name = "core_mods_statusList2021")]
+    fn status_list_2021(
+        &self,
+        id: revoke::RegistryId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<status_list::StatusList2021>>;
+
+    #[rpc(name = "core_mods_revocationStatus")]
+    fn revocation_status(
+        &self,
+        registry_id: revoke::RegistryId,
+        revoke_id: revoke::RevokeId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<revoke::RevocationStatus<T::T>>>;
 }
 
 /// A struct that implements the [`CoreModsApi`].
@@ -141,6 +243,194 @@ where
                 data: Some(format!("{:?}", e).into()),
             })
     }
+
+    fn resolve_did(
+        &self,
+        did: did::Did,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<did_document::DidResolutionResult> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.resolve_did(&at, did)
+            .map(|resolution| did_document::DidResolutionResult::new(did, resolution))
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Unable to resolve DID".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+
+    fn resolve_did_at(
+        &self,
+        did: did::Did,
+        query: did::DidVersionQuery<T::T>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<did_document::DidResolutionResult> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.resolve_did_at(&at, did, query)
+            .map(|resolution| did_document::DidResolutionResult::from_history(did, resolution))
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Unable to resolve DID version".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+
+    fn did_recovery(
+        &self,
+        did: did::Did,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<did::DidRecoveryStatus<T::T>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.did_recovery(&at, did).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(2),
+            message: "Unable to query DID recovery".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn dids_by_account(
+        &self,
+        account: <T::T as frame_system::Config>::AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<did::Did>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.dids_by_account(&at, account).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(2),
+            message: "Unable to query DIDs by account".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn dids_by_public_key(
+        &self,
+        public_key: PublicKey,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(did::Did, IncId)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.dids_by_public_key(&at, public_key)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Unable to query DIDs by public key".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+
+    fn did_keys(
+        &self,
+        did: did::Did,
+        cursor: Option<IncId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<did::Page<IncId, did::DidKeyWithId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.did_keys(&at, did, cursor, limit).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(2),
+            message: "Unable to query DID keys".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn did_controllers(
+        &self,
+        did: did::Did,
+        cursor: Option<did::Controller>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<did::Page<did::Controller, did::Controller>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.did_controllers(&at, did, cursor, limit)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Unable to query DID controllers".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+
+    fn did_service_endpoints(
+        &self,
+        did: did::Did,
+        cursor: Option<WrappedBytes>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<did::Page<WrappedBytes, did::ServiceEndpointWithId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.did_service_endpoints(&at, did, cursor, limit)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Unable to query DID service endpoints".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+
+    fn did_list(
+        &self,
+        did_type: Option<did::DidType>,
+        cursor: Option<did::Did>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<did::Page<did::Did, did::Did>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.did_list(&at, did_type, cursor, limit)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(2),
+                message: "Unable to query DIDs".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+
+    fn did_controller_paths(
+        &self,
+        did: did::Did,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<did::ControllerPath>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.did_controller_paths(&at, did).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(2),
+            message: "Unable to resolve DID controller paths".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
     fn bbs_plus_public_key_with_params(
         &self,
         id: bbs_plus::BBSPlusPublicKeyStorageKey,
@@ -225,4 +515,42 @@ where
                 data: Some(format!("{:?}", e).into()),
             })
     }
+
+    fn status_list_2021(
+        &self,
+        id: revoke::RegistryId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<status_list::StatusList2021>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.status_list(&at, id)
+            .map(|status_list| status_list.map(Into::into))
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(1),
+                message: "Unable to query status list.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
+
+    fn revocation_status(
+        &self,
+        registry_id: revoke::RegistryId,
+        revoke_id: revoke::RevokeId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<revoke::RevocationStatus<T::T>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+        api.revocation_status(&at, registry_id, revoke_id)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(1),
+                message: "Unable to query revocation status.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
 }
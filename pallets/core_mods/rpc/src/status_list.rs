@@ -0,0 +1,34 @@
+//! W3C StatusList2021 representation of the revocation registries' status lists as described in
+//! https://w3c-ccg.github.io/vc-status-list-2021/.
+
+use core_mods::revoke::StatusList;
+use serde::{Deserialize, Serialize};
+
+/// Type of the status list credential subject.
+pub const STATUS_LIST_2021: &str = "StatusList2021";
+/// Purpose of the status lists stored by the revocation registries.
+pub const REVOCATION_PURPOSE: &str = "revocation";
+
+/// Subject of the StatusList2021 credential holding the status list.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusList2021 {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub status_purpose: String,
+    /// GZIP-compressed bitstring encoded in base64url without padding.
+    pub encoded_list: String,
+}
+
+impl From<StatusList> for StatusList2021 {
+    fn from(status_list: StatusList) -> Self {
+        Self {
+            typ: STATUS_LIST_2021.to_string(),
+            status_purpose: REVOCATION_PURPOSE.to_string(),
+            encoded_list: base64::encode_config(
+                &status_list.encoded_list.0,
+                base64::URL_SAFE_NO_PAD,
+            ),
+        }
+    }
+}
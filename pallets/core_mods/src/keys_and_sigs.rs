@@ -1,10 +1,13 @@
-use crate::util::{Bytes32, Bytes33, Bytes64, Bytes65};
+use crate::util::{Bytes32, Bytes33, Bytes64, Bytes65, WrappedBytes};
 use codec::{Decode, Encode};
 use frame_support::dispatch::Weight;
 use sha2::{Digest, Sha256};
 use sp_core::{ed25519, sr25519, Pair};
 use sp_runtime::traits::Verify;
-use sp_std::convert::TryInto;
+use sp_std::{
+    convert::{TryFrom, TryInto},
+    prelude::*,
+};
 
 /// An abstraction for a public key. Abstracts the type and value of the public key where the value is a
 /// byte array
@@ -19,6 +22,8 @@ pub enum PublicKey {
     Secp256k1(Bytes33),
     /// Compressed X25519 public key, 32 bytes. This key is not used for signing
     X25519(Bytes32),
+    /// Compressed public key for Secp256r1 (P-256) is 33 bytes
+    Secp256r1(Bytes33),
 }
 
 impl From<ed25519::Public> for PublicKey {
@@ -41,6 +46,15 @@ impl From<libsecp256k1::PublicKey> for PublicKey {
     }
 }
 
+impl From<p256::ecdsa::VerifyingKey> for PublicKey {
+    fn from(pubkey: p256::ecdsa::VerifyingKey) -> Self {
+        let mut value = [0; 33];
+        value.copy_from_slice(pubkey.to_encoded_point(true).as_bytes());
+
+        PublicKey::Secp256r1(Bytes33 { value })
+    }
+}
+
 /// An abstraction for a signature.
 #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -51,6 +65,172 @@ pub enum SigValue {
     Ed25519(Bytes64),
     /// Signature for Secp256k1 is 65 bytes
     Secp256k1(Bytes65),
+    /// Signature for Secp256r1 (P-256) is 64 bytes
+    Secp256r1(Bytes64),
+    /// WebAuthn assertion made with a Secp256r1 (P-256) key such as a platform passkey
+    WebAuthn(WebAuthnAssertion),
+    /// Recoverable Secp256k1 signature over the EIP-191 (`personal_sign`) keccak256 hash of the message
+    /// as produced by Ethereum wallets. Signature is 65 bytes with the last byte being the recovery id
+    /// which can be either 0/1 or 27/28
+    Secp256k1Eip191(Bytes65),
+}
+
+/// WebAuthn assertion as produced by `navigator.credentials.get`. The challenge in the client data
+/// must be the base64url-encoded (without padding) SHA-256 hash of the signed message.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WebAuthnAssertion {
+    /// Authenticator data returned by the authenticator
+    pub authenticator_data: WrappedBytes,
+    /// UTF-8 encoded client data JSON
+    pub client_data_json: WrappedBytes,
+    /// Secp256r1 signature over `authenticator_data || sha256(client_data_json)` with the DER encoding
+    /// converted to the 64-byte `r || s` form
+    pub signature: Bytes64,
+}
+
+impl WebAuthnAssertion {
+    /// Authenticator data consists of 32-byte RP ID hash, 1-byte flags and 4-byte signature counter
+    /// followed by the optional fields.
+    const MIN_AUTHENTICATOR_DATA_SIZE: usize = 37;
+    /// Maximum size of the authenticator data, leaves enough room for the authenticator extensions.
+    pub const MAX_AUTHENTICATOR_DATA_SIZE: usize = 1024;
+    /// Maximum size of the client data JSON. Bounds the hashing and scanning done by the verification
+    /// so that it can be covered by a fixed weight.
+    pub const MAX_CLIENT_DATA_JSON_SIZE: usize = 2048;
+    /// Index of the flags byte in the authenticator data.
+    const FLAGS_IDX: usize = 32;
+    /// User Present flag.
+    const USER_PRESENT: u8 = 0b0000_0001;
+    /// Type of the client data created for an assertion.
+    const ASSERTION_TYPE: &'static [u8] = b"webauthn.get";
+
+    /// Returns `true` if the sizes of the authenticator data and the client data JSON are within the bounds.
+    pub fn is_well_formed(&self) -> bool {
+        (Self::MIN_AUTHENTICATOR_DATA_SIZE..=Self::MAX_AUTHENTICATOR_DATA_SIZE)
+            .contains(&self.authenticator_data.0.len())
+            && self.client_data_json.0.len() <= Self::MAX_CLIENT_DATA_JSON_SIZE
+    }
+
+    /// Returns `true` if the assertion was made for the Relying Party with the given SHA-256 hash of its RP ID.
+    pub fn is_for_rp(&self, rp_id_hash: &[u8; 32]) -> bool {
+        self.authenticator_data.0.get(..Self::FLAGS_IDX) == Some(&rp_id_hash[..])
+    }
+
+    /// Verifies that the assertion was made for the given message using the given Secp256r1 key. Doesn't
+    /// check the Relying Party the assertion was made for, that is left to the caller using `is_for_rp`.
+    pub fn verify(&self, message: &[u8], pk: &Bytes33) -> bool {
+        use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+
+        if !self.is_well_formed() {
+            return false;
+        }
+        let auth_data = &self.authenticator_data.0;
+        if auth_data[Self::FLAGS_IDX] & Self::USER_PRESENT == 0 {
+            return false;
+        }
+
+        let client_data = &self.client_data_json.0;
+        if json_string_field(client_data, b"type") != Some(Self::ASSERTION_TYPE) {
+            return false;
+        }
+        let expected_challenge = base64url_encode(&Sha256::digest(message));
+        if json_string_field(client_data, b"challenge") != Some(&expected_challenge[..]) {
+            return false;
+        }
+
+        let mut signed_data = Vec::with_capacity(auth_data.len() + 32);
+        signed_data.extend_from_slice(auth_data);
+        signed_data.extend_from_slice(&Sha256::digest(client_data));
+
+        match (
+            VerifyingKey::from_sec1_bytes(&pk.value),
+            Signature::try_from(&self.signature.value[..]),
+        ) {
+            (Ok(pk), Ok(sig)) => pk.verify(&signed_data, &sig).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// Returns the value of the top-level string field `name` from the given JSON object. Values
+/// containing escaped characters aren't supported as the fields of interest never contain them.
+fn json_string_field<'a>(json: &'a [u8], name: &[u8]) -> Option<&'a [u8]> {
+    let skip_whitespace = |mut idx: usize| {
+        while json.get(idx).map_or(false, u8::is_ascii_whitespace) {
+            idx += 1;
+        }
+        idx
+    };
+
+    let mut depth = 0u32;
+    let mut idx = 0;
+    while idx < json.len() {
+        match json[idx] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.checked_sub(1)?,
+            b'"' => {
+                let start = idx + 1;
+                let end = start + json_string_len(&json[start..])?;
+                idx = skip_whitespace(end + 1);
+
+                if depth == 1 && json.get(idx) == Some(&b':') && &json[start..end] == name {
+                    let value_start = skip_whitespace(idx + 1);
+                    if json.get(value_start) != Some(&b'"') {
+                        return None;
+                    }
+                    let value = &json[value_start + 1..];
+                    let value = &value[..json_string_len(value)?];
+
+                    return if value.contains(&b'\\') {
+                        None
+                    } else {
+                        Some(value)
+                    };
+                }
+                continue;
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+/// Returns length of the JSON string contents preceding the closing quote.
+fn json_string_len(string: &[u8]) -> Option<usize> {
+    let mut idx = 0;
+    while idx < string.len() {
+        match string[idx] {
+            b'"' => return Some(idx),
+            b'\\' => idx += 2,
+            _ => idx += 1,
+        }
+    }
+
+    None
+}
+
+/// Encodes given bytes using the URL-safe base64 alphabet without padding.
+fn base64url_encode(bytes: &[u8]) -> Vec<u8> {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut res = Vec::with_capacity((bytes.len() * 4 + 2) / 3);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..=chunk.len() {
+            res.push(ALPHABET[(n >> (18 - 6 * i) & 0b11_1111) as usize]);
+        }
+    }
+
+    res
 }
 
 impl PublicKey {
@@ -77,12 +257,28 @@ impl PublicKey {
         PublicKey::X25519(Bytes32 { value: bytes })
     }
 
+    pub const fn secp256r1(bytes: [u8; 33]) -> Self {
+        PublicKey::Secp256r1(Bytes33 { value: bytes })
+    }
+
+    /// Checks if the public key bytes are a valid encoding of the key. Only Secp256r1 keys
+    /// are checked to be valid compressed curve points.
+    pub fn is_well_formed(&self) -> bool {
+        match self {
+            Self::Secp256r1(bytes) => {
+                p256::ecdsa::VerifyingKey::from_sec1_bytes(&bytes.value).is_ok()
+            }
+            _ => true,
+        }
+    }
+
     pub fn as_slice(&self) -> &[u8] {
         match self {
             Self::Sr25519(bytes) => &bytes.value[..],
             Self::Ed25519(bytes) => &bytes.value[..],
             Self::Secp256k1(bytes) => &bytes.value[..],
             Self::X25519(bytes) => &bytes.value[..],
+            Self::Secp256r1(bytes) => &bytes.value[..],
         }
     }
 }
@@ -124,6 +320,14 @@ impl SigValue {
         }
     }
 
+    /// Try to get reference to the bytes if its a Secp256r1 signature. Return error if its not.
+    pub fn as_secp256r1_sig_bytes(&self) -> Result<&[u8], ()> {
+        match self {
+            SigValue::Secp256r1(bytes) => Ok(bytes.as_bytes()),
+            _ => Err(()),
+        }
+    }
+
     /// Get weight for signature verification.
     /// Considers the type of signature. Disregards message size as messages are hashed giving the
     /// same output size and hashing itself is very cheap. The extrinsic using it might decide to
@@ -133,6 +337,9 @@ impl SigValue {
             SigValue::Sr25519(_) => SR25519_WEIGHT,
             SigValue::Ed25519(_) => ED25519_WEIGHT,
             SigValue::Secp256k1(_) => SECP256K1_WEIGHT,
+            SigValue::Secp256r1(_) => SECP256R1_WEIGHT,
+            SigValue::WebAuthn(_) => WEBAUTHN_WEIGHT,
+            SigValue::Secp256k1Eip191(_) => SECP256K1_WEIGHT,
         }
     }
 
@@ -172,6 +379,25 @@ impl SigValue {
                 let p = libsecp256k1::PublicKey::parse_compressed(&pk_bytes.value).unwrap();
                 libsecp256k1::verify(&m, &sig, &p)
             }
+            (PublicKey::Secp256r1(pk_bytes), SigValue::Secp256r1(sig_bytes)) => {
+                use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+
+                // Message is hashed with SHA-256 by the verifier
+                match (
+                    VerifyingKey::from_sec1_bytes(&pk_bytes.value),
+                    Signature::try_from(&sig_bytes.value[..]),
+                ) {
+                    (Ok(pk), Ok(sig)) => pk.verify(message, &sig).is_ok(),
+                    _ => false,
+                }
+            }
+            (PublicKey::Secp256r1(pk_bytes), SigValue::WebAuthn(assertion)) => {
+                assertion.verify(message, pk_bytes)
+            }
+            (PublicKey::Secp256k1(pk_bytes), SigValue::Secp256k1Eip191(sig_bytes)) => {
+                recover_eip191_public_key(message, sig_bytes)
+                    .map_or(false, |pk| pk.serialize_compressed() == pk_bytes.value)
+            }
             _ => {
                 return Err(());
             }
@@ -194,6 +420,18 @@ impl SigValue {
     pub fn secp256k1(msg: &[u8], sk: &libsecp256k1::SecretKey) -> Self {
         sign_with_secp256k1(msg, sk)
     }
+
+    pub fn secp256r1(msg: &[u8], sk: &p256::ecdsa::SigningKey) -> Self {
+        sign_with_secp256r1(msg, sk)
+    }
+
+    pub fn webauthn(msg: &[u8], sk: &p256::ecdsa::SigningKey) -> Self {
+        sign_with_webauthn(msg, sk)
+    }
+
+    pub fn secp256k1_eip191(msg: &[u8], sk: &libsecp256k1::SecretKey) -> Self {
+        sign_with_eip191(msg, sk)
+    }
 }
 
 // Weight for Sr25519 sig verification
@@ -202,6 +440,13 @@ pub const SR25519_WEIGHT: Weight = 140_000_000;
 pub const ED25519_WEIGHT: Weight = 152_000_000;
 // Weight for ecdsa using secp256k1 sig verification
 pub const SECP256K1_WEIGHT: Weight = 456_000_000;
+// Weight for ecdsa using secp256r1 (P-256) sig verification. Comes from the `verify_secp256r1_sig`
+// benchmark of the revoke module, the secp256k1 weight until it's run on the reference hardware
+pub const SECP256R1_WEIGHT: Weight = 456_000_000;
+// Weight for WebAuthn assertion verification with the largest assertion allowed by
+// `WebAuthnAssertion::is_well_formed`. Comes from the `verify_webauthn_sig` benchmark of the revoke
+// module, the secp256r1 weight plus the hashing and parsing until it's run on the reference hardware
+pub const WEBAUTHN_WEIGHT: Weight = 466_000_000;
 
 // XXX: Substrate UI can't parse them. Maybe later versions will fix it.
 /*
@@ -273,6 +518,167 @@ pub fn sign_with_secp256k1(msg: &[u8], sk: &libsecp256k1::SecretKey) -> SigValue
     SigValue::Secp256k1(Bytes65 { value: sig_bytes })
 }
 
+/// Hashes the message as done by Ethereum's `personal_sign` (EIP-191 version `0x45`), i.e.
+/// `keccak256("\x19Ethereum Signed Message:\n" || len(message) || message)` where the length is
+/// in decimal.
+pub fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    const PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+    let mut len_digits = [0u8; 20];
+    let mut len = message.len();
+    let mut digits_start = len_digits.len();
+    loop {
+        digits_start -= 1;
+        len_digits[digits_start] = b'0' + (len % 10) as u8;
+        len /= 10;
+        if len == 0 {
+            break;
+        }
+    }
+
+    let mut prefixed = Vec::with_capacity(PREFIX.len() + len_digits.len() + message.len());
+    prefixed.extend_from_slice(PREFIX);
+    prefixed.extend_from_slice(&len_digits[digits_start..]);
+    prefixed.extend_from_slice(message);
+
+    sp_io::hashing::keccak_256(&prefixed)
+}
+
+/// Recovers the Secp256k1 public key which produced the given EIP-191 signature of the message.
+/// Returns `None` if the signature is malformed.
+pub fn recover_eip191_public_key(
+    message: &[u8],
+    sig_bytes: &Bytes65,
+) -> Option<libsecp256k1::PublicKey> {
+    let m = libsecp256k1::Message::parse(&eip191_hash(message));
+    let sig = libsecp256k1::Signature::parse_standard_slice(&sig_bytes.value[0..64]).ok()?;
+    let v = sig_bytes.value[64];
+    let recovery_id = libsecp256k1::RecoveryId::parse(if v >= 27 { v - 27 } else { v }).ok()?;
+
+    libsecp256k1::recover(&m, &sig, &recovery_id).ok()
+}
+
+pub fn sign_with_eip191(msg: &[u8], sk: &libsecp256k1::SecretKey) -> SigValue {
+    let m = libsecp256k1::Message::parse(&eip191_hash(msg));
+    let (sig, recovery_id) = libsecp256k1::sign(&m, sk);
+    let mut sig_bytes: [u8; 65] = [0; 65];
+    sig_bytes[0..64].copy_from_slice(&sig.serialize()[..]);
+    sig_bytes[64] = 27 + recovery_id.serialize();
+    SigValue::Secp256k1Eip191(Bytes65 { value: sig_bytes })
+}
+
+pub fn get_secp256r1_keypair(seed: &[u8; 32]) -> (p256::ecdsa::SigningKey, PublicKey) {
+    let sk = p256::ecdsa::SigningKey::from_bytes(seed).unwrap();
+    let pk = sk.verifying_key().into();
+    (sk, pk)
+}
+
+pub fn get_secp256r1_keypair_1(seed: &[u8; 32]) -> Secp256r1Keypair {
+    let sk = p256::ecdsa::SigningKey::from_bytes(seed).unwrap();
+    let pk = sk.verifying_key();
+
+    Secp256r1Keypair { sk, pk }
+}
+
+#[derive(Debug, Clone)]
+pub struct Secp256r1Keypair {
+    pub sk: p256::ecdsa::SigningKey,
+    pub pk: p256::ecdsa::VerifyingKey,
+}
+
+impl Secp256r1Keypair {
+    pub fn sign(&self, msg: &[u8]) -> SigValue {
+        sign_with_secp256r1(msg, &self.sk)
+    }
+
+    pub fn public(&self) -> p256::ecdsa::VerifyingKey {
+        self.pk
+    }
+}
+
+pub fn sign_with_secp256r1(msg: &[u8], sk: &p256::ecdsa::SigningKey) -> SigValue {
+    use p256::ecdsa::{signature::Signer, Signature};
+
+    let sig: Signature = sk.sign(msg);
+    let mut sig_bytes: [u8; 64] = [0; 64];
+    sig_bytes.copy_from_slice(sig.as_ref());
+    SigValue::Secp256r1(Bytes64 { value: sig_bytes })
+}
+
+/// Creates a WebAuthn assertion for the given message as an authenticator would do for the `dock.io` Relying Party.
+pub fn sign_with_webauthn(msg: &[u8], sk: &p256::ecdsa::SigningKey) -> SigValue {
+    sign_with_webauthn_for_rp(msg, sk, b"dock.io")
+}
+
+/// Creates a WebAuthn assertion for the given message as an authenticator would do for the Relying Party with the
+/// given RP ID.
+pub fn sign_with_webauthn_for_rp(
+    msg: &[u8],
+    sk: &p256::ecdsa::SigningKey,
+    rp_id: &[u8],
+) -> SigValue {
+    let mut client_data_json = b"{\"type\":\"webauthn.get\",\"challenge\":\"".to_vec();
+    client_data_json.extend_from_slice(&base64url_encode(&Sha256::digest(msg)));
+    client_data_json.extend_from_slice(b"\",\"origin\":\"https://dock.io\",\"crossOrigin\":false}");
+
+    sign_webauthn_assertion(
+        rp_id,
+        WebAuthnAssertion::MIN_AUTHENTICATOR_DATA_SIZE,
+        client_data_json,
+        sk,
+    )
+}
+
+/// Creates the most expensive to verify WebAuthn assertion for the given message, i.e. one with
+/// the largest authenticator data and client data JSON where the checked fields come last.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn sign_with_webauthn_max_size(msg: &[u8], sk: &p256::ecdsa::SigningKey) -> SigValue {
+    let mut fields = b"\",\"type\":\"webauthn.get\",\"challenge\":\"".to_vec();
+    fields.extend_from_slice(&base64url_encode(&Sha256::digest(msg)));
+    fields.extend_from_slice(b"\"}");
+
+    let mut client_data_json = b"{\"origin\":\"".to_vec();
+    client_data_json.resize(
+        WebAuthnAssertion::MAX_CLIENT_DATA_JSON_SIZE - fields.len(),
+        b'a',
+    );
+    client_data_json.extend_from_slice(&fields);
+
+    sign_webauthn_assertion(
+        b"dock.io",
+        WebAuthnAssertion::MAX_AUTHENTICATOR_DATA_SIZE,
+        client_data_json,
+        sk,
+    )
+}
+
+fn sign_webauthn_assertion(
+    rp_id: &[u8],
+    authenticator_data_size: usize,
+    client_data_json: Vec<u8>,
+    sk: &p256::ecdsa::SigningKey,
+) -> SigValue {
+    use p256::ecdsa::{signature::Signer, Signature};
+
+    // RP ID hash, flags with User Present and User Verified set, and a zero signature counter
+    // followed by the zeroed optional fields
+    let mut authenticator_data = vec![0; authenticator_data_size];
+    authenticator_data[..WebAuthnAssertion::FLAGS_IDX].copy_from_slice(&Sha256::digest(rp_id));
+    authenticator_data[WebAuthnAssertion::FLAGS_IDX] = 0b0000_0101;
+
+    let mut signed_data = authenticator_data.clone();
+    signed_data.extend_from_slice(&Sha256::digest(&client_data_json));
+    let sig: Signature = sk.sign(&signed_data);
+    let mut sig_bytes: [u8; 64] = [0; 64];
+    sig_bytes.copy_from_slice(sig.as_ref());
+
+    SigValue::WebAuthn(WebAuthnAssertion {
+        authenticator_data: WrappedBytes(authenticator_data),
+        client_data_json: WrappedBytes(client_data_json),
+        signature: Bytes64 { value: sig_bytes },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -324,5 +730,169 @@ mod tests {
         let incorrect_sig = SigValue::Ed25519(Bytes64 { value: [10; 64] });
         assert!(correct_sig.verify(&msg, &pk).unwrap());
         assert!(incorrect_sig.verify(&msg, &pk).is_err());
+
+        let (sk, pk) = get_secp256r1_keypair(&[1; 32]);
+        assert!(pk.can_sign());
+        assert!(pk.is_well_formed());
+        let correct_sig = sign_with_secp256r1(&msg, &sk);
+        let incorrect_sig = SigValue::Ed25519(Bytes64 { value: [10; 64] });
+        assert!(correct_sig.verify(&msg, &pk).unwrap());
+        assert!(incorrect_sig.verify(&msg, &pk).is_err());
+        assert!(!correct_sig.verify(&[27u8; 350], &pk).unwrap());
+
+        // Secp256r1 signature isn't accepted for the Secp256k1 key
+        let (_, secp256k1_pk) = get_secp256k1_keypair(&[1; 32]);
+        assert!(correct_sig.verify(&msg, &secp256k1_pk).is_err());
+
+        // Bytes which don't encode a curve point aren't a valid key
+        assert!(!PublicKey::secp256r1([5; 33]).is_well_formed());
+    }
+
+    #[test]
+    fn webauthn_verification() {
+        let msg = vec![26u8; 350];
+        let (sk, pk) = get_secp256r1_keypair(&[1; 32]);
+        let (_, other_pk) = get_secp256r1_keypair(&[2; 32]);
+        let (_, secp256k1_pk) = get_secp256k1_keypair(&[1; 32]);
+
+        let sig = sign_with_webauthn(&msg, &sk);
+        assert!(sig.verify(&msg, &pk).unwrap());
+        assert!(!sig.verify(&msg, &other_pk).unwrap());
+        assert!(sig.verify(&msg, &secp256k1_pk).is_err());
+
+        // Challenge must match the message
+        assert!(!sig.verify(&[27u8; 350], &pk).unwrap());
+
+        let assertion = match sig {
+            SigValue::WebAuthn(assertion) => assertion,
+            _ => unreachable!(),
+        };
+
+        // Relying Party is left to the caller to check
+        let rp_id_hash = sp_io::hashing::sha2_256(b"dock.io");
+        assert!(assertion.is_for_rp(&rp_id_hash));
+        assert!(!assertion.is_for_rp(&sp_io::hashing::sha2_256(b"example.com")));
+        let other_rp = sign_with_webauthn_for_rp(&msg, &sk, b"example.com");
+        assert!(other_rp.verify(&msg, &pk).unwrap());
+        match other_rp {
+            SigValue::WebAuthn(other_rp) => assert!(!other_rp.is_for_rp(&rp_id_hash)),
+            _ => unreachable!(),
+        }
+
+        // User must be present
+        let mut no_user = assertion.clone();
+        no_user.authenticator_data.0[WebAuthnAssertion::FLAGS_IDX] = 0;
+        assert!(!SigValue::WebAuthn(no_user).verify(&msg, &pk).unwrap());
+
+        // Truncated authenticator data isn't accepted
+        let mut truncated = assertion.clone();
+        truncated.authenticator_data.0.truncate(36);
+        assert!(!SigValue::WebAuthn(truncated).verify(&msg, &pk).unwrap());
+
+        // Oversized authenticator data and client data aren't accepted
+        let mut long_auth_data = assertion.clone();
+        long_auth_data
+            .authenticator_data
+            .0
+            .resize(WebAuthnAssertion::MAX_AUTHENTICATOR_DATA_SIZE + 1, 0);
+        assert!(!long_auth_data.is_well_formed());
+        assert!(!SigValue::WebAuthn(long_auth_data)
+            .verify(&msg, &pk)
+            .unwrap());
+        let mut long_client_data = assertion.clone();
+        long_client_data
+            .client_data_json
+            .0
+            .resize(WebAuthnAssertion::MAX_CLIENT_DATA_JSON_SIZE + 1, b' ');
+        assert!(!long_client_data.is_well_formed());
+        assert!(!SigValue::WebAuthn(long_client_data)
+            .verify(&msg, &pk)
+            .unwrap());
+
+        // Assertions created during registration aren't accepted
+        let mut creation = assertion.clone();
+        creation.client_data_json = WrappedBytes(
+            String::from_utf8(creation.client_data_json.0)
+                .unwrap()
+                .replace("webauthn.get", "webauthn.create")
+                .into_bytes(),
+        );
+        assert!(!SigValue::WebAuthn(creation).verify(&msg, &pk).unwrap());
+    }
+
+    #[test]
+    fn eip191_verification() {
+        let msg = vec![26u8; 350];
+        let (sk, pk) = get_secp256k1_keypair(&[1; 32]);
+        let (_, other_pk) = get_secp256k1_keypair(&[2; 32]);
+
+        let sig = sign_with_eip191(&msg, &sk);
+        assert!(sig.verify(&msg, &pk).unwrap());
+        assert!(!sig.verify(&msg, &other_pk).unwrap());
+        assert!(!sig.verify(&[27u8; 350], &pk).unwrap());
+        let (_, p256_pk) = get_secp256r1_keypair(&[1; 32]);
+        assert!(sig.verify(&msg, &p256_pk).is_err());
+
+        // Recovery id can also be given as 0/1
+        let mut sig_bytes = match sig {
+            SigValue::Secp256k1Eip191(bytes) => bytes,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            PublicKey::from(recover_eip191_public_key(&msg, &sig_bytes).unwrap()),
+            pk
+        );
+        sig_bytes.value[64] -= 27;
+        assert!(SigValue::Secp256k1Eip191(sig_bytes.clone())
+            .verify(&msg, &pk)
+            .unwrap());
+        sig_bytes.value[64] = 5;
+        assert!(!SigValue::Secp256k1Eip191(sig_bytes)
+            .verify(&msg, &pk)
+            .unwrap());
+
+        // EIP-191 signature isn't the regular Secp256k1 signature
+        let sig = sign_with_secp256k1(&msg, &sk);
+        let sig_bytes = match sig {
+            SigValue::Secp256k1(bytes) => bytes,
+            _ => unreachable!(),
+        };
+        assert!(!SigValue::Secp256k1Eip191(sig_bytes)
+            .verify(&msg, &pk)
+            .unwrap());
+    }
+
+    #[test]
+    fn eip191_hashing() {
+        // Hash of `personal_sign("hello")`
+        assert_eq!(
+            eip191_hash(b"hello"),
+            [
+                0x50, 0xb2, 0xc4, 0x3f, 0xd3, 0x91, 0x06, 0xba, 0xfb, 0xba, 0x0d, 0xa3, 0x4f, 0xc4,
+                0x30, 0xe1, 0xf9, 0x1e, 0x3c, 0x96, 0xea, 0x2a, 0xce, 0xe2, 0xbc, 0x34, 0x11, 0x9f,
+                0x92, 0xb3, 0x77, 0x50
+            ]
+        );
+        assert_eq!(
+            &eip191_hash(&[]),
+            &sp_io::hashing::keccak_256(b"\x19Ethereum Signed Message:\n0")
+        );
+    }
+
+    #[test]
+    fn webauthn_client_data_parsing() {
+        let json =
+            br#"{ "type" : "webauthn.get", "nested": {"challenge": "b"}, "challenge":"abc_-"}"#;
+        assert_eq!(json_string_field(json, b"type"), Some(&b"webauthn.get"[..]));
+        assert_eq!(json_string_field(json, b"challenge"), Some(&b"abc_-"[..]));
+        assert_eq!(json_string_field(json, b"origin"), None);
+        assert_eq!(json_string_field(br#"{"type":1}"#, b"type"), None);
+        assert_eq!(json_string_field(br#"{"type":"a\"b"}"#, b"type"), None);
+
+        assert_eq!(base64url_encode(b""), b"");
+        assert_eq!(base64url_encode(b"f"), b"Zg");
+        assert_eq!(base64url_encode(b"fo"), b"Zm8");
+        assert_eq!(base64url_encode(b"foo"), b"Zm9v");
+        assert_eq!(base64url_encode(&[0xfb, 0xff]), b"-_8");
     }
 }
@@ -37,7 +37,22 @@ def_state_change! {
         accumulator::RemoveAccumulatorPublicKey,
         accumulator::AddAccumulator,
         accumulator::UpdateAccumulator,
-        accumulator::RemoveAccumulator
+        accumulator::RemoveAccumulator,
+        did::RotateKeys,
+        did::SetControllerThreshold,
+        did::DidBatchUpdate,
+        did::SetRecoveryConfig,
+        did::InitiateRecovery,
+        did::CancelRecovery,
+        did::UpdateServiceEndpoint,
+        did::LinkAccount,
+        did::UnlinkAccount,
+        util::ActionWithDeadline,
+        revoke::UpdateRegistryPolicy,
+        revoke::UpdateStatusList,
+        revoke::SetRevocationStatus,
+        revoke::LiftSuspension,
+        did::MultiControllerAction
 }
 
 /// Converts the given entity to the state change.
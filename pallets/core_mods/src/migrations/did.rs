@@ -46,3 +46,195 @@ pub mod single_key {
         T::DbWeight::get().reads_writes(1 + records * 2, records * 3 + 1)
     }
 }
+
+pub mod key_validity {
+    use crate::{did::*, keys_and_sigs::PublicKey, util::IncId};
+    use codec::{Decode, Encode};
+    use core::fmt::Debug;
+    use frame_support::{log, traits::Get, weights::Weight, *};
+
+    /// `DidKey` as it was stored before the validity window was introduced.
+    #[derive(Encode, Decode, Clone, PartialEq, Debug)]
+    pub struct DidKeyWithoutValidity {
+        pub public_key: PublicKey,
+        pub ver_rels: VerRelType,
+    }
+
+    pub fn migrate_to_keys_with_validity<T: Config + Debug>() -> Weight {
+        let mut records = 0;
+
+        DidKeys::translate(
+            |_did: Did,
+             _key_id: IncId,
+             DidKeyWithoutValidity {
+                 public_key,
+                 ver_rels,
+             }| {
+                records += 1;
+
+                Some(DidKey::new(public_key, ver_rels))
+            },
+        );
+        log::info!("Migrated {} DID keys", records);
+
+        T::DbWeight::get().reads_writes(records, records)
+    }
+}
+
+pub mod controller_threshold {
+    use crate::{did::*, util::*};
+    use codec::{Decode, Encode};
+    use core::fmt::Debug;
+    use frame_support::{log, traits::Get, weights::Weight, *};
+
+    /// `OnChainDidDetails` as they were stored before the controller threshold was introduced.
+    #[derive(Encode, Decode, Clone, PartialEq, Debug)]
+    pub struct OnChainDidDetailsWithoutThreshold {
+        pub last_key_id: IncId,
+        pub active_controller_keys: u32,
+        pub active_controllers: u32,
+    }
+
+    /// `StoredDidDetails` as they were stored before the controller threshold was introduced.
+    #[derive(Encode, Decode)]
+    pub enum StoredDidDetailsWithoutThreshold<T: Config> {
+        OffChain(OffChainDidDetails<T>),
+        OnChain(WithNonce<T, OnChainDidDetailsWithoutThreshold>),
+    }
+
+    pub fn migrate_to_controller_threshold<T: Config + Debug>() -> Weight {
+        let mut records = 0;
+
+        Dids::<T>::translate_values(|details: StoredDidDetailsWithoutThreshold<T>| {
+            records += 1;
+
+            let details = match details {
+                StoredDidDetailsWithoutThreshold::OffChain(details) => details.into(),
+                StoredDidDetailsWithoutThreshold::OnChain(details) => {
+                    let nonce = details.nonce;
+                    let OnChainDidDetailsWithoutThreshold {
+                        last_key_id,
+                        active_controller_keys,
+                        active_controllers,
+                    } = details.into_data();
+
+                    WithNonce::new_with_nonce(
+                        OnChainDidDetails::new(
+                            last_key_id,
+                            active_controller_keys,
+                            active_controllers,
+                        ),
+                        nonce,
+                    )
+                    .into()
+                }
+            };
+
+            Some(details)
+        });
+        log::info!("Migrated {} DIDs", records);
+
+        T::DbWeight::get().reads_writes(records, records)
+    }
+}
+
+pub mod service_endpoint_types {
+    use crate::{did::*, util::WrappedBytes};
+    use codec::{Decode, Encode};
+    use core::fmt::Debug;
+    use frame_support::{log, traits::Get, weights::Weight, *};
+    use sp_std::prelude::*;
+
+    /// Bit of the `LINKED_DOMAINS` type in the former `ServiceEndpointType` bitflags.
+    const LINKED_DOMAINS: u16 = 0b0001;
+
+    /// `ServiceEndpoint` as it was stored when its types were represented by bitflags.
+    #[derive(Encode, Decode, Clone, PartialEq, Debug)]
+    pub struct ServiceEndpointWithBitflagTypes {
+        pub types: u16,
+        pub origins: Vec<WrappedBytes>,
+    }
+
+    pub fn migrate_to_typed_service_endpoints<T: Config + Debug>() -> Weight {
+        let mut records = 0;
+
+        DidServiceEndpoints::translate(
+            |_did: Did, _id: WrappedBytes, ServiceEndpointWithBitflagTypes { types, origins }| {
+                records += 1;
+
+                let types = (types & LINKED_DOMAINS != 0)
+                    .then(|| ServiceEndpointType::LinkedDomains)
+                    .into_iter()
+                    .collect();
+
+                Some(ServiceEndpoint {
+                    types,
+                    origins,
+                    properties: Vec::new(),
+                })
+            },
+        );
+        log::info!("Migrated {} DID service endpoints", records);
+
+        T::DbWeight::get().reads_writes(records, records)
+    }
+}
+
+pub mod account_index {
+    use crate::did::*;
+    use core::fmt::Debug;
+    use frame_support::{log, traits::Get, weights::Weight, *};
+
+    /// Adds owners of the existing off-chain DIDs to the index of DIDs by account.
+    pub fn index_offchain_did_owners<T: Config + Debug>() -> Weight {
+        let mut records = 0;
+        let mut indexed = 0;
+
+        for (did, details) in Dids::<T>::iter() {
+            records += 1;
+
+            if let StoredDidDetails::OffChain(OffChainDidDetails { account_id, .. }) = details {
+                indexed += 1;
+                AccountDids::<T>::insert(account_id, did, ());
+            }
+        }
+        log::info!("Indexed owners of {} off-chain DIDs", indexed);
+
+        T::DbWeight::get().reads_writes(records, indexed)
+    }
+}
+
+pub mod public_key_index {
+    use crate::did::*;
+    use core::fmt::Debug;
+    use frame_support::{log, traits::Get, weights::Weight, *};
+
+    /// Adds the existing keys of on-chain DIDs to the index of DIDs by public key.
+    pub fn index_public_keys<T: Config + Debug>() -> Weight {
+        let mut records = 0;
+
+        for (did, key_id, DidKey { public_key, .. }) in DidKeys::iter() {
+            records += 1;
+            PublicKeyDids::insert(public_key, (did, key_id), ());
+        }
+        log::info!("Indexed {} DID keys", records);
+
+        T::DbWeight::get().reads_writes(records, records)
+    }
+}
+
+pub mod did_history {
+    use crate::did::*;
+    use core::fmt::Debug;
+    use frame_support::{log, traits::Get, weights::Weight, *};
+
+    /// Starts recording the history of on-chain DIDs from the current block. The existing DIDs aren't iterated
+    /// as their current documents are recorded as the first versions on their first updates.
+    pub fn start_recording_history<T: Config + Debug>() -> Weight {
+        let now = <frame_system::Module<T>>::block_number();
+        DidHistoryRecordedSince::<T>::put(now);
+        log::info!("Recording DID history since block {:?}", now);
+
+        T::DbWeight::get().reads_writes(1, 1)
+    }
+}
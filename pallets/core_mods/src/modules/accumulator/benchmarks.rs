@@ -11,17 +11,19 @@ const MAX_KEY: u32 = 256;
 
 crate::bench_with_all_pairs! {
     with_pairs:
-    add_params_sr25519 for sr25519, add_params_ed25519 for ed25519, add_params_secp256k1 for secp256k1 {
+    add_params_sr25519 for sr25519, add_params_ed25519 for ed25519, add_params_secp256k1 for secp256k1, add_params_secp256r1 for secp256r1 {
         {
             let b in 0 .. MAX_PARAMS => ();
             let l in 1 .. MAX_LABEL => ();
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -45,13 +47,15 @@ crate::bench_with_all_pairs! {
         assert_eq!(AccumulatorParams::get(AccumulatorOwner(did), IncId::from(1u8)).unwrap(), params);
     }
 
-    remove_params_sr25519 for sr25519, remove_params_ed25519 for ed25519, remove_params_secp256k1 for secp256k1 {
+    remove_params_sr25519 for sr25519, remove_params_ed25519 for ed25519, remove_params_secp256k1 for secp256k1, remove_params_secp256r1 for secp256r1 {
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -83,16 +87,18 @@ crate::bench_with_all_pairs! {
         assert!(AccumulatorParams::get(AccumulatorOwner(did), IncId::from(1u8)).is_none());
     }
 
-    add_public_sr25519 for sr25519, add_public_ed25519 for ed25519, add_public_secp256k1 for secp256k1 {
+    add_public_sr25519 for sr25519, add_public_ed25519 for ed25519, add_public_secp256k1 for secp256k1, add_public_secp256r1 for secp256r1 {
         {
             let b in 0 .. MAX_KEY;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -130,13 +136,15 @@ crate::bench_with_all_pairs! {
         assert_eq!(AccumulatorKeys::get(AccumulatorOwner(did), IncId::from(1u8)).unwrap(), public_key);
     }
 
-    remove_public_sr25519 for sr25519, remove_public_ed25519 for ed25519, remove_public_secp256k1 for secp256k1 {
+    remove_public_sr25519 for sr25519, remove_public_ed25519 for ed25519, remove_public_secp256k1 for secp256k1, remove_public_secp256r1 for secp256r1 {
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -181,12 +189,13 @@ crate::bench_with_all_pairs! {
         assert!(AccumulatorKeys::get(AccumulatorOwner(did), IncId::from(1u8)).is_none());
     }
 
-    add_accumulator_sr25519 for sr25519, add_accumulator_ed25519 for ed25519, add_accumulator_secp256k1 for secp256k1 {
+    add_accumulator_sr25519 for sr25519, add_accumulator_ed25519 for ed25519, add_accumulator_secp256k1 for secp256k1, add_accumulator_secp256r1 for secp256r1 {
         {
             let b in 0 .. MAX_ACC;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
 
         let public = pair.public();
@@ -196,6 +205,7 @@ crate::bench_with_all_pairs! {
         });
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -243,7 +253,7 @@ crate::bench_with_all_pairs! {
         assert_eq!(Accumulators::<T>::get(acc_id).unwrap().accumulator, accumulator);
     }
 
-    update_accumulator_sr25519 for sr25519, update_accumulator_ed25519 for ed25519, update_accumulator_secp256k1 for secp256k1 {
+    update_accumulator_sr25519 for sr25519, update_accumulator_ed25519 for ed25519, update_accumulator_secp256k1 for secp256k1, update_accumulator_secp256r1 for secp256r1 {
         {
             let a in 0 .. MAX_ACC;
             let b in 0 .. 30;
@@ -254,6 +264,7 @@ crate::bench_with_all_pairs! {
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
 
         let public = pair.public();
@@ -263,6 +274,7 @@ crate::bench_with_all_pairs! {
         });
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -323,10 +335,11 @@ crate::bench_with_all_pairs! {
         assert_eq!(Accumulators::<T>::get(acc_id).unwrap().accumulator.accumulated(), new_accumulated);
     }
 
-    remove_accumulator_sr25519 for sr25519, remove_accumulator_ed25519 for ed25519, remove_accumulator_secp256k1 for secp256k1 {
+    remove_accumulator_sr25519 for sr25519, remove_accumulator_ed25519 for ed25519, remove_accumulator_secp256k1 for secp256k1, remove_accumulator_secp256r1 for secp256r1 {
 
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
@@ -336,6 +349,7 @@ crate::bench_with_all_pairs! {
         });
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
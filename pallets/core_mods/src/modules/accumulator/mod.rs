@@ -3,7 +3,7 @@ use crate::{
     did::{Did, DidSignature},
     keys_and_sigs::SigValue,
     types::CurveType,
-    util::IncId,
+    util::{IncId, WithDeadline},
     StorageVersion,
 };
 pub use actions::*;
@@ -217,6 +217,104 @@ decl_module! {
             did::Module::<T>::try_exec_signed_action_from_onchain_did(Self::remove_accumulator_, remove, signature)
         }
 
+        /// Same as `add_params` but protected from replays by the supplied replay id and expiry block instead of
+        /// the signer's nonce.
+        #[weight = SubstrateWeight::<T>::add_params(&params.action, &signature)
+            .saturating_add(WithDeadline::<T, AddAccumulatorParams<T>>::replay_protection_weight())]
+        pub fn add_params_with_deadline(
+            origin,
+            params: WithDeadline<T, AddAccumulatorParams<T>>,
+            signature: DidSignature<AccumulatorOwner>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(Self::add_params_, params, signature)
+        }
+
+        /// Same as `add_public_key` but protected from replays by the supplied replay id and expiry block instead of
+        /// the signer's nonce.
+        #[weight = SubstrateWeight::<T>::add_public(&public_key.action, &signature)
+            .saturating_add(WithDeadline::<T, AddAccumulatorPublicKey<T>>::replay_protection_weight())]
+        pub fn add_public_key_with_deadline(
+            origin,
+            public_key: WithDeadline<T, AddAccumulatorPublicKey<T>>,
+            signature: DidSignature<AccumulatorOwner>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(Self::add_public_key_, public_key, signature)
+        }
+
+        /// Same as `remove_params` but protected from replays by the supplied replay id and expiry block instead of
+        /// the signer's nonce.
+        #[weight = SubstrateWeight::<T>::remove_params(&remove.action, &signature)
+            .saturating_add(WithDeadline::<T, RemoveAccumulatorParams<T>>::replay_protection_weight())]
+        pub fn remove_params_with_deadline(
+            origin,
+            remove: WithDeadline<T, RemoveAccumulatorParams<T>>,
+            signature: DidSignature<AccumulatorOwner>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(Self::remove_params_, remove, signature)
+        }
+
+        /// Same as `remove_public_key` but protected from replays by the supplied replay id and expiry block instead of
+        /// the signer's nonce.
+        #[weight = SubstrateWeight::<T>::remove_public(&remove.action, &signature)
+            .saturating_add(WithDeadline::<T, RemoveAccumulatorPublicKey<T>>::replay_protection_weight())]
+        pub fn remove_public_key_with_deadline(
+            origin,
+            remove: WithDeadline<T, RemoveAccumulatorPublicKey<T>>,
+            signature: DidSignature<AccumulatorOwner>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(Self::remove_public_key_, remove, signature)
+        }
+
+        /// Same as `add_accumulator` but protected from replays by the supplied replay id and expiry block instead of
+        /// the signer's nonce.
+        #[weight = SubstrateWeight::<T>::add_accumulator(&add_accumulator.action, &signature)
+            .saturating_add(WithDeadline::<T, AddAccumulator<T>>::replay_protection_weight())]
+        pub fn add_accumulator_with_deadline(
+            origin,
+            add_accumulator: WithDeadline<T, AddAccumulator<T>>,
+            signature: DidSignature<AccumulatorOwner>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(Self::add_accumulator_, add_accumulator, signature)
+        }
+
+        /// Same as `update_accumulator` but protected from replays by the supplied replay id and expiry block instead of
+        /// the signer's nonce.
+        #[weight = SubstrateWeight::<T>::update_accumulator(&update.action, &signature)
+            .saturating_add(WithDeadline::<T, UpdateAccumulator<T>>::replay_protection_weight())]
+        pub fn update_accumulator_with_deadline(
+            origin,
+            update: WithDeadline<T, UpdateAccumulator<T>>,
+            signature: DidSignature<AccumulatorOwner>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(Self::update_accumulator_, update, signature)
+        }
+
+        /// Same as `remove_accumulator` but protected from replays by the supplied replay id and expiry block instead of
+        /// the signer's nonce.
+        #[weight = SubstrateWeight::<T>::remove_accumulator(&remove.action, &signature)
+            .saturating_add(WithDeadline::<T, RemoveAccumulator<T>>::replay_protection_weight())]
+        pub fn remove_accumulator_with_deadline(
+            origin,
+            remove: WithDeadline<T, RemoveAccumulator<T>>,
+            signature: DidSignature<AccumulatorOwner>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(Self::remove_accumulator_, remove, signature)
+        }
+
         fn on_runtime_upgrade() -> Weight {
             T::DbWeight::get().reads(1) + if Self::version() == StorageVersion::SingleKey {
                 let weight = crate::migrations::accumulator::single_key::migrate_to_multi_key::<T>();
@@ -238,7 +336,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::add_params_sr25519,
             SigValue::Ed25519(_) => Self::add_params_ed25519,
-            SigValue::Secp256k1(_) => Self::add_params_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::add_params_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::add_params_secp256r1,
         })(
             add_params.params.bytes.len() as u32,
             add_params.params.label.as_ref().map_or(0, |v| v.len()) as u32,
@@ -252,7 +351,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::add_public_sr25519,
             SigValue::Ed25519(_) => Self::add_public_ed25519,
-            SigValue::Secp256k1(_) => Self::add_public_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::add_public_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::add_public_secp256r1,
         })(public_key.public_key.bytes.len() as u32)
     }
 
@@ -263,7 +363,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::remove_params_sr25519,
             SigValue::Ed25519(_) => Self::remove_params_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_params_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::remove_params_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::remove_params_secp256r1,
         })()
     }
 
@@ -274,7 +375,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::remove_public_sr25519,
             SigValue::Ed25519(_) => Self::remove_public_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_public_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::remove_public_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::remove_public_secp256r1,
         })()
     }
 
@@ -285,7 +387,10 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::add_accumulator_sr25519,
             SigValue::Ed25519(_) => Self::add_accumulator_ed25519,
-            SigValue::Secp256k1(_) => Self::add_accumulator_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                Self::add_accumulator_secp256k1
+            }
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::add_accumulator_secp256r1,
         })(acc.accumulator.accumulated().len() as u32)
     }
 
@@ -296,7 +401,10 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::remove_accumulator_sr25519,
             SigValue::Ed25519(_) => Self::remove_accumulator_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_accumulator_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                Self::remove_accumulator_secp256k1
+            }
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::remove_accumulator_secp256r1,
         })()
     }
 
@@ -307,7 +415,10 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::update_accumulator_sr25519,
             SigValue::Ed25519(_) => Self::update_accumulator_ed25519,
-            SigValue::Secp256k1(_) => Self::update_accumulator_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                Self::update_accumulator_secp256k1
+            }
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::update_accumulator_secp256r1,
         })(
             acc.new_accumulated.len() as u32,
             acc.additions.as_ref().map_or(0, |v| v.len()) as u32,
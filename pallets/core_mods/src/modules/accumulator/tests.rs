@@ -564,3 +564,153 @@ fn add_remove_accumulator() {
         )));
     });
 }
+
+#[test]
+fn add_remove_accumulator_with_deadline() {
+    ext().execute_with(|| {
+        run_to_block(10);
+
+        let (author, author_kp) = newdid();
+        let author = AccumulatorOwner(author);
+
+        // Params, keys and accumulators signed in parallel are managed without touching the nonce
+        let params = AccumulatorParameters {
+            label: None,
+            curve_type: CurveType::Bls12381,
+            bytes: vec![1; 100],
+        };
+        let ap = WithDeadline::new(
+            AddAccumulatorParams {
+                params: params.clone(),
+                nonce: 0,
+            },
+            [1; 32],
+            15,
+        );
+        let ap_sig = did_sig::<Test, _, _>(&ap, &author_kp, author, 1);
+        AccumMod::add_params_with_deadline(Origin::signed(1), ap.clone(), ap_sig.clone()).unwrap();
+        assert_eq!(
+            AccumulatorParams::get(&author, IncId::from(1u8)),
+            Some(params)
+        );
+
+        let key = AccumulatorPublicKey {
+            params_ref: None,
+            curve_type: CurveType::Bls12381,
+            bytes: vec![2; 100],
+        };
+        let ak = WithDeadline::new(
+            AddAccumulatorPublicKey {
+                public_key: key.clone(),
+                nonce: 0,
+            },
+            [2; 32],
+            15,
+        );
+        let sig = did_sig::<Test, _, _>(&ak, &author_kp, author, 1);
+        AccumMod::add_public_key_with_deadline(Origin::signed(1), ak, sig).unwrap();
+        assert_eq!(AccumulatorKeys::get(&author, IncId::from(1u8)), Some(key));
+
+        let id = AccumulatorId(rand::random());
+        let add_accum = WithDeadline::new(
+            AddAccumulator {
+                id,
+                accumulator: Accumulator::Positive(AccumulatorCommon {
+                    accumulated: vec![3; 32],
+                    key_ref: (author, 1u8.into()),
+                }),
+                nonce: 0,
+            },
+            [3; 32],
+            15,
+        );
+        let sig = did_sig::<Test, _, _>(&add_accum, &author_kp, author, 1);
+        AccumMod::add_accumulator_with_deadline(Origin::signed(1), add_accum, sig).unwrap();
+
+        let update_accum = WithDeadline::new(
+            UpdateAccumulator {
+                id,
+                new_accumulated: vec![4; 32],
+                additions: Some(vec![vec![0, 1, 2]]),
+                removals: None,
+                witness_update_info: Some(vec![1, 2, 3]),
+                nonce: 0,
+            },
+            [4; 32],
+            15,
+        );
+        let update_sig = did_sig::<Test, _, _>(&update_accum, &author_kp, author, 1);
+        AccumMod::update_accumulator_with_deadline(
+            Origin::signed(1),
+            update_accum.clone(),
+            update_sig.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            Accumulators::<Test>::get(&id)
+                .unwrap()
+                .accumulator
+                .accumulated(),
+            &[4; 32][..]
+        );
+        check_nonce(&author, 10);
+
+        // Replayed actions are rejected
+        assert_err!(
+            AccumMod::add_params_with_deadline(Origin::signed(1), ap, ap_sig),
+            did::Error::<Test>::ReplayIdAlreadyUsed
+        );
+        assert_err!(
+            AccumMod::update_accumulator_with_deadline(Origin::signed(1), update_accum, update_sig),
+            did::Error::<Test>::ReplayIdAlreadyUsed
+        );
+
+        // Actions expiring too far in the future are rejected
+        let rem_accum = WithDeadline::new(
+            RemoveAccumulator { id, nonce: 0 },
+            [5; 32],
+            10 + MaxActionLifetime::get() + 1,
+        );
+        let sig = did_sig::<Test, _, _>(&rem_accum, &author_kp, author, 1);
+        assert_err!(
+            AccumMod::remove_accumulator_with_deadline(Origin::signed(1), rem_accum, sig),
+            did::Error::<Test>::ActionLifetimeTooLong
+        );
+
+        let rem_accum = WithDeadline::new(RemoveAccumulator { id, nonce: 0 }, [5; 32], 15);
+        let sig = did_sig::<Test, _, _>(&rem_accum, &author_kp, author, 1);
+        AccumMod::remove_accumulator_with_deadline(Origin::signed(1), rem_accum, sig).unwrap();
+        assert_eq!(Accumulators::<Test>::get(&id), None);
+
+        let rem_key = WithDeadline::new(
+            RemoveAccumulatorPublicKey {
+                key_ref: (author, 1u8.into()),
+                nonce: 0,
+            },
+            [6; 32],
+            15,
+        );
+        let sig = did_sig::<Test, _, _>(&rem_key, &author_kp, author, 1);
+        AccumMod::remove_public_key_with_deadline(Origin::signed(1), rem_key, sig).unwrap();
+        assert_eq!(AccumulatorKeys::get(&author, IncId::from(1u8)), None);
+        check_nonce(&author, 10);
+
+        // Expired actions are rejected
+        run_to_block(16);
+
+        let rem_params = WithDeadline::new(
+            RemoveAccumulatorParams {
+                params_ref: (author, 1u8.into()),
+                nonce: 0,
+            },
+            [7; 32],
+            15,
+        );
+        let sig = did_sig::<Test, _, _>(&rem_params, &author_kp, author, 1);
+        assert_err!(
+            AccumMod::remove_params_with_deadline(Origin::signed(1), rem_params, sig),
+            did::Error::<Test>::ActionExpired
+        );
+        assert!(AccumulatorParams::get(&author, IncId::from(1u8)).is_some());
+    });
+}
@@ -31,24 +31,31 @@ pub trait WeightInfo {
     fn add_params_sr25519(b: u32, l: u32) -> Weight;
     fn add_params_ed25519(b: u32, l: u32) -> Weight;
     fn add_params_secp256k1(b: u32, l: u32) -> Weight;
+    fn add_params_secp256r1(b: u32, l: u32) -> Weight;
     fn remove_params_sr25519() -> Weight;
     fn remove_params_ed25519() -> Weight;
     fn remove_params_secp256k1() -> Weight;
+    fn remove_params_secp256r1() -> Weight;
     fn add_public_sr25519(b: u32) -> Weight;
     fn add_public_ed25519(b: u32) -> Weight;
     fn add_public_secp256k1(b: u32) -> Weight;
+    fn add_public_secp256r1(b: u32) -> Weight;
     fn remove_public_sr25519() -> Weight;
     fn remove_public_ed25519() -> Weight;
     fn remove_public_secp256k1() -> Weight;
+    fn remove_public_secp256r1() -> Weight;
     fn add_accumulator_sr25519(b: u32) -> Weight;
     fn add_accumulator_ed25519(b: u32) -> Weight;
     fn add_accumulator_secp256k1(b: u32) -> Weight;
+    fn add_accumulator_secp256r1(b: u32) -> Weight;
     fn update_accumulator_sr25519(a: u32, b: u32, c: u32, d: u32, e: u32, f: u32) -> Weight;
     fn update_accumulator_ed25519(a: u32, b: u32, c: u32, d: u32, e: u32, f: u32) -> Weight;
     fn update_accumulator_secp256k1(a: u32, b: u32, c: u32, d: u32, e: u32, f: u32) -> Weight;
+    fn update_accumulator_secp256r1(a: u32, b: u32, c: u32, d: u32, e: u32, f: u32) -> Weight;
     fn remove_accumulator_sr25519() -> Weight;
     fn remove_accumulator_ed25519() -> Weight;
     fn remove_accumulator_secp256k1() -> Weight;
+    fn remove_accumulator_secp256r1() -> Weight;
 }
 
 /// Weights for accumulator using the Substrate node and recommended hardware.
@@ -79,6 +86,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn add_params_secp256r1(b: u32, l: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (159_890_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn remove_params_sr25519() -> Weight {
         (58_306_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
@@ -94,6 +109,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn remove_params_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (162_917_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
     fn add_public_sr25519(b: u32) -> Weight {
         (61_674_000 as Weight)
             // Standard Error: 0
@@ -113,6 +134,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn add_public_secp256r1(_b: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (167_138_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn remove_public_sr25519() -> Weight {
         (56_413_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
@@ -128,6 +155,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn remove_public_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (164_392_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
     fn add_accumulator_sr25519(b: u32) -> Weight {
         (63_558_000 as Weight)
             // Standard Error: 0
@@ -147,6 +180,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(5 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn add_accumulator_secp256r1(_b: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (167_711_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn update_accumulator_sr25519(a: u32, b: u32, c: u32, d: u32, e: u32, _f: u32) -> Weight {
         (46_170_000 as Weight)
             // Standard Error: 0
@@ -194,6 +233,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn update_accumulator_secp256r1(a: u32, b: u32, c: u32, d: u32, e: u32, _f: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (161_276_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((110_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((22_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add((93_000 as Weight).saturating_mul(d as Weight))
+            .saturating_add((18_000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn remove_accumulator_sr25519() -> Weight {
         (60_493_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
@@ -209,6 +259,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn remove_accumulator_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (167_644_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -238,6 +294,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn add_params_secp256r1(b: u32, l: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (159_890_000 as Weight)
+            .saturating_add((4_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn remove_params_sr25519() -> Weight {
         (58_306_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
@@ -253,6 +317,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
+    fn remove_params_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (162_917_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
     fn add_public_sr25519(b: u32) -> Weight {
         (61_674_000 as Weight)
             // Standard Error: 0
@@ -272,6 +342,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn add_public_secp256r1(_b: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (167_138_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn remove_public_sr25519() -> Weight {
         (56_413_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
@@ -287,6 +363,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
+    fn remove_public_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (164_392_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
     fn add_accumulator_sr25519(b: u32) -> Weight {
         (63_558_000 as Weight)
             // Standard Error: 0
@@ -306,6 +388,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(5 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn add_accumulator_secp256r1(_b: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (167_711_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn update_accumulator_sr25519(a: u32, b: u32, c: u32, d: u32, e: u32, _f: u32) -> Weight {
         (46_170_000 as Weight)
             // Standard Error: 0
@@ -353,6 +441,17 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn update_accumulator_secp256r1(a: u32, b: u32, c: u32, d: u32, e: u32, _f: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (161_276_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((110_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((22_000 as Weight).saturating_mul(c as Weight))
+            .saturating_add((93_000 as Weight).saturating_mul(d as Weight))
+            .saturating_add((18_000 as Weight).saturating_mul(e as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn remove_accumulator_sr25519() -> Weight {
         (60_493_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
@@ -368,4 +467,10 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn remove_accumulator_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (167_644_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
 }
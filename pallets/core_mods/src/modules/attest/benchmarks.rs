@@ -11,13 +11,14 @@ const MAX_LEN: u32 = 10_000;
 
 crate::bench_with_all_pairs! {
     with_pairs:
-    set_claim_sr25519 for sr25519, set_claim_ed25519 for ed25519, set_claim_secp256k1 for secp256k1 {
+    set_claim_sr25519 for sr25519, set_claim_ed25519 for ed25519, set_claim_secp256k1 for secp256k1, set_claim_secp256r1 for secp256r1 {
         {
             let l in 0 .. MAX_LEN => ();
         }
 
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let data = vec![0; l as usize];
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
@@ -33,6 +34,7 @@ crate::bench_with_all_pairs! {
         };
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
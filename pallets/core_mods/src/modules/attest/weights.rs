@@ -31,6 +31,7 @@ pub trait WeightInfo {
     fn set_claim_sr25519(l: u32) -> Weight;
     fn set_claim_ed25519(l: u32) -> Weight;
     fn set_claim_secp256k1(l: u32) -> Weight;
+    fn set_claim_secp256r1(l: u32) -> Weight;
 }
 
 /// Weights for attest using the Substrate node and recommended hardware.
@@ -57,6 +58,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn set_claim_secp256r1(l: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (152_813_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -82,4 +90,11 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
+    fn set_claim_secp256r1(l: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (152_813_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
 }
@@ -14,17 +14,19 @@ const MAX_KEY: u32 = 256;
 
 crate::bench_with_all_pairs! {
     with_pairs:
-    add_params_sr25519 for sr25519, add_params_ed25519 for ed25519, add_params_secp256k1 for secp256k1 {
+    add_params_sr25519 for sr25519, add_params_ed25519 for ed25519, add_params_secp256k1 for secp256k1, add_params_secp256r1 for secp256r1 {
         {
             let b in 0 .. MAX_PARAMS => ();
             let l in 1 .. MAX_LABEL => ();
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -47,14 +49,16 @@ crate::bench_with_all_pairs! {
         assert_eq!(BbsPlusParams::get(BBSPlusParamsOwner(did), IncId::from(1u8)).unwrap(), params.clone());
     }
 
-    remove_params_sr25519 for sr25519, remove_params_ed25519 for ed25519, remove_params_secp256k1 for secp256k1 {
+    remove_params_sr25519 for sr25519, remove_params_ed25519 for ed25519, remove_params_secp256k1 for secp256k1, remove_params_secp256r1 for secp256r1 {
 
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -85,16 +89,18 @@ crate::bench_with_all_pairs! {
         assert!(BbsPlusParams::get(BBSPlusParamsOwner(did), IncId::from(1u8)).is_none());
     }
 
-    add_public_sr25519 for sr25519, add_public_ed25519 for ed25519, add_public_secp256k1 for secp256k1 {
+    add_public_sr25519 for sr25519, add_public_ed25519 for ed25519, add_public_secp256k1 for secp256k1, add_public_secp256r1 for secp256r1 {
         {
             let b in 0 .. MAX_KEY;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -132,14 +138,16 @@ crate::bench_with_all_pairs! {
         assert_eq!(BbsPlusKeys::get(did, IncId::from(2u8)).unwrap(), key);
     }
 
-    remove_public_sr25519 for sr25519, remove_public_ed25519 for ed25519, remove_public_secp256k1 for secp256k1 {
+    remove_public_sr25519 for sr25519, remove_public_ed25519 for ed25519, remove_public_secp256k1 for secp256k1, remove_public_secp256r1 for secp256r1 {
 
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -2,6 +2,7 @@ use super::*;
 
 impl<T: Config + Debug> Module<T> {
     pub(super) fn add_params_(
+        depositor: &T::AccountId,
         AddBBSPlusParams { params, .. }: AddBBSPlusParams<T>,
         signer: BBSPlusParamsOwner,
     ) -> DispatchResult {
@@ -14,7 +15,14 @@ impl<T: Config + Debug> Module<T> {
             Error::<T>::ParamsTooBig
         );
 
+        did::Module::<T>::ensure_can_reserve_deposits(depositor, Some(params.encoded_size()))?;
+
         let params_count = ParamsCounter::mutate(signer, |counter| *counter.inc());
+        did::Module::<T>::reserve_deposit(
+            depositor,
+            did::DepositItem::BBSPlusParams(signer.0, params_count),
+            params.encoded_size(),
+        )?;
         BbsPlusParams::insert(signer, params_count, params);
 
         Self::deposit_event(Event::ParamsAdded(signer, params_count));
@@ -61,6 +69,7 @@ impl<T: Config + Debug> Module<T> {
         );
 
         BbsPlusParams::remove(&did, &counter);
+        did::Module::<T>::refund_deposit(&did::DepositItem::BBSPlusParams(did.0, counter));
 
         Self::deposit_event(Event::ParamsRemoved(did, counter));
         Ok(())
@@ -8,7 +8,7 @@ use crate::{
     did::{Controller, Did, DidSignature, OnChainDidDetails},
     keys_and_sigs::SigValue,
     types::CurveType,
-    util::IncId,
+    util::{IncId, WithDeadline},
     StorageVersion,
 };
 use codec::{Decode, Encode};
@@ -147,15 +147,21 @@ decl_module! {
         // Weights are not yet determined by benchmarks and thus ignore processing time and also event storage
         // cost
 
+        /// Add BBS+ params. The deposit for storing the params is reserved from the caller and refunded once
+        /// the params are removed.
         #[weight = SubstrateWeight::<T>::add_params(&params, signature)]
         pub fn add_params(
             origin,
             params: AddBBSPlusParams<T>,
             signature: DidSignature<BBSPlusParamsOwner>,
         ) -> DispatchResult {
-            ensure_signed(origin)?;
+            let caller = ensure_signed(origin)?;
 
-            did::Module::<T>::try_exec_signed_action_from_onchain_did(Self::add_params_, params, signature)
+            did::Module::<T>::try_exec_signed_action_from_onchain_did(
+                |params, signer| Self::add_params_(&caller, params, signer),
+                params,
+                signature,
+            )
         }
 
         /// Add a BBS+ public key. Only the DID controller can add key and it should use the nonce from the DID module.
@@ -196,6 +202,66 @@ decl_module! {
             <did::Module<T>>::try_exec_signed_action_from_controller(Self::remove_public_key_, remove, signature)
         }
 
+        /// Same as `add_params` but protected from replays by the supplied replay id and expiry block instead
+        /// of the signer's nonce.
+        #[weight = SubstrateWeight::<T>::add_params(&params.action, signature)
+            .saturating_add(WithDeadline::<T, AddBBSPlusParams<T>>::replay_protection_weight())]
+        pub fn add_params_with_deadline(
+            origin,
+            params: WithDeadline<T, AddBBSPlusParams<T>>,
+            signature: DidSignature<BBSPlusParamsOwner>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(
+                |params, signer| Self::add_params_(&caller, params, signer),
+                params,
+                signature,
+            )
+        }
+
+        /// Same as `add_public_key` but protected from replays by the supplied replay id and expiry block
+        /// instead of the DID's nonce.
+        #[weight = SubstrateWeight::<T>::add_public(&public_key.action, signature)
+            .saturating_add(WithDeadline::<T, AddBBSPlusPublicKey<T>>::replay_protection_weight())]
+        pub fn add_public_key_with_deadline(
+            origin,
+            public_key: WithDeadline<T, AddBBSPlusPublicKey<T>>,
+            signature: DidSignature<Controller>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            <did::Module<T>>::try_exec_signed_action_with_deadline_from_controller(Self::add_public_key_, public_key, signature)
+        }
+
+        /// Same as `remove_params` but protected from replays by the supplied replay id and expiry block instead
+        /// of the signer's nonce.
+        #[weight = SubstrateWeight::<T>::remove_params(&remove.action, signature)
+            .saturating_add(WithDeadline::<T, RemoveBBSPlusParams<T>>::replay_protection_weight())]
+        pub fn remove_params_with_deadline(
+            origin,
+            remove: WithDeadline<T, RemoveBBSPlusParams<T>>,
+            signature: DidSignature<BBSPlusParamsOwner>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(Self::remove_params_, remove, signature)
+        }
+
+        /// Same as `remove_public_key` but protected from replays by the supplied replay id and expiry block
+        /// instead of the DID's nonce.
+        #[weight = SubstrateWeight::<T>::remove_public(&remove.action, signature)
+            .saturating_add(WithDeadline::<T, RemoveBBSPlusPublicKey<T>>::replay_protection_weight())]
+        pub fn remove_public_key_with_deadline(
+            origin,
+            remove: WithDeadline<T, RemoveBBSPlusPublicKey<T>>,
+            signature: DidSignature<Controller>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            <did::Module<T>>::try_exec_signed_action_with_deadline_from_controller(Self::remove_public_key_, remove, signature)
+        }
+
         fn on_runtime_upgrade() -> Weight {
             T::DbWeight::get().reads(1) + if Self::version() == StorageVersion::SingleKey {
                 let weight = crate::migrations::bbs_plus::single_key::migrate_to_multi_key::<T>();
@@ -217,7 +283,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::add_params_sr25519,
             SigValue::Ed25519(_) => Self::add_params_ed25519,
-            SigValue::Secp256k1(_) => Self::add_params_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::add_params_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::add_params_secp256r1,
         })(
             add_params.params.bytes.len() as u32,
             add_params.params.label.as_ref().map_or(0, |v| v.len()) as u32,
@@ -231,7 +298,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::add_public_sr25519,
             SigValue::Ed25519(_) => Self::add_public_ed25519,
-            SigValue::Secp256k1(_) => Self::add_public_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::add_public_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::add_public_secp256r1,
         })(public_key.key.bytes.len() as u32)
     }
 
@@ -242,7 +310,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::remove_params_sr25519,
             SigValue::Ed25519(_) => Self::remove_params_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_params_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::remove_params_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::remove_params_secp256r1,
         })()
     }
 
@@ -253,7 +322,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::remove_public_sr25519,
             SigValue::Ed25519(_) => Self::remove_public_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_public_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::remove_public_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::remove_public_secp256r1,
         })()
     }
 }
@@ -923,7 +923,7 @@ fn add_remove_public_key_by_controller() {
             nonce: next_nonce_1,
         };
         let sig = did_sig::<_, _, _>(&add_controllers, &did_1_kp, Controller(did_1.clone()), 1);
-        DIDModule::add_controllers(Origin::signed(1), add_controllers, sig).unwrap();
+        DIDModule::add_controllers(Origin::signed(1), add_controllers, vec![sig]).unwrap();
         assert!(DIDModule::is_controller(&did_1, &Controller(did.clone())));
         check_did_detail(&did_1, 1, 1, 2, next_nonce_1);
         check_did_detail(&did, 1, 1, 1, next_nonce - 1);
@@ -1402,3 +1402,127 @@ fn get_params_and_keys() {
         });
     });
 }
+
+#[test]
+fn add_remove_with_deadline() {
+    ext().execute_with(|| {
+        run_to_block(10);
+
+        let (author, author_kp) = newdid();
+
+        // Params and keys signed in parallel are managed without touching the nonce
+        let params = BBSPlusParameters {
+            label: None,
+            curve_type: CurveType::Bls12381,
+            bytes: vec![1u8; 100],
+        };
+        let ap = WithDeadline::new(
+            AddBBSPlusParams {
+                params: params.clone(),
+                nonce: 0,
+            },
+            [1; 32],
+            15,
+        );
+        let ap_sig = did_sig::<Test, _, _>(&ap, &author_kp, BBSPlusParamsOwner(author), 1);
+        BBSPlusMod::add_params_with_deadline(Origin::signed(1), ap.clone(), ap_sig.clone())
+            .unwrap();
+        assert_eq!(
+            BbsPlusParams::get(&BBSPlusParamsOwner(author), IncId::from(1u8)),
+            Some(params)
+        );
+
+        let key = BBSPlusPublicKey {
+            params_ref: None,
+            curve_type: CurveType::Bls12381,
+            bytes: vec![1u8; 100],
+        };
+        let ak = WithDeadline::new(
+            AddBBSPlusPublicKey {
+                key: key.clone(),
+                did: author,
+                nonce: 0,
+            },
+            [2; 32],
+            15,
+        );
+        let ak_sig = did_sig::<Test, _, _>(&ak, &author_kp, Controller(author), 1);
+        BBSPlusMod::add_public_key_with_deadline(Origin::signed(1), ak.clone(), ak_sig.clone())
+            .unwrap();
+        assert_eq!(BbsPlusKeys::get(&author, IncId::from(2u8)), Some(key));
+        check_nonce(&author, 10);
+
+        // Replayed actions are rejected
+        assert_err!(
+            BBSPlusMod::add_params_with_deadline(Origin::signed(1), ap, ap_sig),
+            did::Error::<Test>::ReplayIdAlreadyUsed
+        );
+        assert_err!(
+            BBSPlusMod::add_public_key_with_deadline(Origin::signed(1), ak, ak_sig),
+            did::Error::<Test>::ReplayIdAlreadyUsed
+        );
+
+        // Actions expiring too far in the future are rejected
+        let rk = WithDeadline::new(
+            RemoveBBSPlusPublicKey {
+                key_ref: (author, 2u8.into()),
+                did: author,
+                nonce: 0,
+            },
+            [3; 32],
+            10 + MaxActionLifetime::get() + 1,
+        );
+        let sig = did_sig::<Test, _, _>(&rk, &author_kp, Controller(author), 1);
+        assert_err!(
+            BBSPlusMod::remove_public_key_with_deadline(Origin::signed(1), rk, sig),
+            did::Error::<Test>::ActionLifetimeTooLong
+        );
+
+        let rk = WithDeadline::new(
+            RemoveBBSPlusPublicKey {
+                key_ref: (author, 2u8.into()),
+                did: author,
+                nonce: 0,
+            },
+            [3; 32],
+            15,
+        );
+        let sig = did_sig::<Test, _, _>(&rk, &author_kp, Controller(author), 1);
+        BBSPlusMod::remove_public_key_with_deadline(Origin::signed(1), rk, sig).unwrap();
+        assert_eq!(BbsPlusKeys::get(&author, IncId::from(2u8)), None);
+        check_nonce(&author, 10);
+
+        // Expired actions are rejected
+        run_to_block(16);
+
+        let rp = WithDeadline::new(
+            RemoveBBSPlusParams {
+                params_ref: (BBSPlusParamsOwner(author), 1u8.into()),
+                nonce: 0,
+            },
+            [4; 32],
+            15,
+        );
+        let sig = did_sig::<Test, _, _>(&rp, &author_kp, BBSPlusParamsOwner(author), 1);
+        assert_err!(
+            BBSPlusMod::remove_params_with_deadline(Origin::signed(1), rp, sig),
+            did::Error::<Test>::ActionExpired
+        );
+
+        let rp = WithDeadline::new(
+            RemoveBBSPlusParams {
+                params_ref: (BBSPlusParamsOwner(author), 1u8.into()),
+                nonce: 0,
+            },
+            [4; 32],
+            20,
+        );
+        let sig = did_sig::<Test, _, _>(&rp, &author_kp, BBSPlusParamsOwner(author), 1);
+        BBSPlusMod::remove_params_with_deadline(Origin::signed(1), rp, sig).unwrap();
+        assert_eq!(
+            BbsPlusParams::get(&BBSPlusParamsOwner(author), IncId::from(1u8)),
+            None
+        );
+        check_nonce(&author, 10);
+    });
+}
@@ -31,15 +31,19 @@ pub trait WeightInfo {
     fn add_params_sr25519(b: u32, l: u32) -> Weight;
     fn add_params_ed25519(b: u32, l: u32) -> Weight;
     fn add_params_secp256k1(b: u32, l: u32) -> Weight;
+    fn add_params_secp256r1(b: u32, l: u32) -> Weight;
     fn remove_params_sr25519() -> Weight;
     fn remove_params_ed25519() -> Weight;
     fn remove_params_secp256k1() -> Weight;
+    fn remove_params_secp256r1() -> Weight;
     fn add_public_sr25519(b: u32) -> Weight;
     fn add_public_ed25519(b: u32) -> Weight;
     fn add_public_secp256k1(b: u32) -> Weight;
+    fn add_public_secp256r1(b: u32) -> Weight;
     fn remove_public_sr25519() -> Weight;
     fn remove_public_ed25519() -> Weight;
     fn remove_public_secp256k1() -> Weight;
+    fn remove_public_secp256r1() -> Weight;
 }
 
 /// Weights for bbs_plus using the Substrate node and recommended hardware.
@@ -72,6 +76,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn add_params_secp256r1(b: u32, l: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (154_268_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn remove_params_sr25519() -> Weight {
         (56_041_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
@@ -87,6 +99,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn remove_params_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (155_224_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
     fn add_public_sr25519(b: u32) -> Weight {
         (59_312_000 as Weight)
             // Standard Error: 0
@@ -106,6 +124,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn add_public_secp256r1(_b: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (162_846_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
     fn remove_public_sr25519() -> Weight {
         (59_284_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
@@ -121,6 +145,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn remove_public_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (161_804_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -152,6 +182,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn add_params_secp256r1(b: u32, l: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (154_268_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(b as Weight))
+            .saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn remove_params_sr25519() -> Weight {
         (56_041_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
@@ -167,6 +205,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
+    fn remove_params_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (155_224_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
     fn add_public_sr25519(b: u32) -> Weight {
         (59_312_000 as Weight)
             // Standard Error: 0
@@ -186,6 +230,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
+    fn add_public_secp256r1(_b: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (162_846_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
     fn remove_public_sr25519() -> Weight {
         (59_284_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
@@ -201,4 +251,10 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
+    fn remove_public_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (161_804_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
 }
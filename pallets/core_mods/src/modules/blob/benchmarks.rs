@@ -8,17 +8,18 @@ const MAX_BLOB: u32 = 100;
 
 crate::bench_with_all_pairs! {
     with_pairs:
-    new_sr25519 for sr25519, new_ed25519 for ed25519, new_secp256k1 for secp256k1 {
+    new_sr25519 for sr25519, new_ed25519 for ed25519, new_secp256k1 for secp256k1, new_secp256r1 for secp256r1 {
         {
             let s in 0 .. MAX_BLOB;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        did::Module::<T>::fund_for_deposits(&caller);
         let n = 0;
         let public = pair.public();
         let did = Did([1; Did::BYTE_SIZE]);
 
-        did::Module::<T>::new_onchain_(did, vec![DidKey::new_with_all_relationships(public)], Default::default()).unwrap();
+        did::Module::<T>::new_onchain_(caller.clone(), did, vec![DidKey::new_with_all_relationships(public)], Default::default()).unwrap();
         let id = Default::default();
 
         let blob = Blob {
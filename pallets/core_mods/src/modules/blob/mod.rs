@@ -5,6 +5,7 @@ use crate::{
     did,
     did::{Did, DidSignature},
     keys_and_sigs::SigValue,
+    util::WithDeadline,
 };
 use alloc::vec::Vec;
 use codec::{Decode, Encode};
@@ -89,22 +90,48 @@ decl_module! {
 
         const StorageWeight: Weight = T::StorageWeight::get();
 
-        /// Create a new immutable blob.
+        /// Create a new immutable blob. The deposit for storing the blob is reserved from the caller.
         #[weight = SubstrateWeight::<T>::new(&blob, &signature)]
         pub fn new(
             origin,
             blob: AddBlob<T>,
             signature: DidSignature<BlobOwner>,
         ) -> DispatchResult {
-            ensure_signed(origin)?;
+            let caller = ensure_signed(origin)?;
 
-            did::Module::<T>::try_exec_signed_action_from_onchain_did(Self::new_, blob, signature)
+            did::Module::<T>::try_exec_signed_action_from_onchain_did(
+                |blob, signer| Self::new_(&caller, blob, signer),
+                blob,
+                signature,
+            )
+        }
+
+        /// Create a new immutable blob protected from replays by the supplied replay id and expiry block
+        /// instead of the signer's nonce.
+        #[weight = SubstrateWeight::<T>::new(&blob.action, &signature)
+            .saturating_add(WithDeadline::<T, AddBlob<T>>::replay_protection_weight())]
+        pub fn new_with_deadline(
+            origin,
+            blob: WithDeadline<T, AddBlob<T>>,
+            signature: DidSignature<BlobOwner>,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            did::Module::<T>::try_exec_signed_action_with_deadline_from_onchain_did(
+                |blob, signer| Self::new_(&caller, blob, signer),
+                blob,
+                signature,
+            )
         }
     }
 }
 
 impl<T: Config + Debug> Module<T> {
-    fn new_(AddBlob { blob, .. }: AddBlob<T>, signer: BlobOwner) -> DispatchResult {
+    fn new_(
+        depositor: &T::AccountId,
+        AddBlob { blob, .. }: AddBlob<T>,
+        signer: BlobOwner,
+    ) -> DispatchResult {
         // check
         ensure!(
             T::MaxBlobSize::get() as usize >= blob.blob.len(),
@@ -116,7 +143,13 @@ impl<T: Config + Debug> Module<T> {
         );
 
         // execute
-        Blobs::insert(blob.id, (signer, blob.blob));
+        let entry = (signer, blob.blob);
+        did::Module::<T>::reserve_deposit(
+            depositor,
+            did::DepositItem::Blob(blob.id),
+            entry.encoded_size(),
+        )?;
+        Blobs::insert(blob.id, entry);
 
         Ok(())
     }
@@ -130,7 +163,8 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
         (match sig {
             SigValue::Sr25519(_) => Self::new_sr25519,
             SigValue::Ed25519(_) => Self::new_ed25519,
-            SigValue::Secp256k1(_) => Self::new_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::new_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::new_secp256r1,
         })(blob.blob.len() as u32)
     }
 }
@@ -1,5 +1,5 @@
 use super::{did, Blob, BlobError, BlobId, BlobOwner, Blobs, DispatchResult};
-use crate::{blob::AddBlob, did::Did, test_common::*};
+use crate::{blob::AddBlob, did::Did, test_common::*, util::WithDeadline};
 use frame_support::StorageMap;
 use sp_core::{sr25519, Pair};
 
@@ -65,6 +65,65 @@ fn add_blob() {
     });
 }
 
+#[test]
+fn add_blob_with_deadline() {
+    ext().execute_with(|| {
+        run_to_block(10);
+
+        let (author, author_kp) = newdid();
+        let new_blob = |replay_id, expires_at| {
+            WithDeadline::new(
+                AddBlob {
+                    blob: Blob {
+                        id: rand::random(),
+                        blob: random_bytes(10),
+                    },
+                    nonce: 0,
+                },
+                replay_id,
+                expires_at,
+            )
+        };
+
+        // Blobs signed in parallel can be added in any order and the nonce isn't used
+        let first = new_blob([1; 32], 15);
+        let second = new_blob([2; 32], 15);
+        for blob in vec![second, first.clone()] {
+            let sig = did_sig::<Test, _, _>(&blob, &author_kp, BlobOwner(author), 1);
+            BlobMod::new_with_deadline(Origin::signed(ABBA), blob.clone(), sig).unwrap();
+            assert_eq!(
+                Blobs::get(blob.action.blob.id),
+                Some((BlobOwner(author), blob.action.blob.blob))
+            );
+        }
+        check_nonce(&author, 10);
+
+        // Replay id can't be reused by the same DID but can be used by another one
+        let mut reused = new_blob([1; 32], 15);
+        let sig = did_sig::<Test, _, _>(&reused, &author_kp, BlobOwner(author), 1);
+        let err =
+            BlobMod::new_with_deadline(Origin::signed(ABBA), reused.clone(), sig).unwrap_err();
+        assert_eq!(err, did::Error::<Test>::ReplayIdAlreadyUsed.into());
+
+        let (other, other_kp) = newdid();
+        reused.action.blob.id = rand::random();
+        let sig = did_sig::<Test, _, _>(&reused, &other_kp, BlobOwner(other), 1);
+        BlobMod::new_with_deadline(Origin::signed(ABBA), reused, sig).unwrap();
+
+        // Nonce-protected payload can't be submitted as the one with deadline
+        let blob = new_blob([3; 32], 15);
+        let sig = did_sig::<Test, _, _>(&blob.action, &author_kp, BlobOwner(author), 1);
+        let err = BlobMod::new_with_deadline(Origin::signed(ABBA), blob, sig).unwrap_err();
+        assert_eq!(err, did::Error::<Test>::InvalidSignature.into());
+
+        run_to_block(16);
+        let blob = new_blob([4; 32], 15);
+        let sig = did_sig::<Test, _, _>(&blob, &author_kp, BlobOwner(author), 1);
+        let err = BlobMod::new_with_deadline(Origin::signed(ABBA), blob, sig).unwrap_err();
+        assert_eq!(err, did::Error::<Test>::ActionExpired.into());
+    });
+}
+
 #[test]
 fn err_blob_too_big() {
     fn add_too_big(size: usize, block_no: u64) {
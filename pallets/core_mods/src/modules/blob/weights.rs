@@ -31,6 +31,7 @@ pub trait WeightInfo {
     fn new_sr25519(s: u32) -> Weight;
     fn new_ed25519(s: u32) -> Weight;
     fn new_secp256k1(s: u32) -> Weight;
+    fn new_secp256r1(s: u32) -> Weight;
 }
 
 /// Weights for blob using the Substrate node and recommended hardware.
@@ -57,6 +58,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
     }
+    fn new_secp256r1(s: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (152_477_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -82,4 +90,11 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
     }
+    fn new_secp256r1(s: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (152_477_000 as Weight)
+            .saturating_add((2_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
 }
@@ -0,0 +1,51 @@
+use super::*;
+
+impl<T: Config + Debug> Module<T> {
+    pub(crate) fn link_account_(
+        LinkAccount { did, account, .. }: LinkAccount<T>,
+        _: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        ensure!(
+            !DidLinkedAccounts::<T>::contains_key(did, &account),
+            Error::<T>::AccountAlreadyLinked
+        );
+
+        DidLinkedAccounts::<T>::insert(did, &account, ());
+        AccountDids::<T>::insert(account, did, ());
+
+        deposit_indexed_event!(DidAccountLinked(did));
+        Ok(())
+    }
+
+    pub(crate) fn unlink_account_(
+        UnlinkAccount { did, account, .. }: UnlinkAccount<T>,
+        _: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        DidLinkedAccounts::<T>::take(did, &account).ok_or(Error::<T>::AccountNotLinked)?;
+        AccountDids::<T>::remove(account, did);
+
+        deposit_indexed_event!(DidAccountUnlinked(did));
+        Ok(())
+    }
+
+    /// Removes links between the removed on-chain DID and its accounts.
+    pub(crate) fn unlink_all_accounts(did: &Did) {
+        for (account, _) in DidLinkedAccounts::<T>::drain_prefix(did) {
+            AccountDids::<T>::remove(account, did);
+        }
+    }
+
+    /// Returns accounts linked to the on-chain DID.
+    pub fn linked_accounts(did: &Did) -> Vec<T::AccountId> {
+        DidLinkedAccounts::<T>::iter_prefix(did)
+            .map(|(account, _)| account)
+            .collect()
+    }
+
+    /// Returns on-chain DIDs linked to the account along with off-chain DIDs owned by it.
+    pub fn dids_by_account(account: &T::AccountId) -> Vec<Did> {
+        AccountDids::<T>::iter_prefix(account)
+            .map(|(did, _)| did)
+            .collect()
+    }
+}
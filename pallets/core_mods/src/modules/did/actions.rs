@@ -19,6 +19,20 @@ pub struct RemoveKeys<T: frame_system::Config> {
     pub nonce: T::BlockNumber,
 }
 
+/// Atomically removes keys with given ids and adds new keys, so the DID is never left with
+/// both old and new keys or with neither of them.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct RotateKeys<T: frame_system::Config> {
+    pub did: Did,
+    /// Key ids to remove
+    pub remove: BTreeSet<IncId>,
+    /// Keys to add
+    pub add: Vec<DidKey>,
+    pub nonce: T::BlockNumber,
+}
+
 #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
@@ -60,6 +74,153 @@ pub struct RemoveServiceEndpoint<T: frame_system::Config> {
     pub nonce: T::BlockNumber,
 }
 
+/// Replaces the existing service endpoint having the given id.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct UpdateServiceEndpoint<T: frame_system::Config> {
+    pub did: Did,
+    /// Endpoint id
+    pub id: WrappedBytes,
+    /// New endpoint data
+    pub endpoint: ServiceEndpoint,
+    pub nonce: T::BlockNumber,
+}
+
+/// Sets the amount of distinct controllers required to sign an update of the DID.
+/// Updates signed by several controllers carry the nonce of the updated DID instead of the signer's one
+/// and each controller signs them wrapped in `MultiControllerAction`.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SetControllerThreshold<T: frame_system::Config> {
+    pub did: Did,
+    /// `None` means a signature from any single controller is enough
+    pub threshold: Option<u32>,
+    pub nonce: T::BlockNumber,
+}
+
+/// Payload signed by each controller of an update of the DID signed by several controllers. Such an update carries
+/// the nonce of the updated DID rather than the signer's one, so wrapping it keeps these signatures from being
+/// accepted as the signature of a single controller which carries the signer's nonce.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct MultiControllerAction<T: frame_system::Config> {
+    /// DID being updated
+    pub did: Did,
+    /// Encoded state change of the update
+    pub state_change: WrappedBytes,
+    #[codec(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _marker: sp_std::marker::PhantomData<T>,
+}
+
+impl<T: frame_system::Config> MultiControllerAction<T> {
+    /// Builds the payload to be signed by each controller of the given update.
+    pub fn new<A>(action: &A) -> Self
+    where
+        A: Action<T, Target = Did> + crate::ToStateChange<T>,
+    {
+        Self {
+            did: action.target(),
+            state_change: action.to_state_change().encode().into(),
+            _marker: sp_std::marker::PhantomData,
+        }
+    }
+}
+
+crate::impl_action! { MultiControllerAction for Did: with 1 as len, did as target }
+
+/// Single update of the DID document performed as a part of the `DidBatchUpdate`.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum DidUpdateOperation {
+    AddKeys(Vec<DidKey>),
+    /// Key ids to remove
+    RemoveKeys(BTreeSet<IncId>),
+    AddControllers(BTreeSet<Controller>),
+    RemoveControllers(BTreeSet<Controller>),
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    AddServiceEndpoint {
+        /// Endpoint id
+        id: WrappedBytes,
+        /// Endpoint data
+        endpoint: ServiceEndpoint,
+    },
+    /// Endpoint id to remove
+    RemoveServiceEndpoint(WrappedBytes),
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    UpdateServiceEndpoint {
+        /// Endpoint id
+        id: WrappedBytes,
+        /// New endpoint data
+        endpoint: ServiceEndpoint,
+    },
+}
+
+/// Applies the given operations to the DID document in order. Either all of them are applied
+/// or none of them.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DidBatchUpdate<T: frame_system::Config> {
+    pub did: Did,
+    pub operations: Vec<DidUpdateOperation>,
+    pub nonce: T::BlockNumber,
+}
+
+/// Links the account to the DID. Must be submitted by the account being linked.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct LinkAccount<T: frame_system::Config> {
+    pub did: Did,
+    pub account: T::AccountId,
+    pub nonce: T::BlockNumber,
+}
+
+/// Unlinks the account from the DID.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct UnlinkAccount<T: frame_system::Config> {
+    pub did: Did,
+    pub account: T::AccountId,
+    pub nonce: T::BlockNumber,
+}
+
+/// Sets guardians who can recover the DID. Passing `None` disables the recovery.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SetRecoveryConfig<T: frame_system::Config> {
+    pub did: Did,
+    pub config: Option<RecoveryConfig<T>>,
+    pub nonce: T::BlockNumber,
+}
+
+/// Initiates the recovery of the DID by its guardians. Once the recovery delay passes, `new_key`
+/// will be added to the DID as a controller key.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct InitiateRecovery<T: frame_system::Config> {
+    pub did: Did,
+    pub new_key: DidKey,
+    pub nonce: T::BlockNumber,
+}
+
+/// Cancels the pending recovery of the DID.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CancelRecovery<T: frame_system::Config> {
+    pub did: Did,
+    pub nonce: T::BlockNumber,
+}
+
 /// This struct is passed as an argument while removing the DID
 /// `did` is the DID which is being removed.
 #[derive(Encode, Decode, Clone, PartialEq, Debug)]
@@ -78,5 +239,14 @@ impl_action_with_nonce!(
         RemoveControllers with controllers.len() as len, did as target,
         AddServiceEndpoint with 1 as len, did as target,
         RemoveServiceEndpoint with 1 as len, did as target,
-        DidRemoval with 1 as len, did as target
+        DidRemoval with 1 as len, did as target,
+        RotateKeys with { |this: &Self| (this.remove.len() + this.add.len()) as u32 } as len, did as target,
+        SetControllerThreshold with 1 as len, did as target,
+        DidBatchUpdate with operations.len() as len, did as target,
+        SetRecoveryConfig with 1 as len, did as target,
+        InitiateRecovery with 1 as len, did as target,
+        CancelRecovery with 1 as len, did as target,
+        UpdateServiceEndpoint with 1 as len, did as target,
+        LinkAccount with 1 as len, did as target,
+        UnlinkAccount with 1 as len, did as target
 );
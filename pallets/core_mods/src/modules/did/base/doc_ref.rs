@@ -0,0 +1,144 @@
+use super::super::*;
+
+/// Multicodec codes of the content types accepted in a CIDv1.
+const CID_CODECS: [u64; 6] = [
+    0x55,   // raw
+    0x70,   // dag-pb
+    0x71,   // dag-cbor
+    0x0129, // dag-json
+    0x0200, // json
+    0x0202, // car
+];
+/// Multihash code of `sha2-256`, the only hash function used by CIDv0.
+const SHA2_256: u64 = 0x12;
+/// Size of the `sha2-256` digest.
+const SHA2_256_SIZE: usize = 32;
+/// URL schemes accepted in an off-chain DID Doc reference.
+const URL_SCHEMES: [&[u8]; 4] = [b"https", b"http", b"ipfs", b"ipns"];
+
+/// Reason why the off-chain DID Doc reference is malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocRefError {
+    /// The CID is neither a CIDv0 nor a CIDv1
+    InvalidCidVersion,
+    /// The CIDv1 content type isn't a supported multicodec
+    UnsupportedCidCodec,
+    /// The CID's multihash is truncated or its digest size doesn't match the declared one
+    InvalidCidMultihash,
+    /// The URL contains forbidden characters or has no host
+    InvalidUrl,
+    /// The URL scheme isn't allowed
+    UnsupportedUrlScheme,
+}
+
+impl<T: Config + Debug> From<DocRefError> for Error<T> {
+    fn from(err: DocRefError) -> Self {
+        match err {
+            DocRefError::InvalidCidVersion => Self::InvalidCidVersion,
+            DocRefError::UnsupportedCidCodec => Self::UnsupportedCidCodec,
+            DocRefError::InvalidCidMultihash => Self::InvalidCidMultihash,
+            DocRefError::InvalidUrl => Self::InvalidUrl,
+            DocRefError::UnsupportedUrlScheme => Self::UnsupportedUrlScheme,
+        }
+    }
+}
+
+impl OffChainDidDocRef {
+    /// Checks that the reference is well-formed according to its variant.
+    /// `CID` must be a binary CIDv0 or CIDv1 with a supported content type and `URL` must be an ASCII URL with an
+    /// allowed scheme and a non-empty host. `Custom` references aren't checked.
+    pub fn validate(&self) -> Result<(), DocRefError> {
+        match self {
+            OffChainDidDocRef::CID(cid) => validate_cid(&cid[..]),
+            OffChainDidDocRef::URL(url) => validate_url(&url[..]),
+            OffChainDidDocRef::Custom(_) => Ok(()),
+        }
+    }
+}
+
+/// Validates a CID in the binary form as per https://github.com/multiformats/cid#decoding-algorithm.
+fn validate_cid(cid: &[u8]) -> Result<(), DocRefError> {
+    // CIDv0 is a bare `sha2-256` multihash
+    if cid.len() == 2 + SHA2_256_SIZE && cid[0] == SHA2_256 as u8 && cid[1] == SHA2_256_SIZE as u8 {
+        return Ok(());
+    }
+
+    let (version, rest) = read_varint(cid).ok_or(DocRefError::InvalidCidVersion)?;
+    ensure!(version == 1, DocRefError::InvalidCidVersion);
+    let (codec, multihash) = read_varint(rest).ok_or(DocRefError::UnsupportedCidCodec)?;
+    ensure!(
+        CID_CODECS.contains(&codec),
+        DocRefError::UnsupportedCidCodec
+    );
+
+    validate_multihash(multihash)
+}
+
+/// Validates that the multihash declares the size of its digest correctly.
+fn validate_multihash(multihash: &[u8]) -> Result<(), DocRefError> {
+    let (hash_code, rest) = read_varint(multihash).ok_or(DocRefError::InvalidCidMultihash)?;
+    let (size, digest) = read_varint(rest).ok_or(DocRefError::InvalidCidMultihash)?;
+    ensure!(
+        digest.len() as u64 == size,
+        DocRefError::InvalidCidMultihash
+    );
+    ensure!(
+        hash_code != SHA2_256 || digest.len() == SHA2_256_SIZE,
+        DocRefError::InvalidCidMultihash
+    );
+
+    Ok(())
+}
+
+/// Validates the URL syntax: only printable ASCII characters are allowed, the scheme must be one of `URL_SCHEMES`
+/// and it must be followed by `://` and a non-empty host.
+fn validate_url(url: &[u8]) -> Result<(), DocRefError> {
+    ensure!(
+        url.iter().all(|byte| (0x21..=0x7e).contains(byte)),
+        DocRefError::InvalidUrl
+    );
+    let scheme_end = url
+        .iter()
+        .position(|&byte| byte == b':')
+        .ok_or(DocRefError::InvalidUrl)?;
+    let (scheme, rest) = url.split_at(scheme_end);
+    ensure!(
+        URL_SCHEMES
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(scheme)),
+        DocRefError::UnsupportedUrlScheme
+    );
+
+    let host = rest
+        .strip_prefix(b"://")
+        .ok_or(DocRefError::InvalidUrl)?
+        .split(|byte| matches!(byte, b'/' | b'?' | b'#'))
+        .next()
+        .unwrap_or_default();
+    ensure!(!host.is_empty(), DocRefError::InvalidUrl);
+
+    Ok(())
+}
+
+/// Reads an unsigned varint as per https://github.com/multiformats/unsigned-varint from the start of the bytes.
+/// Returns the value and the remaining bytes, or `None` if the varint is truncated, too long or not minimally encoded.
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    // The spec limits varints to 9 bytes
+    const MAX_VARINT_SIZE: usize = 9;
+
+    let mut value = 0u64;
+    for (idx, &byte) in bytes.iter().take(MAX_VARINT_SIZE).enumerate() {
+        value |= ((byte & 0x7f) as u64) << (7 * idx);
+
+        if byte & 0x80 == 0 {
+            // Trailing zero byte means the varint isn't minimal
+            if byte == 0 && idx > 0 {
+                return None;
+            }
+
+            return Some((value, &bytes[idx + 1..]));
+        }
+    }
+
+    None
+}
@@ -2,10 +2,12 @@ use sp_std::ops::{Index, RangeFull};
 
 use super::*;
 
+pub mod doc_ref;
 pub mod offchain;
 pub mod onchain;
 pub mod signature;
 
+pub use doc_ref::DocRefError;
 pub use offchain::*;
 pub use onchain::*;
 pub use signature::DidSignature;
@@ -90,4 +92,20 @@ impl<T: Config + Debug> Module<T> {
     pub(crate) fn insert_did_details<D: Into<StoredDidDetails<T>>>(did: Did, did_details: D) {
         Dids::<T>::insert(did, did_details.into())
     }
+
+    /// Ensures that the given DID is neither registered nor was removed before.
+    pub(crate) fn ensure_can_be_registered(did: &Did) -> Result<(), Error<T>> {
+        ensure!(!Dids::<T>::contains_key(did), Error::<T>::DidAlreadyExists);
+        ensure!(
+            !DeactivatedDids::<T>::contains_key(did),
+            Error::<T>::DidIsDeactivated
+        );
+
+        Ok(())
+    }
+
+    /// Leaves a tombstone for the removed DID, so it can't be registered again.
+    pub(crate) fn deactivate_did(did: &Did) {
+        DeactivatedDids::<T>::insert(did, <system::Module<T>>::block_number());
+    }
 }
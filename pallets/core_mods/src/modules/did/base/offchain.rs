@@ -49,14 +49,14 @@ impl<T: Config + Debug> OffChainDidDetails<T> {
     }
 }
 
-/// To describe the off chain DID Doc's reference. This is just to inform the client, this module
-/// does not check if the bytes are indeed valid as per the enum variant
+/// To describe the off chain DID Doc's reference. `CID` and `URL` references are validated when set, see
+/// `OffChainDidDocRef::validate`. References stored before the validation was introduced are kept as they are.
 #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OffChainDidDocRef {
-    /// Content IDentifier as per https://github.com/multiformats/cid.
+    /// Content IDentifier in the binary form as per https://github.com/multiformats/cid.
     CID(WrappedBytes),
-    /// A URL
+    /// A URL with one of `http`, `https`, `ipfs` or `ipns` schemes
     URL(WrappedBytes),
     /// A custom encoding of the reference
     Custom(WrappedBytes),
@@ -82,10 +82,14 @@ impl<T: Config + Debug> Module<T> {
             T::MaxDidDocRefSize::get() as usize >= did_doc_ref.len(),
             Error::<T>::DidDocRefTooBig
         );
-        // DID is not registered already
-        ensure!(!Dids::<T>::contains_key(did), Error::<T>::DidAlreadyExists);
+        did_doc_ref.validate()?;
+        // DID is not registered already and wasn't removed before
+        Self::ensure_can_be_registered(&did)?;
 
-        let details = OffChainDidDetails::new(caller, did_doc_ref.clone());
+        let details: StoredDidDetails<T> =
+            OffChainDidDetails::new(caller.clone(), did_doc_ref.clone()).into();
+        Self::reserve_deposit(&caller, DepositItem::Did(did), details.encoded_size())?;
+        AccountDids::<T>::insert(&caller, did, ());
         Self::insert_did_details(did, details);
 
         deposit_indexed_event!(OffChainDidAdded(did, did_doc_ref) over did);
@@ -101,10 +105,13 @@ impl<T: Config + Debug> Module<T> {
             T::MaxDidDocRefSize::get() as usize >= did_doc_ref.len(),
             Error::<T>::DidDocRefTooBig
         );
+        did_doc_ref.validate()?;
         Self::offchain_did_details(&did)?.ensure_can_update(&caller)?;
 
         let details: StoredDidDetails<T> =
-            OffChainDidDetails::new(caller, did_doc_ref.clone()).into();
+            OffChainDidDetails::new(caller.clone(), did_doc_ref.clone()).into();
+        // Deposit is resized according to the new reference
+        Self::reserve_deposit(&caller, DepositItem::Did(did), details.encoded_size())?;
         Dids::<T>::insert(did, details);
 
         deposit_indexed_event!(OffChainDidUpdated(did, did_doc_ref) over did);
@@ -115,11 +122,31 @@ impl<T: Config + Debug> Module<T> {
         Self::offchain_did_details(&did)?.ensure_can_update(&caller)?;
 
         Dids::<T>::remove(did);
+        Self::refund_deposit(&DepositItem::Did(did));
+        AccountDids::<T>::remove(caller, did);
+        Self::deactivate_did(&did);
 
         deposit_indexed_event!(OffChainDidRemoved(did));
         Ok(())
     }
 
+    pub(crate) fn convert_offchain_to_onchain_(
+        caller: T::AccountId,
+        did: Did,
+        keys: Vec<DidKey>,
+        controllers: BTreeSet<Controller>,
+    ) -> Result<(), Error<T>> {
+        let details = Self::offchain_did_details(&did)?;
+        details.ensure_can_update(&caller)?;
+
+        Self::insert_onchain_did(&caller, did, keys, controllers)?;
+        // The owner stays linked to the converted DID
+        DidLinkedAccounts::<T>::insert(did, caller, ());
+
+        deposit_indexed_event!(OffChainDidConvertedToOnChain(did, details.doc_ref) over did);
+        Ok(())
+    }
+
     pub fn is_offchain_did(did: &Did) -> Result<bool, Error<T>> {
         Self::did(did)
             .as_ref()
@@ -1,5 +1,6 @@
 use super::super::*;
 use crate::{util::WrappedActionWithNonce, ToStateChange};
+use sp_std::iter::once;
 
 /// Each on-chain DID is associated with a nonce that is incremented each time the DID does a
 /// write (through an extrinsic). The nonce starts from the block number when the DID was created to avoid
@@ -18,6 +19,9 @@ pub struct OnChainDidDetails {
     pub active_controller_keys: u32,
     /// Number of currently active controllers.
     pub active_controllers: u32,
+    /// Number of distinct controllers required to sign an update of the DID.
+    /// `None` means that a signature from any single controller is enough.
+    pub controller_threshold: Option<u32>,
 }
 
 impl<T: Config> From<StoredOnChainDidDetails<T>> for StoredDidDetails<T> {
@@ -47,44 +51,105 @@ impl OnChainDidDetails {
             last_key_id,
             active_controller_keys: active_controller_keys.into(),
             active_controllers: active_controllers.into(),
+            controller_threshold: None,
         }
     }
+
+    /// Returns amount of distinct controller signatures required to update the DID.
+    pub fn required_controller_sigs(&self) -> u32 {
+        self.controller_threshold.unwrap_or(1)
+    }
+
+    /// Returns `true` if the controller threshold can still be met having `active_controllers` controllers.
+    pub fn threshold_reachable_with(&self, active_controllers: u32) -> bool {
+        self.controller_threshold
+            .map_or(true, |threshold| threshold <= active_controllers)
+    }
 }
 
 impl<T: Config + Debug> Module<T> {
     pub(crate) fn new_onchain_(
+        depositor: T::AccountId,
         did: Did,
         keys: Vec<DidKey>,
-        mut controllers: BTreeSet<Controller>,
+        controllers: BTreeSet<Controller>,
     ) -> Result<(), Error<T>> {
-        // DID is not registered already
-        ensure!(!Dids::<T>::contains_key(did), Error::<T>::DidAlreadyExists);
+        // DID is not registered already and wasn't removed before
+        Self::ensure_can_be_registered(&did)?;
+
+        Self::insert_onchain_did(&depositor, did, keys, controllers)?;
 
+        deposit_indexed_event!(OnChainDidAdded(did));
+        Ok(())
+    }
+
+    /// Stores keys, controllers and details of the on-chain DID replacing its existing details if any.
+    /// The nonce is initialized with the current block number. Deposits for the details, keys and the first version
+    /// of the DID Document are reserved from the depositor's balance.
+    pub(crate) fn insert_onchain_did(
+        depositor: &T::AccountId,
+        did: Did,
+        keys: Vec<DidKey>,
+        mut controllers: BTreeSet<Controller>,
+    ) -> Result<(), Error<T>> {
         let (keys_to_insert, controller_keys_count) = Self::prepare_keys_to_insert(keys)?;
         // Make self controlled if needed
         if controller_keys_count > 0 {
             controllers.insert(Controller(did));
         }
         ensure!(!controllers.is_empty(), Error::<T>::NoControllerProvided);
+        // The DID could have been added as a controller before being registered
+        if T::PreventControllerCycles::get() {
+            for ctrl in &controllers {
+                Self::ensure_no_controller_cycle(&did, ctrl)?;
+            }
+        }
 
         let mut last_key_id = IncId::new();
-        for (key, key_id) in keys_to_insert.into_iter().zip(&mut last_key_id) {
-            DidKeys::insert(&did, key_id, key);
+        let keys_to_insert: Vec<_> = keys_to_insert.into_iter().zip(&mut last_key_id).collect();
+        let did_details: StoredDidDetails<T> = WithNonce::new(OnChainDidDetails::new(
+            last_key_id,
+            controller_keys_count,
+            controllers.len() as u32,
+        ))
+        .into();
+        // First version of the DID Document is built upfront to check that its deposit can be reserved
+        let document = OnChainDidDocument {
+            controllers: controllers.iter().copied().collect(),
+            keys: keys_to_insert
+                .iter()
+                .map(|(key, id)| DidKeyWithId {
+                    id: *id,
+                    key: key.clone(),
+                })
+                .collect(),
+            service_endpoints: Vec::new(),
+        };
+        ensure!(
+            document.item_count() <= T::MaxDidDocumentItems::get(),
+            Error::<T>::TooManyDidDocumentItems
+        );
+        Self::ensure_can_reserve_deposits(
+            depositor,
+            keys_to_insert
+                .iter()
+                .map(|(key, _)| key.encoded_size())
+                .chain(once(did_details.encoded_size()))
+                .chain(once(document.encoded_size())),
+        )?;
+
+        for (key, key_id) in keys_to_insert {
+            Self::insert_key_with_deposit(depositor, did, key_id, key)?;
         }
 
         for ctrl in &controllers {
             DidControllers::insert(&did, &ctrl, ());
         }
 
-        let did_details = WithNonce::new(OnChainDidDetails::new(
-            last_key_id,
-            controller_keys_count,
-            controllers.len() as u32,
-        ));
-
+        Self::reserve_deposit(depositor, DepositItem::Did(did), did_details.encoded_size())?;
         Self::insert_did_details(did, did_details);
+        Self::record_document_version_with_deposit(did, document, depositor)?;
 
-        deposit_indexed_event!(OnChainDidAdded(did));
         Ok(())
     }
 
@@ -92,11 +157,18 @@ impl<T: Config + Debug> Module<T> {
         DidRemoval { did, .. }: DidRemoval<T>,
         details: &mut Option<OnChainDidDetails>,
     ) -> Result<(), Error<T>> {
+        // The DID Document is recorded before the removal if the DID has no versions yet
+        Self::record_initial_version(&did);
         // This will result in the removal of DID from storage map `Dids`
         details.take();
-        DidKeys::remove_prefix(did);
+        Self::refund_deposit(&DepositItem::Did(did));
+        Self::remove_all_keys(did);
         DidControllers::remove_prefix(did);
-        DidServiceEndpoints::remove_prefix(did);
+        Self::remove_all_service_endpoints(did);
+        Self::remove_recovery(&did);
+        Self::unlink_all_accounts(&did);
+        Self::deactivate_did(&did);
+        Self::record_did_removal(did);
 
         deposit_indexed_event!(OnChainDidRemoved(did));
         Ok(())
@@ -142,16 +214,84 @@ impl<T: Config + Debug> Module<T> {
         A::Target: Into<Did>,
         E: From<Error<T>> + From<NonceError>,
     {
-        Self::try_exec_signed_removable_action_from_controller(
+        Self::try_exec_signed_action_from_controllers(
+            f,
+            action,
+            sp_std::iter::once(signature).collect(),
+        )
+    }
+
+    /// Try to execute an action signed by one or more DIDs that control (possibly) another DID.
+    /// If the action is signed by a single controller and the controlled DID has no controller threshold,
+    /// the nonce of the signing DID must be checked and increased. Otherwise, signatures from at least the
+    /// threshold amount of distinct controllers are required and the nonce of the controlled DID is used.
+    /// In the latter case, the controllers sign the action wrapped in `MultiControllerAction`, so that their
+    /// signatures can't be replayed as the signature of a single controller carrying its own nonce.
+    pub(crate) fn try_exec_signed_action_from_controllers<A, F, R, E>(
+        f: F,
+        action: A,
+        signatures: Vec<DidSignature<Controller>>,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, &mut OnChainDidDetails) -> Result<R, E>,
+        A: ActionWithNonce<T, Target = Did> + ToStateChange<T>,
+        A::Target: Into<Did>,
+        E: From<Error<T>> + From<NonceError>,
+    {
+        Self::try_exec_signed_removable_action_from_controllers(
             |action, details_opt| f(action, details_opt.as_mut().unwrap()),
             action,
-            signature,
+            signatures,
         )
     }
 
-    /// Same as `Self::try_exec_signed_action_from_controller` except that the DID
+    /// Same as `Self::try_exec_signed_action_from_controllers` except that the DID
     /// Doc of controlled DID might be removed on completion.
-    pub(crate) fn try_exec_signed_removable_action_from_controller<A, F, R, E>(
+    pub(crate) fn try_exec_signed_removable_action_from_controllers<A, F, R, E>(
+        f: F,
+        action: A,
+        mut signatures: Vec<DidSignature<Controller>>,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, &mut Option<OnChainDidDetails>) -> Result<R, E>,
+        A: ActionWithNonce<T, Target = Did> + ToStateChange<T>,
+        A::Target: Into<Did>,
+        E: From<Error<T>> + From<NonceError>,
+    {
+        // DID existence is checked later, so a missing or off-chain DID is treated as having no threshold here
+        let required_sigs = Self::onchain_did_details(&action.target())
+            .map(|details| details.data().required_controller_sigs())
+            .unwrap_or(1);
+
+        if signatures.len() == 1 && required_sigs <= 1 {
+            let signature = signatures.pop().unwrap();
+
+            return Self::try_exec_signed_removable_action_from_controller(f, action, signature);
+        }
+
+        ensure!(
+            signatures.len() as u32 >= required_sigs,
+            Error::<T>::NotEnoughControllerSignatures
+        );
+        let payload = MultiControllerAction::<T>::new(&action);
+        let mut signers = BTreeSet::new();
+        for signature in &signatures {
+            ensure!(
+                signers.insert(signature.did),
+                Error::<T>::DuplicateControllerSignature
+            );
+            ensure!(
+                Self::verify_sig_from_controller(&payload, signature)?,
+                Error::<T>::InvalidSignature
+            );
+        }
+
+        // Multiple controllers signed the action so the nonce of the controlled DID is used
+        Self::try_exec_removable_action_over_onchain_did(f, action)
+    }
+
+    /// Executes an action signed by a single controller. The nonce of the signing DID is checked and increased.
+    fn try_exec_signed_removable_action_from_controller<A, F, R, E>(
         f: F,
         action: A,
         signature: DidSignature<Controller>,
@@ -189,6 +329,47 @@ impl<T: Config + Debug> Module<T> {
         }
     }
 
+    /// Try to execute an action signed by a single controller of the target DID regardless of the DID's controller
+    /// threshold. The nonce of the signing DID is checked and increased.
+    pub(crate) fn try_exec_signed_action_from_any_controller<A, F, R, E>(
+        f: F,
+        action: A,
+        signature: DidSignature<Controller>,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, &mut OnChainDidDetails) -> Result<R, E>,
+        A: ActionWithNonce<T, Target = Did> + ToStateChange<T>,
+        A::Target: Into<Did>,
+        E: From<Error<T>> + From<NonceError>,
+    {
+        Self::try_exec_signed_removable_action_from_controller(
+            |action, details_opt| f(action, details_opt.as_mut().unwrap()),
+            action,
+            signature,
+        )
+    }
+
+    /// Try to execute an action signed by the recovery guardians of the target DID. Signatures from at least
+    /// the threshold amount of distinct guardians are required and the nonce of the target DID is used.
+    /// The set of guardians who signed the action is passed to `f`.
+    pub(crate) fn try_exec_signed_action_from_guardians<A, F, R, E>(
+        f: F,
+        action: A,
+        signatures: Vec<DidSignature<Did>>,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, BTreeSet<Did>, &mut OnChainDidDetails) -> Result<R, E>,
+        A: ActionWithNonce<T, Target = Did> + ToStateChange<T>,
+        E: From<Error<T>> + From<NonceError>,
+    {
+        let guardians = Self::verify_sigs_from_guardians(&action, &signatures)?;
+
+        Self::try_exec_action_over_onchain_did(
+            |action, details| f(action, guardians, details),
+            action,
+        )
+    }
+
     crate::pub_for_test! {
         /// Executes action over target on-chain DID providing a mutable reference if the given
         /// nonce is correct, i.e. 1 more than the current nonce.
@@ -27,9 +27,16 @@ impl<D: Into<Did>> DidSignature<D> {
         message: &[u8],
         public_key: &PublicKey,
     ) -> Result<bool, Error<T>> {
-        self.sig
+        let verified = self
+            .sig
             .verify(message, public_key)
-            .map_err(|_| Error::<T>::IncompatSigPubkey)
+            .map_err(|_| Error::<T>::IncompatSigPubkey)?;
+
+        Ok(verified
+            && match &self.sig {
+                SigValue::WebAuthn(assertion) => assertion.is_for_rp(&T::WebAuthnRpIdHash::get()),
+                _ => true,
+            })
     }
 
     /// This is just the weight to verify the signature. It does not include weight to read the DID or the key.
@@ -0,0 +1,99 @@
+use super::*;
+use frame_support::storage::with_transaction;
+use sp_runtime::TransactionOutcome;
+
+impl<T: Config + Debug> Module<T> {
+    pub(crate) fn batch_update_(
+        depositor: &T::AccountId,
+        DidBatchUpdate {
+            did,
+            operations,
+            nonce,
+        }: DidBatchUpdate<T>,
+        details: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        // Operations write to the storage one by one, so all of them are discarded if any fails.
+        // Changes to the `details` are discarded by the caller in this case.
+        with_transaction(|| {
+            let res = operations.into_iter().try_for_each(|operation| {
+                Self::apply_operation(depositor, did, operation, nonce, details)
+            });
+
+            if res.is_ok() {
+                TransactionOutcome::Commit(res)
+            } else {
+                TransactionOutcome::Rollback(res)
+            }
+        })
+    }
+
+    fn apply_operation(
+        depositor: &T::AccountId,
+        did: Did,
+        operation: DidUpdateOperation,
+        nonce: T::BlockNumber,
+        details: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        match operation {
+            DidUpdateOperation::AddKeys(keys) => {
+                ensure!(!keys.is_empty(), Error::<T>::NoKeyProvided);
+
+                Self::add_keys_(depositor, AddKeys { did, keys, nonce }, details)
+            }
+            DidUpdateOperation::RemoveKeys(keys) => {
+                ensure!(!keys.is_empty(), Error::<T>::NoKeyProvided);
+
+                Self::remove_keys_(RemoveKeys { did, keys, nonce }, details)
+            }
+            DidUpdateOperation::AddControllers(controllers) => {
+                ensure!(!controllers.is_empty(), Error::<T>::NoControllerProvided);
+
+                Self::add_controllers_(
+                    AddControllers {
+                        did,
+                        controllers,
+                        nonce,
+                    },
+                    details,
+                )
+            }
+            DidUpdateOperation::RemoveControllers(controllers) => {
+                ensure!(!controllers.is_empty(), Error::<T>::NoControllerProvided);
+
+                Self::remove_controllers_(
+                    RemoveControllers {
+                        did,
+                        controllers,
+                        nonce,
+                    },
+                    details,
+                )
+            }
+            DidUpdateOperation::AddServiceEndpoint { id, endpoint } => Self::add_service_endpoint_(
+                depositor,
+                AddServiceEndpoint {
+                    did,
+                    id,
+                    endpoint,
+                    nonce,
+                },
+                details,
+            ),
+            DidUpdateOperation::RemoveServiceEndpoint(id) => {
+                Self::remove_service_endpoint_(RemoveServiceEndpoint { did, id, nonce }, details)
+            }
+            DidUpdateOperation::UpdateServiceEndpoint { id, endpoint } => {
+                Self::update_service_endpoint_(
+                    depositor,
+                    UpdateServiceEndpoint {
+                        did,
+                        id,
+                        endpoint,
+                        nonce,
+                    },
+                    details,
+                )
+            }
+        }
+    }
+}
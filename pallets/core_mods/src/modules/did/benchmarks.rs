@@ -14,18 +14,29 @@ const MAX_ORIGINS: u32 = 10;
 const MAX_ORIGIN_LENGTH: u32 = 10;
 const MAX_SERVICE_ENDPOINT_ID_LENGTH: u32 = 100;
 
+/// CIDv1 of the given size (but at least 4 bytes) using the identity multihash.
+fn cid_doc_ref(size: u32) -> OffChainDidDocRef {
+    let digest_size = size.saturating_sub(4).min(127) as u8;
+    let mut cid = vec![0x01, 0x55, 0x00, digest_size];
+    cid.extend(0..digest_size);
+
+    OffChainDidDocRef::CID(cid.into())
+}
+
 crate::bench_with_all_pairs! {
     with_pairs:
-    add_keys_sr25519 for sr25519, add_keys_ed25519 for ed25519, add_keys_secp256k1 for secp256k1 {
+    add_keys_sr25519 for sr25519, add_keys_ed25519 for ed25519, add_keys_secp256k1 for secp256k1, add_keys_secp256r1 for secp256r1 {
         {
             let k in 1 .. MAX_ENTITY_AMOUNT;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let did_key = DidKey::new_with_all_relationships(pair.public());
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![did_key.clone()],
             Default::default(),
@@ -47,7 +58,7 @@ crate::bench_with_all_pairs! {
         let sig = pair.sign(&key_update.to_state_change().encode());
         // frame_support::log::error!("B");
         let signature = DidSignature::new(did, 1u32, sig);
-    }: add_keys(RawOrigin::Signed(caller), key_update, signature)
+    }: add_keys(RawOrigin::Signed(caller), key_update, vec![signature])
     verify {
         let mut stored_keys = DidKeys::iter_prefix_values(did).collect::<Vec<_>>();
         stored_keys.sort_by_key(|key| key.public_key.as_slice().to_vec());
@@ -59,12 +70,13 @@ crate::bench_with_all_pairs! {
         assert_eq!(stored_keys, keys);
     }
 
-    remove_keys_sr25519 for sr25519, remove_keys_ed25519 for ed25519, remove_keys_secp256k1 for secp256k1 {
+    remove_keys_sr25519 for sr25519, remove_keys_ed25519 for ed25519, remove_keys_secp256k1 for secp256k1, remove_keys_secp256r1 for secp256r1 {
         {
             let k in 1 .. MAX_ENTITY_AMOUNT;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
@@ -75,6 +87,7 @@ crate::bench_with_all_pairs! {
                 .collect();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             keys.clone(),
             Default::default(),
@@ -88,21 +101,23 @@ crate::bench_with_all_pairs! {
 
         let sig = pair.sign(&key_update.to_state_change().encode());
         let signature = DidSignature::new(did, 1u32, sig);
-    }: remove_keys(RawOrigin::Signed(caller), key_update, signature)
+    }: remove_keys(RawOrigin::Signed(caller), key_update, vec![signature])
     verify {
         assert_eq!(DidKeys::iter_prefix(did).count(), 0);
     }
 
-    add_controllers_sr25519 for sr25519, add_controllers_ed25519 for ed25519, add_controllers_secp256k1 for secp256k1 {
+    add_controllers_sr25519 for sr25519, add_controllers_ed25519 for ed25519, add_controllers_secp256k1 for secp256k1, add_controllers_secp256r1 for secp256r1 {
         {
             let k in 1 .. MAX_ENTITY_AMOUNT;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([2; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -122,7 +137,7 @@ crate::bench_with_all_pairs! {
 
         let sig = pair.sign(&new_controllers.to_state_change().encode());
         let signature = DidSignature::new(did, 1u32, sig);
-    }: add_controllers(RawOrigin::Signed(caller), new_controllers, signature)
+    }: add_controllers(RawOrigin::Signed(caller), new_controllers, vec![signature])
     verify {
         let mut stored_controllers = DidControllers::iter_prefix(did).map(|(cnt, _)| cnt).collect::<Vec<_>>();
         stored_controllers.sort();
@@ -134,12 +149,13 @@ crate::bench_with_all_pairs! {
         assert_eq!(stored_controllers, controllers);
     }
 
-    remove_controllers_sr25519 for sr25519, remove_controllers_ed25519 for ed25519, remove_controllers_secp256k1 for secp256k1 {
+    remove_controllers_sr25519 for sr25519, remove_controllers_ed25519 for ed25519, remove_controllers_secp256k1 for secp256k1, remove_controllers_secp256r1 for secp256r1 {
         {
             let k in 1 .. MAX_ENTITY_AMOUNT;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([3; Did::BYTE_SIZE]);
         let public = pair.public();
         let controllers: BTreeSet<_> = (0..k)
@@ -149,6 +165,7 @@ crate::bench_with_all_pairs! {
             .collect();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             controllers.clone(),
@@ -162,12 +179,12 @@ crate::bench_with_all_pairs! {
 
         let sig = pair.sign(&rem_controllers.to_state_change().encode());
         let signature = DidSignature::new(did, 1u32, sig);
-    }: remove_controllers(RawOrigin::Signed(caller), rem_controllers, signature)
+    }: remove_controllers(RawOrigin::Signed(caller), rem_controllers, vec![signature])
     verify {
         assert_eq!(DidControllers::iter_prefix(did).count(), 0);
     }
 
-    add_service_endpoint_sr25519 for sr25519, add_service_endpoint_ed25519 for ed25519, add_service_endpoint_secp256k1 for secp256k1 {
+    add_service_endpoint_sr25519 for sr25519, add_service_endpoint_ed25519 for ed25519, add_service_endpoint_secp256k1 for secp256k1, add_service_endpoint_secp256r1 for secp256r1 {
         {
             let o in 1 .. MAX_ORIGINS;
             let l in 1 .. MAX_ORIGIN_LENGTH;
@@ -175,10 +192,12 @@ crate::bench_with_all_pairs! {
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([3; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -189,40 +208,45 @@ crate::bench_with_all_pairs! {
             id: WrappedBytes(vec![1; i as usize]),
             endpoint: ServiceEndpoint {
                 origins: (0..o).map(|i| vec![i as u8; l as usize].into()).collect(),
-                types: crate::did::service_endpoints::ServiceEndpointType::LINKED_DOMAINS
+                types: once(crate::did::ServiceEndpointType::LinkedDomains).collect(),
+                properties: Default::default()
             },
             nonce: 1u8.into()
         };
 
         let sig = pair.sign(&add_endpoint.to_state_change().encode());
         let signature = DidSignature::new(did, 1u32, sig);
-    }: add_service_endpoint(RawOrigin::Signed(caller), add_endpoint.clone(), signature)
+    }: add_service_endpoint(RawOrigin::Signed(caller), add_endpoint.clone(), vec![signature])
     verify {
         assert_eq!(DidServiceEndpoints::get(did, WrappedBytes(vec![1; i as usize])).unwrap(), add_endpoint.endpoint);
     }
 
-    remove_service_endpoint_sr25519 for sr25519, remove_service_endpoint_ed25519 for ed25519, remove_service_endpoint_secp256k1 for secp256k1 {
+    remove_service_endpoint_sr25519 for sr25519, remove_service_endpoint_ed25519 for ed25519, remove_service_endpoint_secp256k1 for secp256k1, remove_service_endpoint_secp256r1 for secp256r1 {
         {
             let i in 1 .. MAX_SERVICE_ENDPOINT_ID_LENGTH;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([3; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
         ).unwrap();
 
         crate::did::Module::<T>::add_service_endpoint_(
+            &caller,
             AddServiceEndpoint {
                 did,
                 id: WrappedBytes(vec![1; i as usize]),
                 endpoint: ServiceEndpoint {
                     origins: (0..MAX_ORIGINS as usize).map(|i| vec![i as u8; MAX_ORIGIN_LENGTH as usize].into()).collect(),
-                    types: crate::did::service_endpoints::ServiceEndpointType::LINKED_DOMAINS
+                    types: once(crate::did::ServiceEndpointType::LinkedDomains).collect(),
+                    properties: Default::default()
                 },
                 nonce: 1u8.into()
             },
@@ -237,13 +261,14 @@ crate::bench_with_all_pairs! {
 
         let sig = pair.sign(&remove_endpoint.to_state_change().encode());
         let signature = DidSignature::new(did, 1u32, sig);
-    }: remove_service_endpoint(RawOrigin::Signed(caller), remove_endpoint.clone(), signature)
+    }: remove_service_endpoint(RawOrigin::Signed(caller), remove_endpoint.clone(), vec![signature])
     verify {
        assert!(DidServiceEndpoints::get(did, WrappedBytes(vec![1; i as usize])).is_none());
     }
-    remove_onchain_did_sr25519 for sr25519, remove_onchain_did_ed25519 for ed25519, remove_onchain_did_secp256k1 for secp256k1 {
+    remove_onchain_did_sr25519 for sr25519, remove_onchain_did_ed25519 for ed25519, remove_onchain_did_secp256k1 for secp256k1, remove_onchain_did_secp256r1 for secp256r1 {
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([3; Did::BYTE_SIZE]);
         let public = pair.public();
 
@@ -258,6 +283,7 @@ crate::bench_with_all_pairs! {
             .collect();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             keys,
             controllers,
@@ -270,12 +296,14 @@ crate::bench_with_all_pairs! {
 
         for i in 0..MAX_ENTITY_AMOUNT {
             crate::did::Module::<T>::add_service_endpoint_(
+                &caller,
                 AddServiceEndpoint {
                     did,
                     id: WrappedBytes(vec![1; (i + 1) as usize]),
                     endpoint: ServiceEndpoint {
                         origins: (0..MAX_ORIGINS as usize).map(|i| vec![i as u8; MAX_ORIGIN_LENGTH as usize].into()).collect(),
-                        types: crate::did::service_endpoints::ServiceEndpointType::LINKED_DOMAINS
+                        types: once(crate::did::ServiceEndpointType::LinkedDomains).collect(),
+                        properties: Default::default()
                     },
                     nonce: 1u8.into()
                 },
@@ -285,7 +313,7 @@ crate::bench_with_all_pairs! {
 
         let sig = pair.sign(&remove_did.to_state_change().encode());
         let signature = DidSignature::new(did, 1u32, sig);
-    }: remove_onchain_did(RawOrigin::Signed(caller), remove_did.clone(), signature)
+    }: remove_onchain_did(RawOrigin::Signed(caller), remove_did.clone(), vec![signature])
     verify {
        assert!(Dids::<T>::get(did).is_none());
     };
@@ -296,6 +324,7 @@ crate::bench_with_all_pairs! {
         let c in 1 .. MAX_ENTITY_AMOUNT => ();
 
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([4; Did::BYTE_SIZE]);
 
         let keys: Vec<_> = (0..k)
@@ -334,9 +363,10 @@ crate::bench_with_all_pairs! {
         let k in 1 .. MAX_DID_DOC_REF_SIZE => ();
 
         let caller: T::AccountId = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([4; Did::BYTE_SIZE]);
 
-        let did_doc_ref = OffChainDidDocRef::CID((0..k).map(|k| k as u8).collect::<Vec<_>>().into());
+        let did_doc_ref = cid_doc_ref(k);
 
     }: new_offchain(RawOrigin::Signed(caller.clone()), did, did_doc_ref.clone())
     verify {
@@ -346,10 +376,11 @@ crate::bench_with_all_pairs! {
         let k in 1 .. MAX_DID_DOC_REF_SIZE => ();
 
         let caller: T::AccountId = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([4; Did::BYTE_SIZE]);
 
-        let did_doc_ref = OffChainDidDocRef::CID((0..k).map(|k| k as u8).collect::<Vec<_>>().into());
-        super::Module::<T>::new_offchain_(caller.clone(), did, OffChainDidDocRef::URL(Default::default())).unwrap();
+        let did_doc_ref = cid_doc_ref(k);
+        super::Module::<T>::new_offchain_(caller.clone(), did, OffChainDidDocRef::URL(b"https://example.com".to_vec().into())).unwrap();
 
     }: set_offchain_did_doc_ref(RawOrigin::Signed(caller.clone()), did, did_doc_ref.clone())
     verify {
@@ -357,10 +388,11 @@ crate::bench_with_all_pairs! {
     }
     remove_offchain_did {
         let caller: T::AccountId = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([4; Did::BYTE_SIZE]);
 
-        let did_doc_ref = OffChainDidDocRef::CID((1..MAX_DID_DOC_REF_SIZE).map(|k| k as u8).collect::<Vec<_>>().into());
-        super::Module::<T>::new_offchain_(caller.clone(), did, OffChainDidDocRef::URL(Default::default())).unwrap();
+        let did_doc_ref = cid_doc_ref(MAX_DID_DOC_REF_SIZE);
+        super::Module::<T>::new_offchain_(caller.clone(), did, OffChainDidDocRef::URL(b"https://example.com".to_vec().into())).unwrap();
 
     }: remove_offchain_did(RawOrigin::Signed(caller.clone()), did)
     verify {
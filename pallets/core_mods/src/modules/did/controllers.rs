@@ -8,6 +8,21 @@ pub struct Controller(pub Did);
 
 impl_wrapper!(Controller, Did, for rand use Did(rand::random()), with tests as controller_tests);
 
+/// Maximum amount of controller paths returned for a DID
+pub const MAX_CONTROLLER_PATHS: usize = 256;
+
+/// Path in the controller graph from a DID to a key which can control it either directly or by updating
+/// the controllers in between.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ControllerPath {
+    /// DIDs starting with the resolved one where each DID is controlled by the next one. The last DID owns the key.
+    pub dids: Vec<Did>,
+    /// Key with the `CAPABILITY_INVOCATION` verification relationship.
+    pub key: DidKeyWithId,
+}
+
 impl<T: Config + Debug> Module<T> {
     pub(crate) fn add_controllers_(
         AddControllers {
@@ -21,6 +36,9 @@ impl<T: Config + Debug> Module<T> {
             if Self::is_controller(&did, ctrl) {
                 fail!(Error::<T>::ControllerIsAlreadyAdded)
             }
+            if T::PreventControllerCycles::get() {
+                Self::ensure_no_controller_cycle(&did, ctrl)?;
+            }
         }
 
         for ctrl in &controllers {
@@ -36,25 +54,42 @@ impl<T: Config + Debug> Module<T> {
         RemoveControllers {
             did, controllers, ..
         }: RemoveControllers<T>,
-        OnChainDidDetails {
-            active_controllers, ..
-        }: &mut OnChainDidDetails,
+        details: &mut OnChainDidDetails,
     ) -> Result<(), Error<T>> {
         for controller_did in &controllers {
             if !Self::is_controller(&did, controller_did) {
                 fail!(Error::<T>::NoControllerForDid)
             }
         }
+        ensure!(
+            details.threshold_reachable_with(details.active_controllers - controllers.len() as u32),
+            Error::<T>::ControllerThresholdNotReachable
+        );
 
         for controller_did in &controllers {
             DidControllers::remove(&did, controller_did);
-            *active_controllers -= 1;
+            details.active_controllers -= 1;
         }
 
         deposit_indexed_event!(DidControllersRemoved(did));
         Ok(())
     }
 
+    pub(crate) fn set_controller_threshold_(
+        SetControllerThreshold { did, threshold, .. }: SetControllerThreshold<T>,
+        details: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        ensure!(threshold != Some(0), Error::<T>::InvalidControllerThreshold);
+        details.controller_threshold = threshold;
+        ensure!(
+            details.threshold_reachable_with(details.active_controllers),
+            Error::<T>::ControllerThresholdNotReachable
+        );
+
+        deposit_indexed_event!(DidControllerThresholdSet(did, threshold) over did);
+        Ok(())
+    }
+
     /// Throws an error if `controller` is not the controller of `controlled`
     pub fn ensure_controller(controlled: &Did, controller: &Controller) -> Result<(), Error<T>> {
         if !Self::is_controller(controlled, controller) {
@@ -72,4 +107,115 @@ impl<T: Config + Debug> Module<T> {
     pub fn is_self_controlled(did: &Did) -> bool {
         Self::is_controller(did, &Controller(*did))
     }
+
+    /// Returns paths from the DID to each key which can control it. Controllers are followed transitively
+    /// up to `MaxControllerDepth` hops skipping the controllers already present on the path. At most
+    /// `MAX_CONTROLLER_PATHS` paths are returned.
+    pub fn controller_paths(did: &Did) -> Vec<ControllerPath> {
+        let mut paths = Vec::new();
+        Self::collect_controller_paths(
+            &mut sp_std::vec![*did],
+            &mut paths,
+            T::MaxControllerDepth::get(),
+        );
+
+        paths
+    }
+
+    fn collect_controller_paths(path: &mut Vec<Did>, paths: &mut Vec<ControllerPath>, depth: u16) {
+        if depth == 0 || paths.len() >= MAX_CONTROLLER_PATHS {
+            return;
+        }
+        let controlled = *path.last().unwrap();
+
+        for (Controller(controller), ()) in DidControllers::iter_prefix(controlled) {
+            if controller == controlled {
+                // Keys of a self-controlled DID are already emitted when it's reached as a controller
+                if path.len() == 1 {
+                    Self::push_controller_keys(path, paths);
+                }
+                continue;
+            }
+            if path.contains(&controller) {
+                // Cycle in the controller graph
+                continue;
+            }
+
+            path.push(controller);
+            Self::push_controller_keys(path, paths);
+            Self::collect_controller_paths(path, paths, depth - 1);
+            path.pop();
+
+            if paths.len() >= MAX_CONTROLLER_PATHS {
+                return;
+            }
+        }
+    }
+
+    /// Adds a path ending with each key of the last DID on the path which can control until there are
+    /// `MAX_CONTROLLER_PATHS` paths.
+    fn push_controller_keys(path: &[Did], paths: &mut Vec<ControllerPath>) {
+        let controller = *path.last().unwrap();
+        for (id, key) in DidKeys::iter_prefix(controller) {
+            if paths.len() >= MAX_CONTROLLER_PATHS {
+                return;
+            }
+            if key.can_control() {
+                paths.push(ControllerPath {
+                    dids: path.to_vec(),
+                    key: DidKeyWithId { id, key },
+                });
+            }
+        }
+    }
+
+    /// Ensures that adding `controller` to the DID won't create a cycle in the controller graph, i.e. the DID
+    /// isn't a direct or transitive controller of `controller`. Fails if the controllers of `controller`
+    /// can't be fully walked within `MaxControllerDepth` hops and `MaxControllerGraphReads` reads.
+    pub(crate) fn ensure_no_controller_cycle(
+        did: &Did,
+        controller: &Controller,
+    ) -> Result<(), Error<T>> {
+        if controller.0 == *did {
+            return Ok(());
+        }
+
+        // Each walked DID and each of its controllers is a read
+        let mut reads = 0;
+        let mut read = || -> Result<(), Error<T>> {
+            reads += 1;
+            ensure!(
+                reads <= T::MaxControllerGraphReads::get(),
+                Error::<T>::ControllerGraphTooLarge
+            );
+            Ok(())
+        };
+
+        let mut visited = BTreeSet::new();
+        let mut frontier = sp_std::vec![controller.0];
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            ensure!(
+                depth < T::MaxControllerDepth::get(),
+                Error::<T>::ControllerGraphTooDeep
+            );
+
+            let mut next = Vec::new();
+            for controlled in frontier {
+                read()?;
+                for (Controller(ctrl), ()) in DidControllers::iter_prefix(controlled) {
+                    read()?;
+                    ensure!(ctrl != *did, Error::<T>::ControllerCycle);
+                    if ctrl != controlled && visited.insert(ctrl) {
+                        next.push(ctrl);
+                    }
+                }
+            }
+
+            frontier = next;
+            depth += 1;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,164 @@
+use super::*;
+use crate::ToStateChange;
+use sp_runtime::traits::CheckedSub;
+
+impl<T: Config + Debug> Module<T> {
+    /// Try executing an action signed by a DID protected from replays by its replay id and expiry block instead of
+    /// the signer's nonce. The replay id is marked as used by the signer until the action expires if `f` executes
+    /// successfully.
+    pub(crate) fn try_exec_signed_action_with_deadline_from_onchain_did<A, F, S, R, E>(
+        f: F,
+        action: WithDeadline<T, A>,
+        signature: DidSignature<S>,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, S) -> Result<R, E>,
+        A: Action<T> + ToStateChange<T>,
+        S: Into<Did> + Copy,
+        E: From<Error<T>>,
+    {
+        ensure!(!action.is_empty(), Error::<T>::EmptyPayload);
+        let signer = signature.did.into();
+        Self::ensure_replay_id_can_be_used(&signer, &action)?;
+        ensure!(
+            Self::verify_sig_from_auth_or_control_key(&action, &signature)?,
+            Error::<T>::InvalidSignature
+        );
+
+        let WithDeadline {
+            action,
+            replay_id,
+            expires_at,
+        } = action;
+        let res = f(action, signature.did)?;
+        Self::use_replay_id(signer, replay_id, expires_at);
+
+        Ok(res)
+    }
+
+    /// Try executing an action signed by a controller of the target DID protected from replays by its replay id
+    /// and expiry block instead of the signer's nonce. The replay id is marked as used by the controller until the
+    /// action expires if `f` executes successfully.
+    pub(crate) fn try_exec_signed_action_with_deadline_from_controller<A, F, R, E>(
+        f: F,
+        action: WithDeadline<T, A>,
+        signature: DidSignature<Controller>,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, &mut OnChainDidDetails) -> Result<R, E>,
+        A: Action<T, Target = Did> + ToStateChange<T>,
+        E: From<Error<T>> + From<NonceError>,
+    {
+        ensure!(!action.is_empty(), Error::<T>::EmptyPayload);
+        Self::ensure_replay_id_can_be_used(&signature.did, &action)?;
+        ensure!(
+            Self::verify_sig_from_controller(&action, &signature)?,
+            Error::<T>::InvalidSignature
+        );
+
+        let WithDeadline {
+            action,
+            replay_id,
+            expires_at,
+        } = action;
+        let res = Dids::<T>::try_mutate_exists(action.target(), |details_opt| {
+            WithNonce::try_update_opt_without_increasing_nonce_with(details_opt, |data_opt| {
+                f(action, data_opt.as_mut().unwrap())
+            })
+            .ok_or(Error::<T>::DidDoesNotExist)?
+        })?;
+        Self::use_replay_id(*signature.did, replay_id, expires_at);
+
+        Ok(res)
+    }
+
+    /// Ensures that the action hasn't expired, its expiry block isn't further than `MaxActionLifetime` blocks from
+    /// the current one and its replay id wasn't used by the signer.
+    pub(crate) fn ensure_replay_id_can_be_used<A>(
+        signer: &Did,
+        WithDeadline {
+            replay_id,
+            expires_at,
+            ..
+        }: &WithDeadline<T, A>,
+    ) -> Result<(), Error<T>> {
+        let current_block = <system::Module<T>>::block_number();
+        ensure!(*expires_at >= current_block, Error::<T>::ActionExpired);
+        ensure!(
+            *expires_at - current_block <= T::MaxActionLifetime::get(),
+            Error::<T>::ActionLifetimeTooLong
+        );
+        // Replay ids of expired actions might not be removed yet
+        ensure!(
+            Self::replay_id_expiry(signer, replay_id).map_or(true, |expiry| expiry < current_block),
+            Error::<T>::ReplayIdAlreadyUsed
+        );
+
+        Ok(())
+    }
+
+    /// Marks the replay id as used by the signer until the given block.
+    pub(crate) fn use_replay_id(signer: Did, replay_id: ReplayId, expires_at: T::BlockNumber) {
+        UsedReplayIds::<T>::insert(signer, replay_id, expires_at);
+        ReplayIdsByExpiry::<T>::insert(expires_at, (signer, replay_id), ());
+    }
+
+    /// Removes replay ids of the actions which expired before the current block starting from the expiry block
+    /// where the previous removal stopped. At most `MaxReplayIdRemovalsPerBlock` replay ids are removed and expiry
+    /// blocks are checked, the rest is left for the following blocks. Returns the consumed weight.
+    pub(crate) fn remove_expired_replay_ids(current_block: T::BlockNumber) -> Weight {
+        let expired_at = match current_block.checked_sub(&1u8.into()) {
+            Some(expired_at) => expired_at,
+            None => return 0,
+        };
+        let cursor = Self::replay_ids_cleanup_cursor();
+        let mut block = cursor.unwrap_or(expired_at);
+        let mut budget = T::MaxReplayIdRemovalsPerBlock::get();
+        let (mut removed, mut checked_blocks) = (0, 0);
+
+        while budget > 0 && block <= expired_at {
+            let mut exhausted = true;
+            for ((signer, replay_id), ()) in ReplayIdsByExpiry::<T>::drain_prefix(block) {
+                // The replay id might be used again in an action expiring later
+                UsedReplayIds::<T>::mutate_exists(signer, replay_id, |expiry| {
+                    if *expiry == Some(block) {
+                        expiry.take();
+                    }
+                });
+                removed += 1;
+                budget -= 1;
+
+                if budget == 0 {
+                    exhausted = false;
+                    break;
+                }
+            }
+
+            if exhausted {
+                checked_blocks += 1;
+                budget = budget.saturating_sub(1);
+                block += 1u8.into();
+            }
+        }
+
+        // The cursor is kept only while the removal lags behind, otherwise the next block starts from
+        // the block expired right before it
+        let new_cursor = if block <= expired_at {
+            Some(block)
+        } else {
+            None
+        };
+        let cursor_moved = new_cursor != cursor;
+        if cursor_moved {
+            match new_cursor {
+                Some(block) => ReplayIdsCleanupCursor::<T>::put(block),
+                None => ReplayIdsCleanupCursor::<T>::kill(),
+            }
+        }
+
+        T::DbWeight::get().reads_writes(
+            1 + checked_blocks + removed * 2,
+            cursor_moved as u64 + removed * 2,
+        )
+    }
+}
@@ -0,0 +1,103 @@
+use super::*;
+use frame_support::traits::{Currency, ReservableCurrency};
+use sp_runtime::traits::{Saturating, Zero};
+
+/// Balance of the currency used to reserve storage deposits.
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as system::Config>::AccountId>>::Balance;
+
+/// Item stored in the state for which a deposit is reserved from the account which submitted it.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum DepositItem {
+    /// Details of the off-chain or on-chain DID stored in `Dids`.
+    Did(Did),
+    /// Key of the DID stored in `DidKeys`.
+    DidKey(Did, IncId),
+    /// Service endpoint of the DID stored in `DidServiceEndpoints`.
+    ServiceEndpoint(Did, WrappedBytes),
+    /// Blob stored by the blob module.
+    Blob(#[cfg_attr(feature = "serde", serde(with = "crate::util::hex"))] [u8; 32]),
+    /// BBS+ params stored by the BBS+ module.
+    BBSPlusParams(Did, IncId),
+    /// Version of the DID Document stored in `DidDocumentHistory`. Refunded once the version is pruned.
+    DidDocumentVersion(Did, u32),
+}
+
+/// Deposit reserved from the depositor's balance while the item is stored.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+pub struct Deposit<T: Config> {
+    pub depositor: T::AccountId,
+    pub amount: BalanceOf<T>,
+}
+
+impl<T: Config + Debug> Module<T> {
+    /// Returns the deposit required to store an item of the given encoded size.
+    pub fn deposit_for(byte_size: usize) -> BalanceOf<T> {
+        T::StorageItemDeposit::get()
+            .saturating_add(T::StorageByteDeposit::get().saturating_mul((byte_size as u32).into()))
+    }
+
+    /// Ensures that the depositor can afford the deposits for storing items of the given encoded sizes.
+    /// Must be called before touching the storage as the reservation of deposits happens after the checks.
+    pub(crate) fn ensure_can_reserve_deposits<I>(
+        depositor: &T::AccountId,
+        byte_sizes: I,
+    ) -> Result<(), Error<T>>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let total = byte_sizes
+            .into_iter()
+            .fold(BalanceOf::<T>::zero(), |total, byte_size| {
+                total.saturating_add(Self::deposit_for(byte_size))
+            });
+        ensure!(
+            T::Currency::can_reserve(depositor, total),
+            Error::<T>::InsufficientBalanceForDeposit
+        );
+
+        Ok(())
+    }
+
+    /// Reserves the deposit for storing the item from the depositor's balance. The previous deposit
+    /// of the item, if any, is refunded to its depositor.
+    pub(crate) fn reserve_deposit(
+        depositor: &T::AccountId,
+        item: DepositItem,
+        byte_size: usize,
+    ) -> Result<(), Error<T>> {
+        let amount = Self::deposit_for(byte_size);
+        T::Currency::reserve(depositor, amount)
+            .map_err(|_| Error::<T>::InsufficientBalanceForDeposit)?;
+        Self::refund_deposit(&item);
+
+        Deposits::<T>::insert(
+            item,
+            Deposit {
+                depositor: depositor.clone(),
+                amount,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Refunds the deposit of the removed item to its depositor. Items stored before deposits were introduced
+    /// or added in the genesis don't have a deposit.
+    pub(crate) fn refund_deposit(item: &DepositItem) {
+        if let Some(Deposit { depositor, amount }) = Deposits::<T>::take(item) {
+            T::Currency::unreserve(&depositor, amount);
+        }
+    }
+
+    /// Funds the account so that it can afford the deposits of the items created by the benchmarks.
+    #[cfg(feature = "runtime-benchmarks")]
+    pub(crate) fn fund_for_deposits(account: &T::AccountId) {
+        T::Currency::make_free_balance_be(
+            account,
+            <BalanceOf<T> as sp_runtime::traits::Bounded>::max_value() / 2u32.into(),
+        );
+    }
+}
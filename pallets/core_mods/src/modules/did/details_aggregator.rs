@@ -27,8 +27,8 @@ pub struct AggregatedDidDetailsResponse<T: Config> {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct DidKeyWithId {
-    id: IncId,
-    key: DidKey,
+    pub id: IncId,
+    pub key: DidKey,
 }
 
 /// `ServiceEndpoint` with its identifier.
@@ -36,8 +36,8 @@ pub struct DidKeyWithId {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct ServiceEndpointWithId {
-    id: WrappedBytes,
-    endpoint: ServiceEndpoint,
+    pub id: WrappedBytes,
+    pub endpoint: ServiceEndpoint,
 }
 
 impl<T: Config> AggregatedDidDetailsResponse<T> {
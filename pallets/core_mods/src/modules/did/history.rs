@@ -0,0 +1,362 @@
+use super::*;
+use crate::ToStateChange;
+use frame_support::storage::with_transaction;
+use sp_runtime::{traits::Saturating, TransactionOutcome};
+
+/// Version of the on-chain DID along with the blocks in which it was created and last updated.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DidVersion<T: frame_system::Config> {
+    /// Identifier of the latest version of the DID Document. Starts from 1 and is increased on each update.
+    pub version_id: u32,
+    /// Identifier of the oldest version of the DID Document kept in the history. Versions before it were pruned.
+    pub oldest_version_id: u32,
+    /// Block number in which the DID was registered on chain. For DIDs registered before the history was
+    /// recorded, this is the block number of the runtime upgrade.
+    pub created_at: T::BlockNumber,
+    /// Block number in which the latest version of the DID Document was produced.
+    pub updated_at: T::BlockNumber,
+    /// Total amount of keys, controllers and service endpoints in the latest version of the DID Document.
+    pub document_items: u32,
+}
+
+/// DID Document of the on-chain DID as it was at some version.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DidDocumentVersion<T: frame_system::Config> {
+    pub document: OnChainDidDocument,
+    /// Block number in which this version was produced.
+    pub updated_at: T::BlockNumber,
+    /// Set for the version recorded on the DID removal, which has an empty DID Document.
+    pub deactivated: bool,
+}
+
+/// Selects the version of the DID Document to be resolved.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum DidVersionQuery<T: frame_system::Config> {
+    /// Version with the given identifier.
+    VersionId(u32),
+    /// Version which was the latest one at the given block number.
+    VersionTime(T::BlockNumber),
+}
+
+/// Result of the DID resolution from the recorded history.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HistoricalDidResolution<T: frame_system::Config> {
+    pub document: OnChainDidDocument,
+    pub version_id: u32,
+    pub created_at: T::BlockNumber,
+    pub updated_at: T::BlockNumber,
+    /// Identifier and block number of the version which replaced the resolved one, if any.
+    pub next_version: Option<(u32, T::BlockNumber)>,
+    /// Whether the DID was removed in the resolved version.
+    pub deactivated: bool,
+}
+
+impl<T: Config + Debug> Module<T> {
+    /// Records the current DID Document of the on-chain DID as its new version without reserving a deposit.
+    /// Used only for the DIDs added in the genesis.
+    pub(crate) fn record_did_version(did: Did) {
+        Self::record_document_version(did, Self::onchain_did_document(&did), false);
+    }
+
+    /// Records the current DID Document of the on-chain DID registered before the history was recorded as its first
+    /// version produced in the block from which the history is recorded. Does nothing for DIDs already having a version.
+    /// DID Documents having more than `MaxDidDocumentItems` items aren't recorded, so the history of such DIDs starts
+    /// from their next version.
+    pub(crate) fn record_initial_version(did: &Did) {
+        let recorded_since = match Self::did_history_recorded_since() {
+            Some(recorded_since) if !DidVersions::<T>::contains_key(did) => recorded_since,
+            _ => return,
+        };
+        if !Self::did(did).map_or(false, |details| details.is_onchain()) {
+            return;
+        }
+
+        let max_items = T::MaxDidDocumentItems::get();
+        let document = Self::onchain_did_document_with_max_items(did, max_items.saturating_add(1));
+        if document.item_count() > max_items {
+            return;
+        }
+
+        DidVersions::<T>::insert(
+            did,
+            DidVersion {
+                version_id: 1,
+                oldest_version_id: 1,
+                created_at: recorded_since,
+                updated_at: recorded_since,
+                document_items: document.item_count(),
+            },
+        );
+        DidDocumentHistory::<T>::insert(
+            did,
+            1,
+            DidDocumentVersion {
+                document,
+                updated_at: recorded_since,
+                deactivated: false,
+            },
+        );
+    }
+
+    /// Records the current DID Document of the on-chain DID as its new version. The deposit for the stored document
+    /// is reserved from the depositor's balance and kept reserved until the version is pruned.
+    /// At most one item more than `MaxDidDocumentItems` is read before the document is rejected.
+    pub(crate) fn record_did_version_with_deposit(
+        did: Did,
+        depositor: &T::AccountId,
+    ) -> Result<(), Error<T>> {
+        let document = Self::onchain_did_document_with_max_items(
+            &did,
+            T::MaxDidDocumentItems::get().saturating_add(1),
+        );
+
+        Self::record_document_version_with_deposit(did, document, depositor)
+    }
+
+    /// Records the given DID Document as the new version of the on-chain DID and reserves the deposit for it.
+    /// Fails if the document has more than `MaxDidDocumentItems` items.
+    pub(crate) fn record_document_version_with_deposit(
+        did: Did,
+        document: OnChainDidDocument,
+        depositor: &T::AccountId,
+    ) -> Result<(), Error<T>> {
+        ensure!(
+            document.item_count() <= T::MaxDidDocumentItems::get(),
+            Error::<T>::TooManyDidDocumentItems
+        );
+
+        let byte_size = document.encoded_size();
+        let version_id = Self::record_document_version(did, document, false);
+
+        Self::reserve_deposit(
+            depositor,
+            DepositItem::DidDocumentVersion(did, version_id),
+            byte_size,
+        )
+    }
+
+    /// Records an empty DID Document as the last version of the removed on-chain DID. No deposit is reserved
+    /// as the document is empty and such a version is recorded only once per DID.
+    pub(crate) fn record_did_removal(did: Did) {
+        Self::record_document_version(did, Default::default(), true);
+    }
+
+    fn record_document_version(did: Did, document: OnChainDidDocument, deactivated: bool) -> u32 {
+        let now = <system::Module<T>>::block_number();
+        let version_id = DidVersions::<T>::mutate(did, |version| {
+            let version = version.get_or_insert_with(|| DidVersion {
+                version_id: 0,
+                oldest_version_id: 1,
+                created_at: now,
+                updated_at: now,
+                document_items: 0,
+            });
+            version.version_id += 1;
+            version.updated_at = now;
+            version.document_items = document.item_count();
+
+            version.version_id
+        });
+
+        DidDocumentHistory::<T>::insert(
+            did,
+            version_id,
+            DidDocumentVersion {
+                document,
+                updated_at: now,
+                deactivated,
+            },
+        );
+
+        version_id
+    }
+
+    /// Removes up to `max_versions` oldest versions of the DID Document which were replaced at least
+    /// `DidVersionRetentionPeriod` blocks ago and refunds their deposits. The latest version is never pruned.
+    pub(crate) fn prune_did_history_(did: Did, max_versions: u32) -> Result<(), Error<T>> {
+        let now = <system::Module<T>>::block_number();
+        let retention_period = T::DidVersionRetentionPeriod::get();
+
+        let oldest_version_id =
+            DidVersions::<T>::try_mutate(did, |version| -> Result<_, Error<T>> {
+                let version = version.as_mut().ok_or(Error::<T>::NoPrunableDidVersions)?;
+                let mut pruned = 0;
+
+                while pruned < max_versions && version.oldest_version_id < version.version_id {
+                    let next_version_id = version.oldest_version_id + 1;
+                    // A version is replaced in the block its next version was produced in
+                    match Self::did_document_version(did, next_version_id) {
+                        Some(next) if next.updated_at.saturating_add(retention_period) <= now => {}
+                        _ => break,
+                    }
+
+                    DidDocumentHistory::<T>::remove(did, version.oldest_version_id);
+                    Self::refund_deposit(&DepositItem::DidDocumentVersion(
+                        did,
+                        version.oldest_version_id,
+                    ));
+                    version.oldest_version_id = next_version_id;
+                    pruned += 1;
+                }
+                ensure!(pruned > 0, Error::<T>::NoPrunableDidVersions);
+
+                Ok(version.oldest_version_id)
+            })?;
+
+        deposit_indexed_event!(DidHistoryPruned(did, oldest_version_id) over did);
+        Ok(())
+    }
+
+    /// Executes `f` updating the DID Document of the on-chain DID and records the resulting document as its new
+    /// version. All changes made by `f` are discarded if the deposit for the new version can't be reserved.
+    /// The DID Document of the DID registered before the history was recorded is recorded as its first version beforehand.
+    pub(crate) fn exec_doc_update<F, R, E>(did: Did, depositor: &T::AccountId, f: F) -> Result<R, E>
+    where
+        F: FnOnce() -> Result<R, E>,
+        E: From<Error<T>>,
+    {
+        with_transaction(|| {
+            Self::record_initial_version(&did);
+
+            let res = f().and_then(|res| {
+                Self::record_did_version_with_deposit(did, depositor)?;
+
+                Ok(res)
+            });
+
+            if res.is_ok() {
+                TransactionOutcome::Commit(res)
+            } else {
+                TransactionOutcome::Rollback(res)
+            }
+        })
+    }
+
+    /// Same as `Self::try_exec_signed_action_from_controllers` except that the DID Document of the target DID
+    /// is recorded as its new version once the action succeeds. The deposit for the new version is reserved
+    /// from the depositor's balance.
+    pub(crate) fn try_exec_signed_doc_update_from_controllers<A, F, R, E>(
+        depositor: &T::AccountId,
+        f: F,
+        action: A,
+        signatures: Vec<DidSignature<Controller>>,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, &mut OnChainDidDetails) -> Result<R, E>,
+        A: ActionWithNonce<T, Target = Did> + ToStateChange<T>,
+        E: From<Error<T>> + From<NonceError>,
+    {
+        let did = action.target();
+
+        Self::exec_doc_update(did, depositor, || {
+            Self::try_exec_signed_action_from_controllers(f, action, signatures)
+        })
+    }
+
+    /// Resolves the given version of the on-chain DID from the recorded history. Returns `None` if there is
+    /// no such version or it was pruned. Versions remain resolvable after the DID is removed, and the version
+    /// recorded on the removal is resolved as deactivated.
+    pub fn resolve_did_at(
+        did: &Did,
+        query: DidVersionQuery<T>,
+    ) -> Option<HistoricalDidResolution<T>> {
+        let DidVersion {
+            version_id: latest_version_id,
+            oldest_version_id,
+            created_at,
+            ..
+        } = match Self::did_version(did) {
+            Some(version) => version,
+            None => return Self::resolve_unrecorded_did_at(did, query),
+        };
+        let version_id = match query {
+            DidVersionQuery::VersionId(version_id) => version_id,
+            DidVersionQuery::VersionTime(block_number) => {
+                Self::version_at_block(did, oldest_version_id, latest_version_id, block_number)?
+            }
+        };
+        let DidDocumentVersion {
+            document,
+            updated_at,
+            deactivated,
+        } = Self::did_document_version(did, version_id)?;
+        let next_version = version_id.checked_add(1).and_then(|next_version_id| {
+            Self::did_document_version(did, next_version_id)
+                .map(|next| (next_version_id, next.updated_at))
+        });
+
+        Some(HistoricalDidResolution {
+            document,
+            version_id,
+            created_at,
+            updated_at,
+            next_version,
+            deactivated,
+        })
+    }
+
+    /// Finds the latest version produced at or before the given block among the kept versions. Versions are produced
+    /// in non-decreasing blocks, so the version ids index the history by block and a binary search takes
+    /// a logarithmic amount of reads.
+    fn version_at_block(
+        did: &Did,
+        oldest_version_id: u32,
+        latest_version_id: u32,
+        block_number: T::BlockNumber,
+    ) -> Option<u32> {
+        let produced_by = |version_id| {
+            Self::did_document_version(did, version_id)
+                .map_or(false, |version| version.updated_at <= block_number)
+        };
+        if !produced_by(oldest_version_id) {
+            return None;
+        }
+
+        let (mut low, mut high) = (oldest_version_id, latest_version_id);
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            if produced_by(mid) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        Some(low)
+    }
+
+    /// Resolves the on-chain DID registered before the history was recorded and not updated since. Its current
+    /// DID Document is resolved as the first version produced in the block from which the history is recorded.
+    fn resolve_unrecorded_did_at(
+        did: &Did,
+        query: DidVersionQuery<T>,
+    ) -> Option<HistoricalDidResolution<T>> {
+        let recorded_since = Self::did_history_recorded_since()?;
+        if !Self::did(did)?.is_onchain() {
+            return None;
+        }
+
+        match query {
+            DidVersionQuery::VersionId(1) => {}
+            DidVersionQuery::VersionTime(block_number) if block_number >= recorded_since => {}
+            _ => return None,
+        }
+
+        Some(HistoricalDidResolution {
+            document: Self::onchain_did_document(did),
+            version_id: 1,
+            created_at: recorded_since,
+            updated_at: recorded_since,
+            next_version: None,
+            deactivated: false,
+        })
+    }
+}
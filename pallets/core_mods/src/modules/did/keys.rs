@@ -1,4 +1,5 @@
 use super::*;
+use sp_runtime::traits::SaturatedConversion;
 use sp_std::borrow::Borrow;
 
 #[derive(Encode, Decode, Clone, Debug, PartialEq, Eq, PartialOrd)]
@@ -9,6 +10,12 @@ pub struct DidKey {
     pub public_key: PublicKey,
     /// The different verification relationships the above key has with the DID.
     pub ver_rels: VerRelType,
+    /// Block number starting from which (inclusive) the key can be used. `None` means no lower bound.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub valid_from: Option<u32>,
+    /// Block number up to which (inclusive) the key can be used. `None` means no upper bound.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub valid_until: Option<u32>,
 }
 
 bitflags::bitflags! {
@@ -42,6 +49,7 @@ pub struct PublicKeyParams<T> {
     pub ed25519: T,
     pub secp256k1: T,
     pub x25519: T,
+    pub secp256r1: T,
 }
 
 impl<T> PublicKeyParams<T> {
@@ -51,6 +59,7 @@ impl<T> PublicKeyParams<T> {
             PublicKey::Ed25519(_) => self.ed25519 = f(self.ed25519),
             PublicKey::Secp256k1(_) => self.secp256k1 = f(self.secp256k1),
             PublicKey::X25519(_) => self.x25519 = f(self.x25519),
+            PublicKey::Secp256r1(_) => self.secp256r1 = f(self.secp256r1),
         };
 
         self
@@ -63,9 +72,23 @@ impl DidKey {
         DidKey {
             public_key: public_key.into(),
             ver_rels,
+            valid_from: None,
+            valid_until: None,
         }
     }
 
+    /// Sets the window of block numbers in which the key can be used.
+    pub fn with_validity_window(
+        mut self,
+        valid_from: Option<u32>,
+        valid_until: Option<u32>,
+    ) -> Self {
+        self.valid_from = valid_from;
+        self.valid_until = valid_until;
+
+        self
+    }
+
     /// Add all possible verification relationships for a given key
     pub fn new_with_all_relationships(public_key: impl Into<PublicKey>) -> Self {
         let public_key = public_key.into();
@@ -91,6 +114,20 @@ impl DidKey {
         !self.can_sign() ^ (self.ver_rels & VerRelType::ALL_FOR_SIGNING == self.ver_rels)
     }
 
+    /// Checks if the validity window is well-formed, i.e. it doesn't end before it starts.
+    pub fn has_valid_window(&self) -> bool {
+        match (self.valid_from, self.valid_until) {
+            (Some(from), Some(until)) => from <= until,
+            _ => true,
+        }
+    }
+
+    /// Checks if the key can be used at the given block number.
+    pub fn is_active_at(&self, block_number: u32) -> bool {
+        self.valid_from.map_or(true, |from| from <= block_number)
+            && self.valid_until.map_or(true, |until| block_number <= until)
+    }
+
     pub fn can_control(&self) -> bool {
         self.is_valid() && self.ver_rels.intersects(VerRelType::CAPABILITY_INVOCATION)
     }
@@ -113,6 +150,7 @@ impl DidKey {
 
 impl<T: Config + Debug> Module<T> {
     pub(crate) fn add_keys_(
+        depositor: &T::AccountId,
         AddKeys { did, keys, .. }: AddKeys<T>,
         OnChainDidDetails {
             active_controllers,
@@ -123,6 +161,10 @@ impl<T: Config + Debug> Module<T> {
     ) -> Result<(), Error<T>> {
         // If DID was not self controlled first, check if it can become by looking
         let (keys_to_insert, controller_keys_count) = Self::prepare_keys_to_insert(keys)?;
+        Self::ensure_can_reserve_deposits(
+            depositor,
+            keys_to_insert.iter().map(Encode::encoded_size),
+        )?;
         *active_controller_keys += controller_keys_count;
 
         // Make self controlled if needed
@@ -132,8 +174,8 @@ impl<T: Config + Debug> Module<T> {
             *active_controllers += 1;
         }
 
-        for (key, key_id) in keys_to_insert.iter().zip(last_key_id) {
-            DidKeys::insert(did, key_id, key);
+        for (key, key_id) in keys_to_insert.into_iter().zip(last_key_id) {
+            Self::insert_key_with_deposit(depositor, did, key_id, key)?;
         }
 
         deposit_indexed_event!(DidKeysAdded(did));
@@ -142,38 +184,101 @@ impl<T: Config + Debug> Module<T> {
 
     pub(crate) fn remove_keys_(
         RemoveKeys { did, keys, .. }: RemoveKeys<T>,
-        OnChainDidDetails {
-            active_controllers,
-            active_controller_keys,
-            ..
-        }: &mut OnChainDidDetails,
+        details: &mut OnChainDidDetails,
     ) -> Result<(), Error<T>> {
+        let mut removed_controller_keys_count = 0;
         for key_id in &keys {
             let key = DidKeys::get(&did, key_id).ok_or(Error::<T>::NoKeyForDid)?;
 
             if key.can_control() {
-                *active_controller_keys -= 1;
+                removed_controller_keys_count += 1;
             }
         }
+        details.active_controller_keys -= removed_controller_keys_count;
 
-        for key in &keys {
-            DidKeys::remove(did, key);
+        // If no self-control keys exist for the given DID, remove self-control
+        let remove_self_controlled =
+            details.active_controller_keys == 0 && Self::is_self_controlled(&did);
+        ensure!(
+            !remove_self_controlled
+                || details.threshold_reachable_with(details.active_controllers - 1),
+            Error::<T>::ControllerThresholdNotReachable
+        );
+
+        for key_id in keys {
+            Self::remove_key(did, key_id);
         }
 
-        // If no self-control keys exist for the given DID, remove self-control
-        let remove_self_controlled = *active_controller_keys == 0 && Self::is_self_controlled(&did);
         if remove_self_controlled {
             DidControllers::remove(&did, &Controller(did));
-            *active_controllers -= 1;
+            details.active_controllers -= 1;
         }
 
         deposit_indexed_event!(DidKeysRemoved(did));
         Ok(())
     }
 
-    /// Return `did`'s key with id `key_id` only if has control capability, otherwise returns an error.
+    pub(crate) fn rotate_keys_(
+        depositor: &T::AccountId,
+        RotateKeys {
+            did, remove, add, ..
+        }: RotateKeys<T>,
+        details: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        // Validate everything before touching the storage so that the rotation is either fully applied or not at all
+        let mut removed_controller_keys_count = 0;
+        for key_id in &remove {
+            let key = DidKeys::get(&did, key_id).ok_or(Error::<T>::NoKeyForDid)?;
+
+            if key.can_control() {
+                removed_controller_keys_count += 1;
+            }
+        }
+        let (keys_to_insert, added_controller_keys_count) = Self::prepare_keys_to_insert(add)?;
+        Self::ensure_can_reserve_deposits(
+            depositor,
+            keys_to_insert.iter().map(Encode::encoded_size),
+        )?;
+
+        let active_controller_keys = details.active_controller_keys - removed_controller_keys_count
+            + added_controller_keys_count;
+        let is_self_controlled = Self::is_self_controlled(&did);
+        let remove_self_controlled = active_controller_keys == 0 && is_self_controlled;
+        ensure!(
+            !remove_self_controlled
+                || details.threshold_reachable_with(details.active_controllers - 1),
+            Error::<T>::ControllerThresholdNotReachable
+        );
+        details.active_controller_keys = active_controller_keys;
+
+        for key_id in &remove {
+            Self::remove_key(did, *key_id);
+        }
+
+        let mut added = Vec::with_capacity(keys_to_insert.len());
+        for (key, key_id) in keys_to_insert.into_iter().zip(&mut details.last_key_id) {
+            Self::insert_key_with_deposit(depositor, did, key_id, key)?;
+            added.push(key_id);
+        }
+
+        // Keep self-control consistent with the presence of the controller keys
+        if active_controller_keys > 0 && !is_self_controlled {
+            DidControllers::insert(&did, &Controller(did), ());
+            details.active_controllers += 1;
+        } else if remove_self_controlled {
+            DidControllers::remove(&did, &Controller(did));
+            details.active_controllers -= 1;
+        }
+
+        let removed: Vec<_> = remove.into_iter().collect();
+        deposit_indexed_event!(DidKeysRotated(did, removed, added) over did);
+        Ok(())
+    }
+
+    /// Return `did`'s key with id `key_id` only if has control capability and is within its validity window,
+    /// otherwise returns an error.
     pub fn control_key(did: &Controller, key_id: IncId) -> Result<PublicKey, Error<T>> {
-        let did_key = DidKeys::get(did.0, key_id).ok_or(Error::<T>::NoKeyForDid)?;
+        let did_key = Self::active_key(&did.0, key_id)?;
 
         if did_key.can_control() {
             Ok(did_key.public_key)
@@ -182,9 +287,10 @@ impl<T: Config + Debug> Module<T> {
         }
     }
 
-    /// Return `did`'s key with id `key_id` only if it can authenticate or control otherwise returns an error
+    /// Return `did`'s key with id `key_id` only if it can authenticate or control and is within its validity window
+    /// otherwise returns an error
     pub fn auth_or_control_key(did: &Did, key_id: IncId) -> Result<PublicKey, Error<T>> {
-        let did_key = DidKeys::get(did, key_id).ok_or(Error::<T>::NoKeyForDid)?;
+        let did_key = Self::active_key(did, key_id)?;
 
         if did_key.can_authenticate_or_control() {
             Ok(did_key.public_key)
@@ -193,6 +299,65 @@ impl<T: Config + Debug> Module<T> {
         }
     }
 
+    /// Return `did`'s key with id `key_id` only if the current block is within its validity window.
+    fn active_key(did: &Did, key_id: IncId) -> Result<DidKey, Error<T>> {
+        let did_key = DidKeys::get(did, key_id).ok_or(Error::<T>::NoKeyForDid)?;
+        let current_block = <system::Module<T>>::block_number().saturated_into::<u32>();
+        ensure!(
+            did_key.is_active_at(current_block),
+            Error::<T>::KeyOutsideValidityWindow
+        );
+
+        Ok(did_key)
+    }
+
+    /// Inserts the key of the DID and adds it to the index of DIDs by public key.
+    pub(crate) fn insert_key(did: Did, key_id: IncId, key: DidKey) {
+        PublicKeyDids::insert(&key.public_key, (did, key_id), ());
+        DidKeys::insert(did, key_id, key);
+    }
+
+    /// Inserts the key of the DID reserving the storage deposit for it from the depositor's balance.
+    pub(crate) fn insert_key_with_deposit(
+        depositor: &T::AccountId,
+        did: Did,
+        key_id: IncId,
+        key: DidKey,
+    ) -> Result<(), Error<T>> {
+        Self::reserve_deposit(
+            depositor,
+            DepositItem::DidKey(did, key_id),
+            key.encoded_size(),
+        )?;
+        Self::insert_key(did, key_id, key);
+
+        Ok(())
+    }
+
+    /// Removes the key of the DID along with its entry in the index of DIDs by public key and refunds its deposit.
+    pub(crate) fn remove_key(did: Did, key_id: IncId) {
+        if let Some(DidKey { public_key, .. }) = DidKeys::take(did, key_id) {
+            PublicKeyDids::remove(public_key, (did, key_id));
+            Self::refund_deposit(&DepositItem::DidKey(did, key_id));
+        }
+    }
+
+    /// Removes all keys of the removed DID along with their entries in the index of DIDs by public key and refunds
+    /// their deposits.
+    pub(crate) fn remove_all_keys(did: Did) {
+        for (key_id, DidKey { public_key, .. }) in DidKeys::drain_prefix(did) {
+            PublicKeyDids::remove(public_key, (did, key_id));
+            Self::refund_deposit(&DepositItem::DidKey(did, key_id));
+        }
+    }
+
+    /// Returns DIDs having the given public key along with the ids of the key in each DID.
+    pub fn dids_by_public_key(public_key: &PublicKey) -> Vec<(Did, IncId)> {
+        PublicKeyDids::iter_prefix(public_key)
+            .map(|(did_and_key_id, _)| did_and_key_id)
+            .collect()
+    }
+
     /// Prepare `DidKey`s to insert. The DID is assumed to be self controlled as well if there is any key
     /// that is capable of invoking a capability. Returns the keys along with the
     /// amount of controller keys being met. The following logic is contentious.
@@ -202,8 +367,15 @@ impl<T: Config + Debug> Module<T> {
         let mut controller_keys_count = 0;
         let mut keys_to_insert = Vec::with_capacity(keys.len());
         for key in keys {
+            ensure!(key.has_valid_window(), Error::<T>::InvalidKeyValidityWindow);
+            ensure!(
+                key.public_key.is_well_formed(),
+                Error::<T>::InvalidPublicKey
+            );
+
             let key = if key.ver_rels.is_empty() {
                 DidKey::new_with_all_relationships(key.public_key)
+                    .with_validity_window(key.valid_from, key.valid_until)
             } else {
                 if !key.is_valid() {
                     fail!(Error::<T>::IncompatibleVerificationRelation)
@@ -3,19 +3,26 @@ use crate::{
     deposit_indexed_event, impl_action_with_nonce, impl_bits_conversion, impl_wrapper,
     keys_and_sigs::{PublicKey, SigValue},
     util::{with_nonce::NonceError, *},
-    Action, ActionWithNonce, StorageVersion,
+    Action, ActionWithNonce,
 };
 pub use actions::*;
 pub use base::{offchain, onchain, signature};
 use codec::{Decode, Encode};
 use common::arith_utils::DivCeil;
 use core::fmt::Debug;
+pub use deposits::*;
 pub use details_aggregator::*;
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult, ensure, fail,
-    traits::Get, weights::Weight,
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::DispatchResult,
+    ensure, fail,
+    traits::{Get, ReservableCurrency},
+    weights::Weight,
 };
 use frame_system::{self as system, ensure_signed};
+pub use history::*;
+pub use pagination::*;
+pub use resolution::*;
 use sp_runtime::traits::Hash;
 use sp_std::{
     collections::btree_set::BTreeSet,
@@ -25,15 +32,24 @@ use sp_std::{
 use weights::*;
 
 pub use base::*;
-pub use controllers::Controller;
+pub use controllers::{Controller, ControllerPath, MAX_CONTROLLER_PATHS};
 pub use keys::{DidKey, VerRelType};
-pub use service_endpoints::ServiceEndpoint;
+pub use recovery::*;
+pub use service_endpoints::{ServiceEndpoint, ServiceEndpointProperty, ServiceEndpointType};
 
+mod accounts;
 mod actions;
 mod base;
+mod batch_update;
 mod controllers;
+mod deadline;
+mod deposits;
 mod details_aggregator;
+mod history;
 mod keys;
+mod pagination;
+mod recovery;
+mod resolution;
 mod service_endpoints;
 mod weights;
 
@@ -60,6 +76,80 @@ pub trait Config: system::Config {
     type MaxServiceEndpointOriginSize: Get<u16>;
     /// Weight per byte of service endpoint's `origin`
     type ServiceEndpointOriginPerByteWeight: Get<Weight>;
+    /// Maximum number of service endpoint's `types`
+    type MaxServiceEndpointTypes: Get<u16>;
+    /// Maximum byte size of service endpoint's custom type
+    type MaxServiceEndpointTypeSize: Get<u16>;
+    /// Weight per byte of service endpoint's custom types
+    type ServiceEndpointTypePerByteWeight: Get<Weight>;
+    /// Maximum number of service endpoint's `properties`
+    type MaxServiceEndpointProperties: Get<u16>;
+    /// Maximum number of values of service endpoint's property
+    type MaxServiceEndpointPropertyValues: Get<u16>;
+    /// Maximum byte size of service endpoint's property name or value
+    type MaxServiceEndpointPropertySize: Get<u16>;
+    /// Weight per byte of service endpoint's properties
+    type ServiceEndpointPropertyPerByteWeight: Get<Weight>;
+    /// Maximum number of recovery guardians of a DID
+    type MaxRecoveryGuardians: Get<u16>;
+    /// Minimum amount of blocks between the initiation and the execution of the DID recovery
+    type MinRecoveryDelay: Get<Self::BlockNumber>;
+    /// Maximum amount of blocks before the expiry of an action protected from replays by a replay id
+    type MaxActionLifetime: Get<Self::BlockNumber>;
+    /// Maximum amount of expired replay ids removed at the start of each block. Remaining ones are removed
+    /// in the following blocks
+    type MaxReplayIdRemovalsPerBlock: Get<u32>;
+    /// Currency used to reserve storage deposits from the accounts submitting the stored items
+    type Currency: ReservableCurrency<Self::AccountId>;
+    /// Deposit reserved for each stored item regardless of its size
+    type StorageItemDeposit: Get<BalanceOf<Self>>;
+    /// Deposit reserved for each byte of the stored item
+    type StorageByteDeposit: Get<BalanceOf<Self>>;
+    /// Maximum amount of hops made while walking the controller graph
+    type MaxControllerDepth: Get<u16>;
+    /// Whether adding a controller which would create a cycle in the controller graph is rejected
+    type PreventControllerCycles: Get<bool>;
+    /// Maximum amount of reads made while walking the controller graph to check that adding a controller
+    /// doesn't create a cycle. Each walked DID and each of its controllers takes a read
+    type MaxControllerGraphReads: Get<u32>;
+    /// Maximum amount of keys, controllers and service endpoints an on-chain DID can have for its DID Document to be
+    /// recorded in the history. Bounds the reads made while recording a new version of the DID Document
+    type MaxDidDocumentItems: Get<u32>;
+    /// Minimum amount of blocks for which a replaced version of the DID Document is kept in the history before it
+    /// can be pruned
+    type DidVersionRetentionPeriod: Get<Self::BlockNumber>;
+    /// SHA-256 hash of the RP ID of the WebAuthn Relying Party which WebAuthn assertions used as DID signatures must
+    /// be made for. Assertions made for other Relying Parties aren't accepted as the key holder doesn't intend them
+    /// to authorize actions on the chain
+    type WebAuthnRpIdHash: Get<[u8; 32]>;
+}
+
+/// Defines version of the DID module's storage. The first two versions are shared with the other modules
+/// and have the same encoding as `crate::StorageVersion`.
+#[derive(Encode, Decode, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StorageVersion {
+    /// The old version which supports only a single key for DID.
+    SingleKey,
+    /// Multi-key DID.
+    MultiKey,
+    /// Multi-key DID with keys having validity windows.
+    MultiKeyWithValidity,
+    /// Multi-key DID with keys having validity windows and an optional controller threshold.
+    MultiKeyWithControllerThreshold,
+    /// Multi-key DID having service endpoints with arbitrary types and properties.
+    MultiKeyWithTypedServiceEndpoints,
+    /// Multi-key DID with the index of DIDs by account.
+    MultiKeyWithAccountIndex,
+    /// Multi-key DID with the indices of DIDs by account and by public key.
+    MultiKeyWithPublicKeyIndex,
+    /// Multi-key DID with the recorded history of on-chain DID Documents.
+    MultiKeyWithDidHistory,
+}
+
+impl Default for StorageVersion {
+    fn default() -> Self {
+        Self::SingleKey
+    }
 }
 
 decl_error! {
@@ -96,7 +186,77 @@ decl_error! {
         ControllerIsAlreadyAdded,
         InvalidServiceEndpoint,
         ServiceEndpointAlreadyExists,
-        ServiceEndpointDoesNotExist
+        ServiceEndpointDoesNotExist,
+        /// The key's validity window ends before it starts
+        InvalidKeyValidityWindow,
+        /// The key can't be used at the current block as it's outside of the key's validity window
+        KeyOutsideValidityWindow,
+        /// Fewer distinct controllers than required by the DID's controller threshold signed the update
+        NotEnoughControllerSignatures,
+        /// More than one signature from the same controller was provided
+        DuplicateControllerSignature,
+        /// Controller threshold must be greater than zero
+        InvalidControllerThreshold,
+        /// Controller threshold can't be met by the DID's controllers
+        ControllerThresholdNotReachable,
+        /// The DID was removed before and can't be registered again
+        DidIsDeactivated,
+        /// Public key bytes don't encode a valid key of the specified type
+        InvalidPublicKey,
+        /// Batch update doesn't contain any operation
+        NoOperationProvided,
+        /// The DID has no recovery config
+        NoRecoveryConfig,
+        /// Recovery config has no guardians, too many guardians, an unreachable threshold, a too short delay or lists the DID itself
+        InvalidRecoveryConfig,
+        /// The signer is not a recovery guardian of the DID
+        NotAGuardian,
+        /// Fewer distinct guardians than required by the recovery config signed the request
+        NotEnoughGuardianSignatures,
+        /// More than one signature from the same guardian was provided
+        DuplicateGuardianSignature,
+        /// The DID already has a pending recovery
+        RecoveryInProgress,
+        /// The DID has no pending recovery
+        NoRecoveryInProgress,
+        /// The recovery delay hasn't passed yet
+        RecoveryDelayNotElapsed,
+        /// The recovery key must be a single key which can control the DID
+        InvalidRecoveryKey,
+        /// The account linked to the DID must submit the linking transaction
+        NotSignedByAccount,
+        /// The account is already linked to the DID
+        AccountAlreadyLinked,
+        /// The account isn't linked to the DID
+        AccountNotLinked,
+        /// The action's expiry block has passed
+        ActionExpired,
+        /// The action's expiry block is further than `MaxActionLifetime` blocks from the current one
+        ActionLifetimeTooLong,
+        /// The replay id was already used by the signer in an action which hasn't expired yet
+        ReplayIdAlreadyUsed,
+        /// The account can't afford the storage deposit
+        InsufficientBalanceForDeposit,
+        /// The CID of the off-chain DID Doc is neither a CIDv0 nor a CIDv1
+        InvalidCidVersion,
+        /// The CID of the off-chain DID Doc has an unsupported content type
+        UnsupportedCidCodec,
+        /// The CID's multihash is truncated or its digest size doesn't match the declared one
+        InvalidCidMultihash,
+        /// The URL of the off-chain DID Doc contains forbidden characters or has no host
+        InvalidUrl,
+        /// The URL of the off-chain DID Doc has a scheme which isn't allowed
+        UnsupportedUrlScheme,
+        /// Adding the controller would create a cycle in the controller graph
+        ControllerCycle,
+        /// The controller graph can't be walked within `MaxControllerDepth` hops
+        ControllerGraphTooDeep,
+        /// The controller graph can't be walked within `MaxControllerGraphReads` reads
+        ControllerGraphTooLarge,
+        /// The DID Document has more than `MaxDidDocumentItems` keys, controllers and service endpoints
+        TooManyDidDocumentItems,
+        /// None of the versions of the DID Document has been replaced for `DidVersionRetentionPeriod` blocks yet
+        NoPrunableDidVersions
     }
 }
 
@@ -128,6 +288,28 @@ decl_event!(
         DidServiceEndpointAdded(Did),
         DidServiceEndpointRemoved(Did),
         OnChainDidRemoved(Did),
+        /// Keys of the DID were rotated. Contains ids of the removed keys followed by ids of the added keys.
+        DidKeysRotated(Did, Vec<IncId>, Vec<IncId>),
+        /// Controller threshold of the DID was changed. `None` means a signature from any single controller is enough.
+        DidControllerThresholdSet(Did, Option<u32>),
+        /// Off-chain DID was converted to the on-chain DID. Contains reference to the previously used off-chain DID Doc.
+        OffChainDidConvertedToOnChain(Did, OffChainDidDocRef),
+        /// Recovery guardians of the DID were set or removed.
+        DidRecoveryConfigSet(Did),
+        /// Guardians initiated the recovery of the DID.
+        DidRecoveryInitiated(Did),
+        /// Pending recovery of the DID was cancelled.
+        DidRecoveryCancelled(Did),
+        /// The DID was recovered. Contains id of the added controller key.
+        DidRecovered(Did, IncId),
+        /// Service endpoint of the DID was replaced.
+        DidServiceEndpointUpdated(Did),
+        /// An account was linked to the DID.
+        DidAccountLinked(Did),
+        /// An account was unlinked from the DID.
+        DidAccountUnlinked(Did),
+        /// Oldest versions of the DID Document were pruned from the history. Contains id of the oldest kept version.
+        DidHistoryPruned(Did, u32),
     }
 );
 
@@ -141,6 +323,39 @@ decl_storage! {
         pub DidControllers get(fn bound_controller): double_map hasher(blake2_128_concat) Did, hasher(blake2_128_concat) Controller => Option<()>;
         /// Stores service endpoints of a DID as (DID, endpoint id) -> ServiceEndpoint.
         pub DidServiceEndpoints get(fn did_service_endpoints): double_map hasher(blake2_128_concat) Did, hasher(blake2_128_concat) WrappedBytes => Option<ServiceEndpoint>;
+        /// Stores tombstones of the removed DIDs as DID -> block number of the removal. A DID having a tombstone can't be registered again.
+        pub DeactivatedDids get(fn deactivated_did): map hasher(blake2_128_concat) Did => Option<T::BlockNumber>;
+        /// Stores accounts linked to an on-chain DID as (DID, account) -> zero-sized record.
+        pub DidLinkedAccounts get(fn did_linked_account): double_map hasher(blake2_128_concat) Did, hasher(blake2_128_concat) T::AccountId => Option<()>;
+        /// Reverse index of DIDs by account as (account, DID) -> zero-sized record. Contains on-chain DIDs linked to the account
+        /// and off-chain DIDs owned by it.
+        pub AccountDids get(fn account_did): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) Did => Option<()>;
+        /// Reverse index of keys as (public key, (DID, key id)) -> zero-sized record. Allows to find DIDs having the given
+        /// public key without iterating over all keys.
+        pub PublicKeyDids get(fn public_key_did): double_map hasher(opaque_blake2_256) PublicKey, hasher(blake2_128_concat) (Did, IncId) => Option<()>;
+        /// Stores the latest version of an on-chain DID along with the blocks in which it was created and last updated.
+        pub DidVersions get(fn did_version): map hasher(blake2_128_concat) Did => Option<DidVersion<T>>;
+        /// Stores history of on-chain DID Documents as (DID, version id) -> DidDocumentVersion. The history is kept
+        /// after the DID is removed while the replaced versions can be pruned once `DidVersionRetentionPeriod` passes.
+        pub DidDocumentHistory get(fn did_document_version): double_map hasher(blake2_128_concat) Did, hasher(identity) u32 => Option<DidDocumentVersion<T>>;
+        /// Block number from which the history of on-chain DIDs is recorded, set by the runtime upgrade which introduced the history.
+        /// DIDs registered before it get their first version recorded on their first update.
+        pub DidHistoryRecordedSince get(fn did_history_recorded_since): Option<T::BlockNumber>;
+        /// Stores replay ids used by DIDs in actions which haven't expired yet as (DID, replay id) -> expiry block.
+        pub UsedReplayIds get(fn replay_id_expiry): double_map hasher(blake2_128_concat) Did, hasher(blake2_128_concat) ReplayId => Option<T::BlockNumber>;
+        /// Stores used replay ids by the expiry block of their actions as (expiry block, (DID, replay id)) -> zero-sized record.
+        /// Used to remove replay ids once their actions expire.
+        pub ReplayIdsByExpiry: double_map hasher(twox_64_concat) T::BlockNumber, hasher(blake2_128_concat) (Did, ReplayId) => Option<()>;
+        /// Expiry block from which the removal of expired replay ids continues in the next block. Set only
+        /// while there are expired replay ids left from the previous blocks.
+        pub ReplayIdsCleanupCursor get(fn replay_ids_cleanup_cursor): Option<T::BlockNumber>;
+        /// Stores deposits reserved for the stored items as DepositItem -> Deposit. The deposit is refunded to its
+        /// depositor once the item is removed.
+        pub Deposits get(fn deposit): map hasher(blake2_128_concat) DepositItem => Option<Deposit<T>>;
+        /// Stores recovery guardians of a DID as DID -> RecoveryConfig.
+        pub DidRecoveryConfigs get(fn did_recovery_config): map hasher(blake2_128_concat) Did => Option<RecoveryConfig<T>>;
+        /// Stores pending recoveries as DID -> RecoveryRequest.
+        pub DidRecoveryRequests get(fn did_recovery_request): map hasher(blake2_128_concat) Did => Option<RecoveryRequest<T>>;
 
         pub Version get(fn storage_version): StorageVersion;
     }
@@ -163,8 +378,9 @@ decl_storage! {
                 );
 
                 <Module<T>>::insert_did_details(*did, did_details);
-                DidKeys::insert(did, key_id, key);
+                <Module<T>>::insert_key(*did, key_id, key.clone());
                 DidControllers::insert(did, Controller(*did), ());
+                <Module<T>>::record_did_version(*did);
             }
         })
     }
@@ -183,6 +399,25 @@ decl_module! {
         const MaxServiceEndpointOrigins: u16 = T::MaxServiceEndpointOrigins::get();
         const MaxServiceEndpointOriginSize: u16 = T::MaxServiceEndpointOriginSize::get();
         const ServiceEndpointOriginPerByteWeight: Weight = T::ServiceEndpointOriginPerByteWeight::get();
+        const MaxServiceEndpointTypes: u16 = T::MaxServiceEndpointTypes::get();
+        const MaxServiceEndpointTypeSize: u16 = T::MaxServiceEndpointTypeSize::get();
+        const ServiceEndpointTypePerByteWeight: Weight = T::ServiceEndpointTypePerByteWeight::get();
+        const MaxServiceEndpointProperties: u16 = T::MaxServiceEndpointProperties::get();
+        const MaxServiceEndpointPropertyValues: u16 = T::MaxServiceEndpointPropertyValues::get();
+        const MaxServiceEndpointPropertySize: u16 = T::MaxServiceEndpointPropertySize::get();
+        const ServiceEndpointPropertyPerByteWeight: Weight = T::ServiceEndpointPropertyPerByteWeight::get();
+        const MaxRecoveryGuardians: u16 = T::MaxRecoveryGuardians::get();
+        const MinRecoveryDelay: T::BlockNumber = T::MinRecoveryDelay::get();
+        const MaxActionLifetime: T::BlockNumber = T::MaxActionLifetime::get();
+        const MaxReplayIdRemovalsPerBlock: u32 = T::MaxReplayIdRemovalsPerBlock::get();
+        const StorageItemDeposit: BalanceOf<T> = T::StorageItemDeposit::get();
+        const StorageByteDeposit: BalanceOf<T> = T::StorageByteDeposit::get();
+        const MaxControllerDepth: u16 = T::MaxControllerDepth::get();
+        const PreventControllerCycles: bool = T::PreventControllerCycles::get();
+        const MaxControllerGraphReads: u32 = T::MaxControllerGraphReads::get();
+        const MaxDidDocumentItems: u32 = T::MaxDidDocumentItems::get();
+        const DidVersionRetentionPeriod: T::BlockNumber = T::DidVersionRetentionPeriod::get();
+        const WebAuthnRpIdHash: [u8; 32] = T::WebAuthnRpIdHash::get();
 
         #[weight = SubstrateWeight::<T>::new_offchain(did_doc_ref.len() as u32)]
         pub fn new_offchain(origin, did: dock::did::Did, did_doc_ref: OffChainDidDocRef) -> DispatchResult {
@@ -209,27 +444,42 @@ decl_module! {
             Ok(())
         }
 
-        /// Create new DID.
+        /// Create new DID. The deposits for the DID, its keys and the first version of its DID Document are reserved from the caller.
         /// If no `keys` are provided, then its a keyless DID and at least 1 `controllers` must be provided.
         /// If any `keys` are provided, but they have an empty `ver_rel`, then its set to a vector with variants
         /// `AUTHENTICATION`, `ASSERTION` and `CAPABILITY_INVOCATION`. This is because keys without any verification
         /// relation won't be usable and these 3 keep the logic most similar to before. Avoiding more
         /// explicit argument to keep the caller's experience simple.
-        #[weight = SubstrateWeight::<T>::new_onchain(keys.len() as u32, controllers.len() as u32)]
+        #[weight = SubstrateWeight::<T>::new_onchain(keys.len() as u32, controllers.len() as u32) + SubstrateWeight::<T>::record_did_version((keys.len() + controllers.len() + 1) as u32) + SubstrateWeight::<T>::check_controller_cycles(controllers.len() as u32)]
         pub fn new_onchain(origin, did: dock::did::Did, keys: Vec<DidKey>, controllers: BTreeSet<Controller>) -> DispatchResult {
-            ensure_signed(origin)?;
+            let caller = ensure_signed(origin)?;
+
+            Self::new_onchain_(caller, did, keys, controllers)?;
+            Ok(())
+        }
 
-            Self::new_onchain_(did, keys, controllers)?;
+        /// Convert the off-chain DID owned by the caller to the on-chain DID keeping the same identifier.
+        /// Accepts the same `keys` and `controllers` as `new_onchain` does and initializes the nonce the same way.
+        #[weight = SubstrateWeight::<T>::new_onchain(keys.len() as u32, controllers.len() as u32) + SubstrateWeight::<T>::record_did_version((keys.len() + controllers.len() + 1) as u32) + SubstrateWeight::<T>::check_controller_cycles(controllers.len() as u32) + T::DbWeight::get().reads(1)]
+        pub fn convert_offchain_to_onchain(origin, did: dock::did::Did, keys: Vec<DidKey>, controllers: BTreeSet<Controller>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            Self::convert_offchain_to_onchain_(caller, did, keys, controllers)?;
             Ok(())
         }
 
         /// Add more keys from DID doc. Does not check if the key is already added or it has duplicate
         /// verification relationships
-        #[weight = SubstrateWeight::<T>::add_keys(&keys, &sig)]
-        pub fn add_keys(origin, keys: AddKeys<T>, sig: DidSignature<Controller>) -> DispatchResult {
-            ensure_signed(origin)?;
+        #[weight = SubstrateWeight::<T>::add_keys(&keys, &sigs)]
+        pub fn add_keys(origin, keys: AddKeys<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
 
-            Self::try_exec_signed_action_from_controller(Self::add_keys_, keys, sig)
+            Self::try_exec_signed_doc_update_from_controllers(
+                &caller,
+                |keys, details| Self::add_keys_(&caller, keys, details),
+                keys,
+                sigs,
+            )
                 .map_err(|err| err.empty_payload_to(Error::<T>::NoKeyProvided))?;
             Ok(())
         }
@@ -237,129 +487,432 @@ decl_module! {
         /// Remove keys from DID doc. This is an atomic operation meaning that it will either remove all keys or do nothing.
         /// # **Note that removing all might make DID unusable**.
         // TODO: Weights are not accurate as each DidKey can have different cost depending on type and no of relationships
-        #[weight = SubstrateWeight::<T>::remove_keys(&keys, &sig)]
-        pub fn remove_keys(origin, keys: RemoveKeys<T>, sig: DidSignature<Controller>) -> DispatchResult {
-            ensure_signed(origin)?;
+        #[weight = SubstrateWeight::<T>::remove_keys(&keys, &sigs)]
+        pub fn remove_keys(origin, keys: RemoveKeys<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
 
-            Self::try_exec_signed_action_from_controller(Self::remove_keys_, keys, sig)
+            Self::try_exec_signed_doc_update_from_controllers(&caller, Self::remove_keys_, keys, sigs)
+                .map_err(|err| err.empty_payload_to(Error::<T>::NoKeyProvided))?;
+            Ok(())
+        }
+
+        /// Atomically remove keys with the given ids and add new keys to the DID doc. Used to rotate keys without
+        /// leaving the DID with both old and new keys or without any of them in between.
+        /// # **Note that removing all controller keys without adding new ones might make DID unusable**.
+        #[weight = SubstrateWeight::<T>::rotate_keys(&keys, &sigs)]
+        pub fn rotate_keys(origin, keys: RotateKeys<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            Self::try_exec_signed_doc_update_from_controllers(
+                &caller,
+                |keys, details| Self::rotate_keys_(&caller, keys, details),
+                keys,
+                sigs,
+            )
                 .map_err(|err| err.empty_payload_to(Error::<T>::NoKeyProvided))?;
             Ok(())
         }
 
         /// Add new controllers. Does not check if the controller being added has any key or is even
         /// a DID that exists on or off chain. Does not check if the controller is already added.
-        #[weight = SubstrateWeight::<T>::add_controllers(&controllers, &sig)]
-        pub fn add_controllers(origin, controllers: AddControllers<T>, sig: DidSignature<Controller>) -> DispatchResult {
-            ensure_signed(origin)?;
+        #[weight = SubstrateWeight::<T>::add_controllers(&controllers, &sigs)]
+        pub fn add_controllers(origin, controllers: AddControllers<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
 
-            Self::try_exec_signed_action_from_controller(Self::add_controllers_, controllers, sig)
+            Self::try_exec_signed_doc_update_from_controllers(&caller, Self::add_controllers_, controllers, sigs)
                 .map_err(|err| err.empty_payload_to(Error::<T>::NoControllerProvided))?;
             Ok(())
         }
 
         /// Remove controllers. This is an atomic operation meaning that it will either remove all keys or do nothing.
         /// # **Note that removing all might make DID unusable**.
-        #[weight = SubstrateWeight::<T>::remove_controllers(&controllers, &sig)]
-        pub fn remove_controllers(origin, controllers: RemoveControllers<T>, sig: DidSignature<Controller>) -> DispatchResult {
-            ensure_signed(origin)?;
+        #[weight = SubstrateWeight::<T>::remove_controllers(&controllers, &sigs)]
+        pub fn remove_controllers(origin, controllers: RemoveControllers<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
 
-            Self::try_exec_signed_action_from_controller(Self::remove_controllers_, controllers, sig)
+            Self::try_exec_signed_doc_update_from_controllers(&caller, Self::remove_controllers_, controllers, sigs)
                 .map_err(|err| err.empty_payload_to(Error::<T>::NoControllerProvided))?;
             Ok(())
         }
 
-        /// Add a single service endpoint.
-        #[weight = SubstrateWeight::<T>::add_service_endpoint(&service_endpoint, &sig)]
-        pub fn add_service_endpoint(origin, service_endpoint: AddServiceEndpoint<T>, sig: DidSignature<Controller>) -> DispatchResult {
+        /// Set the amount of distinct controllers required to sign an update of the DID.
+        /// Passing `None` removes the threshold, so a signature from any single controller is enough again.
+        ///
+        /// The threshold changes which nonce the updates of the DID must carry. An update signed by a single
+        /// controller uses the next nonce of the signing DID, while an update signed by several controllers,
+        /// which is required once the threshold is above 1, uses the next nonce of the updated DID itself and
+        /// each controller signs it wrapped in `MultiControllerAction`.
+        #[weight = SubstrateWeight::<T>::set_controller_threshold(&threshold, &sigs)]
+        pub fn set_controller_threshold(origin, threshold: SetControllerThreshold<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
             ensure_signed(origin)?;
 
-            Self::try_exec_signed_action_from_controller(Self::add_service_endpoint_, service_endpoint, sig)?;
+            Self::try_exec_signed_action_from_controllers(Self::set_controller_threshold_, threshold, sigs)?;
+            Ok(())
+        }
+
+        /// Add a single service endpoint.
+        #[weight = SubstrateWeight::<T>::add_service_endpoint(&service_endpoint, &sigs)]
+        pub fn add_service_endpoint(origin, service_endpoint: AddServiceEndpoint<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            Self::try_exec_signed_doc_update_from_controllers(
+                &caller,
+                |service_endpoint, details| Self::add_service_endpoint_(&caller, service_endpoint, details),
+                service_endpoint,
+                sigs,
+            )?;
+            Ok(())
+        }
+
+        /// Replace an existing service endpoint keeping its id.
+        #[weight = SubstrateWeight::<T>::update_service_endpoint(&service_endpoint, &sigs)]
+        pub fn update_service_endpoint(origin, service_endpoint: UpdateServiceEndpoint<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            Self::try_exec_signed_doc_update_from_controllers(
+                &caller,
+                |service_endpoint, details| Self::update_service_endpoint_(&caller, service_endpoint, details),
+                service_endpoint,
+                sigs,
+            )?;
             Ok(())
         }
 
         /// Remove a single service endpoint.
-        #[weight = SubstrateWeight::<T>::remove_service_endpoint(&service_endpoint, &sig)]
-        pub fn remove_service_endpoint(origin, service_endpoint: RemoveServiceEndpoint<T>, sig: DidSignature<Controller>) -> DispatchResult {
+        #[weight = SubstrateWeight::<T>::remove_service_endpoint(&service_endpoint, &sigs)]
+        pub fn remove_service_endpoint(origin, service_endpoint: RemoveServiceEndpoint<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            Self::try_exec_signed_doc_update_from_controllers(&caller, Self::remove_service_endpoint_, service_endpoint, sigs)?;
+            Ok(())
+        }
+
+        /// Apply an ordered list of updates to the DID doc signed at once. This is an atomic operation meaning
+        /// that it will either apply all updates or do nothing. Each update emits the same event as the corresponding
+        /// standalone extrinsic does.
+        #[weight = SubstrateWeight::<T>::batch_update(&batch, &sigs)]
+        pub fn batch_update(origin, batch: DidBatchUpdate<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            Self::try_exec_signed_doc_update_from_controllers(
+                &caller,
+                |batch, details| Self::batch_update_(&caller, batch, details),
+                batch,
+                sigs,
+            )
+                .map_err(|err| err.empty_payload_to(Error::<T>::NoOperationProvided))?;
+            Ok(())
+        }
+
+        /// Set guardians who can recover the DID once all of its control keys are lost. Passing `None` disables the recovery.
+        /// Fails if the DID has a pending recovery.
+        #[weight = SubstrateWeight::<T>::set_recovery_config(&config, &sigs)]
+        pub fn set_recovery_config(origin, config: SetRecoveryConfig<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_signed_action_from_controllers(Self::set_recovery_config_, config, sigs)?;
+            Ok(())
+        }
+
+        /// Initiate the recovery of the DID. Must be signed by the threshold amount of the DID's guardians using their
+        /// control keys. The new key can be added to the DID by calling `execute_recovery` once the recovery delay passes.
+        #[weight = SubstrateWeight::<T>::initiate_recovery(&recovery, &sigs)]
+        pub fn initiate_recovery(origin, recovery: InitiateRecovery<T>, sigs: Vec<DidSignature<Did>>) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_signed_action_from_guardians(Self::initiate_recovery_, recovery, sigs)?;
+            Ok(())
+        }
+
+        /// Cancel the pending recovery of the DID. A signature from any single controller is enough regardless of the
+        /// DID's controller threshold.
+        #[weight = SubstrateWeight::<T>::cancel_recovery(&cancellation, &sig)]
+        pub fn cancel_recovery(origin, cancellation: CancelRecovery<T>, sig: DidSignature<Controller>) -> DispatchResult {
             ensure_signed(origin)?;
 
-            Self::try_exec_signed_action_from_controller(Self::remove_service_endpoint_, service_endpoint, sig)?;
+            Self::try_exec_signed_action_from_any_controller(Self::cancel_recovery_, cancellation, sig)?;
+            Ok(())
+        }
+
+        /// Add the key from the pending recovery request to the DID once the recovery delay has passed.
+        /// Can be called by any account, which pays the deposit for the new key.
+        #[weight = SubstrateWeight::<T>::execute_recovery()]
+        pub fn execute_recovery(origin, did: Did) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+
+            Self::execute_recovery_(caller, did)?;
+            Ok(())
+        }
+
+        /// Link the account to the on-chain DID. The DID's controllers sign the link while the account
+        /// co-signs it by submitting the transaction.
+        #[weight = SubstrateWeight::<T>::link_account(&link, &sigs)]
+        pub fn link_account(origin, link: LinkAccount<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            let caller = ensure_signed(origin)?;
+            ensure!(caller == link.account, Error::<T>::NotSignedByAccount);
+
+            Self::try_exec_signed_action_from_controllers(Self::link_account_, link, sigs)?;
+            Ok(())
+        }
+
+        /// Unlink the account from the on-chain DID.
+        #[weight = SubstrateWeight::<T>::unlink_account(&unlink, &sigs)]
+        pub fn unlink_account(origin, unlink: UnlinkAccount<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_signed_action_from_controllers(Self::unlink_account_, unlink, sigs)?;
             Ok(())
         }
 
         /// Remove the on-chain DID. This will remove this DID's keys, controllers and service endpoints. But it won't remove storage
         /// entries for DIDs that it controls. However, the authorization logic ensures that once a DID is removed, it
         /// loses its ability to control any DID.
-        #[weight = SubstrateWeight::<T>::remove_onchain_did(&removal, &sig)]
-        pub fn remove_onchain_did(origin, removal: dock::did::DidRemoval<T>, sig: DidSignature<Controller>) -> DispatchResult {
+        #[weight = SubstrateWeight::<T>::remove_onchain_did(&removal, &sigs)]
+        pub fn remove_onchain_did(origin, removal: dock::did::DidRemoval<T>, sigs: Vec<DidSignature<Controller>>) -> DispatchResult {
             ensure_signed(origin)?;
 
-            Self::try_exec_signed_removable_action_from_controller(Self::remove_onchain_did_, removal, sig)?;
+            Self::try_exec_signed_removable_action_from_controllers(Self::remove_onchain_did_, removal, sigs)?;
             Ok(())
         }
 
+        /// Prune up to `versions` oldest versions of the DID Document which were replaced at least `DidVersionRetentionPeriod`
+        /// blocks ago, refunding their deposits to the depositors. Can be called by any account. The latest version is never pruned.
+        #[weight = SubstrateWeight::<T>::prune_did_history(*versions)]
+        pub fn prune_did_history(origin, did: Did, versions: u32) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::prune_did_history_(did, versions)?;
+            Ok(())
+        }
+
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            Self::remove_expired_replay_ids(n)
+        }
+
         fn on_runtime_upgrade() -> Weight {
-            if Version::get() == StorageVersion::SingleKey {
-                let weight = crate::migrations::did::single_key::migrate_to_multi_key::<T>();
-                Version::put(StorageVersion::MultiKey);
+            use crate::migrations::did::*;
 
-                T::DbWeight::get().writes(1) + weight
-            } else {
-                0
-            }
+            let weight = match Version::get() {
+                // Keys and details are created in the latest format so only the keys have to be indexed and the history started afterwards
+                StorageVersion::SingleKey => single_key::migrate_to_multi_key::<T>()
+                    .saturating_add(public_key_index::index_public_keys::<T>())
+                    .saturating_add(did_history::start_recording_history::<T>()),
+                StorageVersion::MultiKey => key_validity::migrate_to_keys_with_validity::<T>()
+                    .saturating_add(controller_threshold::migrate_to_controller_threshold::<T>())
+                    .saturating_add(service_endpoint_types::migrate_to_typed_service_endpoints::<T>())
+                    .saturating_add(account_index::index_offchain_did_owners::<T>())
+                    .saturating_add(public_key_index::index_public_keys::<T>())
+                    .saturating_add(did_history::start_recording_history::<T>()),
+                StorageVersion::MultiKeyWithValidity => controller_threshold::migrate_to_controller_threshold::<T>()
+                    .saturating_add(service_endpoint_types::migrate_to_typed_service_endpoints::<T>())
+                    .saturating_add(account_index::index_offchain_did_owners::<T>())
+                    .saturating_add(public_key_index::index_public_keys::<T>())
+                    .saturating_add(did_history::start_recording_history::<T>()),
+                StorageVersion::MultiKeyWithControllerThreshold => service_endpoint_types::migrate_to_typed_service_endpoints::<T>()
+                    .saturating_add(account_index::index_offchain_did_owners::<T>())
+                    .saturating_add(public_key_index::index_public_keys::<T>())
+                    .saturating_add(did_history::start_recording_history::<T>()),
+                StorageVersion::MultiKeyWithTypedServiceEndpoints => account_index::index_offchain_did_owners::<T>()
+                    .saturating_add(public_key_index::index_public_keys::<T>())
+                    .saturating_add(did_history::start_recording_history::<T>()),
+                StorageVersion::MultiKeyWithAccountIndex => public_key_index::index_public_keys::<T>()
+                    .saturating_add(did_history::start_recording_history::<T>()),
+                StorageVersion::MultiKeyWithPublicKeyIndex => did_history::start_recording_history::<T>(),
+                StorageVersion::MultiKeyWithDidHistory => return T::DbWeight::get().reads(1),
+            };
+            Version::put(StorageVersion::MultiKeyWithDidHistory);
+
+            T::DbWeight::get().reads_writes(1, 1).saturating_add(weight)
         }
     }
 }
 
-impl<T: frame_system::Config> SubstrateWeight<T> {
-    fn add_keys(keys: &AddKeys<T>, DidSignature { sig, .. }: &DidSignature<Controller>) -> Weight {
+impl<T: Config + Debug> SubstrateWeight<T> {
+    /// Computes weight of the action signed by the given controllers. The benchmarked weight for the type of the
+    /// first signature is used as a base while each additional signature adds its verification cost and reads of
+    /// the controller record and the key.
+    fn with_controller_sigs<F>(sigs: &[DidSignature<Controller>], weight_for_sig: F) -> Weight
+    where
+        F: FnOnce(&SigValue) -> Weight,
+    {
+        let base = sigs
+            .first()
+            .map_or(0, |DidSignature { sig, .. }| weight_for_sig(sig));
+
+        sigs.iter().skip(1).fold(base, |weight, sig| {
+            weight
+                .saturating_add(sig.weight())
+                .saturating_add(T::DbWeight::get().reads(2))
+        })
+    }
+
+    /// Weight of recording the new version of the DID Document having the given amount of items along with its deposit.
+    /// Reads the version, every key, controller and service endpoint of the DID, the deposit record and the depositor's
+    /// balance, then writes the version, the document, the deposit record and the balance.
+    fn record_did_version(items: u32) -> Weight {
+        T::DbWeight::get().reads_writes(3 + items as Weight, 4)
+    }
+
+    /// Weight of recording the new version of the updated DID Document. At most one item more than `MaxDidDocumentItems`
+    /// is read before the update is rejected, so the worst case is charged regardless of the DID's current document.
+    fn record_did_update() -> Weight {
+        Self::record_did_version(T::MaxDidDocumentItems::get().saturating_add(1))
+            .saturating_add(Self::record_initial_version())
+    }
+
+    /// Weight of recording the first version of the DID registered before the history was recorded. Reads the version,
+    /// the block from which the history is recorded, DID details and at most one item more than `MaxDidDocumentItems`,
+    /// then writes the version and the document.
+    fn record_initial_version() -> Weight {
+        let items = T::MaxDidDocumentItems::get().saturating_add(1) as Weight;
+
+        T::DbWeight::get().reads_writes(items.saturating_add(3), 2)
+    }
+
+    /// Weight of checking that adding the given amount of controllers won't create a cycle in the controller graph.
+    /// Each check makes at most `MaxControllerGraphReads` reads.
+    fn check_controller_cycles(controllers: u32) -> Weight {
+        if T::PreventControllerCycles::get() {
+            T::DbWeight::get().reads(
+                (controllers as Weight).saturating_mul(T::MaxControllerGraphReads::get() as Weight),
+            )
+        } else {
+            0
+        }
+    }
+
+    fn add_keys(keys: &AddKeys<T>, sigs: &[DidSignature<Controller>]) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| Self::add_keys_for_sig(sig, keys.len() as u32))
+            .saturating_add(Self::record_did_update())
+    }
+
+    fn add_keys_for_sig(sig: &SigValue, keys: u32) -> Weight {
         (match sig {
             SigValue::Sr25519(_) => Self::add_keys_sr25519,
             SigValue::Ed25519(_) => Self::add_keys_ed25519,
-            SigValue::Secp256k1(_) => Self::add_keys_secp256k1,
-        })(keys.len() as u32)
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::add_keys_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::add_keys_secp256r1,
+        })(keys)
     }
 
-    fn remove_keys(
-        keys: &RemoveKeys<T>,
-        DidSignature { sig, .. }: &DidSignature<Controller>,
-    ) -> Weight {
+    fn remove_keys(keys: &RemoveKeys<T>, sigs: &[DidSignature<Controller>]) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| {
+            Self::remove_keys_for_sig(sig, keys.len() as u32)
+        })
+        .saturating_add(Self::record_did_update())
+    }
+
+    fn remove_keys_for_sig(sig: &SigValue, keys: u32) -> Weight {
         (match sig {
             SigValue::Sr25519(_) => Self::remove_keys_sr25519,
             SigValue::Ed25519(_) => Self::remove_keys_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_keys_secp256k1,
-        })(keys.len() as u32)
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::remove_keys_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::remove_keys_secp256r1,
+        })(keys)
+    }
+
+    fn rotate_keys(
+        RotateKeys { remove, add, .. }: &RotateKeys<T>,
+        sigs: &[DidSignature<Controller>],
+    ) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| {
+            Self::remove_keys_for_sig(sig, remove.len() as u32)
+                .saturating_add(Self::add_keys_for_sig(sig, add.len() as u32))
+        })
+        .saturating_add(Self::record_did_update())
     }
 
     fn add_controllers(
         controllers: &AddControllers<T>,
-        DidSignature { sig, .. }: &DidSignature<Controller>,
+        sigs: &[DidSignature<Controller>],
     ) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| {
+            Self::add_controllers_for_sig(sig, controllers.len() as u32)
+        })
+        .saturating_add(Self::record_did_update())
+        .saturating_add(Self::check_controller_cycles(controllers.len() as u32))
+    }
+
+    fn add_controllers_for_sig(sig: &SigValue, controllers: u32) -> Weight {
         (match sig {
             SigValue::Sr25519(_) => Self::add_controllers_sr25519,
             SigValue::Ed25519(_) => Self::add_controllers_ed25519,
-            SigValue::Secp256k1(_) => Self::add_controllers_secp256k1,
-        })(controllers.len() as u32)
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                Self::add_controllers_secp256k1
+            }
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::add_controllers_secp256r1,
+        })(controllers)
     }
 
     fn remove_controllers(
         controllers: &RemoveControllers<T>,
-        DidSignature { sig, .. }: &DidSignature<Controller>,
+        sigs: &[DidSignature<Controller>],
     ) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| {
+            Self::remove_controllers_for_sig(sig, controllers.len() as u32)
+        })
+        .saturating_add(Self::record_did_update())
+    }
+
+    fn remove_controllers_for_sig(sig: &SigValue, controllers: u32) -> Weight {
         (match sig {
             SigValue::Sr25519(_) => Self::remove_controllers_sr25519,
             SigValue::Ed25519(_) => Self::remove_controllers_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_controllers_secp256k1,
-        })(controllers.len() as u32)
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                Self::remove_controllers_secp256k1
+            }
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::remove_controllers_secp256r1,
+        })(controllers)
+    }
+
+    fn set_controller_threshold(
+        _: &SetControllerThreshold<T>,
+        sigs: &[DidSignature<Controller>],
+    ) -> Weight {
+        // Performs the same checks and storage accesses as removing zero controllers
+        Self::with_controller_sigs(sigs, |sig| Self::remove_controllers_for_sig(sig, 0))
     }
 
     fn add_service_endpoint(
         AddServiceEndpoint { id, endpoint, .. }: &AddServiceEndpoint<T>,
-        DidSignature { sig, .. }: &DidSignature<Controller>,
+        sigs: &[DidSignature<Controller>],
+    ) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| {
+            Self::add_service_endpoint_for_sig(sig, id, endpoint)
+        })
+        .saturating_add(Self::record_did_update())
+    }
+
+    fn update_service_endpoint(
+        UpdateServiceEndpoint { id, endpoint, .. }: &UpdateServiceEndpoint<T>,
+        sigs: &[DidSignature<Controller>],
+    ) -> Weight {
+        // Performs the same checks and storage accesses as adding the endpoint
+        Self::with_controller_sigs(sigs, |sig| {
+            Self::add_service_endpoint_for_sig(sig, id, endpoint)
+        })
+        .saturating_add(Self::record_did_update())
+    }
+
+    /// Weight of adding the endpoint. Custom types and properties aren't covered by the benchmarks
+    /// so their weight is computed using per byte weights.
+    fn add_service_endpoint_for_sig(
+        sig: &SigValue,
+        id: &WrappedBytes,
+        endpoint: &ServiceEndpoint,
     ) -> Weight {
+        let types_weight = T::ServiceEndpointTypePerByteWeight::get()
+            .saturating_mul(endpoint.custom_types_size() as Weight);
+        let properties_weight = T::ServiceEndpointPropertyPerByteWeight::get()
+            .saturating_mul(endpoint.properties_size() as Weight);
+
         (match sig {
             SigValue::Sr25519(_) => Self::add_service_endpoint_sr25519,
             SigValue::Ed25519(_) => Self::add_service_endpoint_ed25519,
-            SigValue::Secp256k1(_) => Self::add_service_endpoint_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                Self::add_service_endpoint_secp256k1
+            }
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::add_service_endpoint_secp256r1,
         })(
             endpoint.origins.len() as u32,
             endpoint
@@ -371,27 +924,141 @@ impl<T: frame_system::Config> SubstrateWeight<T> {
                 .unwrap_or(0),
             id.len() as u32,
         )
+        .saturating_add(types_weight)
+        .saturating_add(properties_weight)
     }
 
     fn remove_service_endpoint(
         RemoveServiceEndpoint { id, .. }: &RemoveServiceEndpoint<T>,
-        DidSignature { sig, .. }: &DidSignature<Controller>,
+        sigs: &[DidSignature<Controller>],
     ) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| Self::remove_service_endpoint_for_sig(sig, id))
+            .saturating_add(Self::record_did_update())
+    }
+
+    fn remove_service_endpoint_for_sig(sig: &SigValue, id: &WrappedBytes) -> Weight {
         (match sig {
             SigValue::Sr25519(_) => Self::remove_service_endpoint_sr25519,
             SigValue::Ed25519(_) => Self::remove_service_endpoint_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_service_endpoint_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                Self::remove_service_endpoint_secp256k1
+            }
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => {
+                Self::remove_service_endpoint_secp256r1
+            }
         })(id.len() as u32)
     }
 
-    fn remove_onchain_did(
-        _: &DidRemoval<T>,
-        DidSignature { sig, .. }: &DidSignature<Controller>,
+    fn batch_update(
+        DidBatchUpdate { operations, .. }: &DidBatchUpdate<T>,
+        sigs: &[DidSignature<Controller>],
     ) -> Weight {
-        (match sig {
-            SigValue::Sr25519(_) => Self::remove_onchain_did_sr25519,
-            SigValue::Ed25519(_) => Self::remove_onchain_did_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_onchain_did_secp256k1,
-        })()
+        // Each operation is weighted as the corresponding standalone extrinsic signed with the same type of signature
+        Self::with_controller_sigs(sigs, |sig| {
+            operations
+                .iter()
+                .map(|operation| match operation {
+                    DidUpdateOperation::AddKeys(keys) => {
+                        Self::add_keys_for_sig(sig, keys.len() as u32)
+                    }
+                    DidUpdateOperation::RemoveKeys(keys) => {
+                        Self::remove_keys_for_sig(sig, keys.len() as u32)
+                    }
+                    DidUpdateOperation::AddControllers(controllers) => {
+                        Self::add_controllers_for_sig(sig, controllers.len() as u32)
+                    }
+                    DidUpdateOperation::RemoveControllers(controllers) => {
+                        Self::remove_controllers_for_sig(sig, controllers.len() as u32)
+                    }
+                    DidUpdateOperation::AddServiceEndpoint { id, endpoint } => {
+                        Self::add_service_endpoint_for_sig(sig, id, endpoint)
+                    }
+                    DidUpdateOperation::RemoveServiceEndpoint(id) => {
+                        Self::remove_service_endpoint_for_sig(sig, id)
+                    }
+                    DidUpdateOperation::UpdateServiceEndpoint { id, endpoint } => {
+                        Self::add_service_endpoint_for_sig(sig, id, endpoint)
+                    }
+                })
+                .fold(0, Weight::saturating_add)
+        })
+        .saturating_add(Self::record_did_update())
+        .saturating_add(Self::check_controller_cycles(
+            operations
+                .iter()
+                .map(|operation| match operation {
+                    DidUpdateOperation::AddControllers(controllers) => controllers.len() as u32,
+                    _ => 0,
+                })
+                .fold(0, u32::saturating_add),
+        ))
+    }
+
+    fn link_account(_: &LinkAccount<T>, sigs: &[DidSignature<Controller>]) -> Weight {
+        // Performs the same checks as removing zero controllers along with a read and writes of the link
+        Self::with_controller_sigs(sigs, |sig| Self::remove_controllers_for_sig(sig, 0))
+            .saturating_add(T::DbWeight::get().reads_writes(1, 2))
+    }
+
+    fn unlink_account(_: &UnlinkAccount<T>, sigs: &[DidSignature<Controller>]) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| Self::remove_controllers_for_sig(sig, 0))
+            .saturating_add(T::DbWeight::get().reads_writes(1, 2))
+    }
+
+    fn set_recovery_config(_: &SetRecoveryConfig<T>, sigs: &[DidSignature<Controller>]) -> Weight {
+        // Performs the same checks as removing zero controllers along with a read of the pending recovery and a write
+        // of the recovery config
+        Self::with_controller_sigs(sigs, |sig| Self::remove_controllers_for_sig(sig, 0))
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+
+    fn initiate_recovery(_: &InitiateRecovery<T>, sigs: &[DidSignature<Did>]) -> Weight {
+        // Each guardian signature requires reads of the guardian's key and the signature verification. The recovery
+        // config, the pending recovery and DID details are read while the recovery request and DID details are written.
+        sigs.iter()
+            .fold(T::DbWeight::get().reads_writes(3, 2), |weight, sig| {
+                weight
+                    .saturating_add(sig.weight())
+                    .saturating_add(T::DbWeight::get().reads(1))
+            })
+    }
+
+    fn cancel_recovery(_: &CancelRecovery<T>, sig: &DidSignature<Controller>) -> Weight {
+        Self::remove_controllers_for_sig(&sig.sig, 0).saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    fn execute_recovery() -> Weight {
+        // Reads the recovery request, DID details and the self-control record, writes the key, the controller record,
+        // DID details and removes the request.
+        T::DbWeight::get()
+            .reads_writes(3, 4)
+            .saturating_add(Self::record_did_update())
+    }
+
+    fn prune_did_history(versions: u32) -> Weight {
+        // Reads and writes the version. Each pruned version requires reads of the next version, the deposit record and
+        // the depositor's balance, and writes of the removed version, the deposit record and the balance.
+        let per_version = (versions as Weight).saturating_mul(3);
+
+        T::DbWeight::get()
+            .reads_writes(per_version.saturating_add(1), per_version.saturating_add(1))
+    }
+
+    fn remove_onchain_did(_: &DidRemoval<T>, sigs: &[DidSignature<Controller>]) -> Weight {
+        Self::with_controller_sigs(sigs, |sig| {
+            (match sig {
+                SigValue::Sr25519(_) => Self::remove_onchain_did_sr25519,
+                SigValue::Ed25519(_) => Self::remove_onchain_did_ed25519,
+                SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                    Self::remove_onchain_did_secp256k1
+                }
+                SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => {
+                    Self::remove_onchain_did_secp256r1
+                }
+            })()
+        })
+        // Reads and writes the version, writes the empty document
+        .saturating_add(T::DbWeight::get().reads_writes(1, 2))
+        .saturating_add(Self::record_initial_version())
     }
 }
@@ -0,0 +1,157 @@
+use super::*;
+use codec::FullCodec;
+use frame_support::{
+    storage::{generator, unhashed},
+    Blake2_128Concat, ReversibleStorageHasher, StorageMap,
+};
+
+/// Maximum amount of items returned in a single page. Bigger limits are lowered to this value.
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Page of the items stored under the same prefix. Items are returned in the storage order.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Page<C, I> {
+    pub items: Vec<I>,
+    /// Cursor to be passed to get the next page, `None` if this page is the last one.
+    pub next_cursor: Option<C>,
+}
+
+/// Type of the DID used to filter the paginated listing of DIDs.
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DidType {
+    OnChain,
+    OffChain,
+}
+
+impl<T: Config + Debug> Module<T> {
+    /// Returns up to `limit` keys of the DID following the key with id `cursor`.
+    pub fn did_keys_page(
+        did: &Did,
+        cursor: Option<IncId>,
+        limit: u32,
+    ) -> Page<IncId, DidKeyWithId> {
+        Self::double_map_page::<DidKeys, _, _, _, _>(did, cursor, limit, |id, key| {
+            Some(DidKeyWithId { id, key })
+        })
+    }
+
+    /// Returns up to `limit` controllers of the DID following the controller `cursor`.
+    pub fn did_controllers_page(
+        did: &Did,
+        cursor: Option<Controller>,
+        limit: u32,
+    ) -> Page<Controller, Controller> {
+        Self::double_map_page::<DidControllers, _, _, _, _>(did, cursor, limit, |controller, ()| {
+            Some(controller)
+        })
+    }
+
+    /// Returns up to `limit` service endpoints of the DID following the endpoint with id `cursor`.
+    pub fn did_service_endpoints_page(
+        did: &Did,
+        cursor: Option<WrappedBytes>,
+        limit: u32,
+    ) -> Page<WrappedBytes, ServiceEndpointWithId> {
+        Self::double_map_page::<DidServiceEndpoints, _, _, _, _>(
+            did,
+            cursor,
+            limit,
+            |id, endpoint| Some(ServiceEndpointWithId { id, endpoint }),
+        )
+    }
+
+    /// Returns up to `limit` registered DIDs following the DID `cursor`, optionally only of the given type.
+    pub fn dids_page(did_type: Option<DidType>, cursor: Option<Did>, limit: u32) -> Page<Did, Did> {
+        let prefix = <Dids<T> as generator::StorageMap<_, _>>::prefix_hash();
+        let previous_key =
+            cursor.map_or_else(|| prefix.clone(), |did| Dids::<T>::hashed_key_for(did));
+
+        Self::collect_page(
+            prefix,
+            previous_key,
+            limit,
+            |did_key| Did::decode(&mut Blake2_128Concat::reverse(did_key)).ok(),
+            |did, raw_key| {
+                let details: StoredDidDetails<T> = unhashed::get(raw_key)?;
+                let matches = match did_type {
+                    Some(DidType::OnChain) => details.is_onchain(),
+                    Some(DidType::OffChain) => details.is_offchain(),
+                    None => true,
+                };
+
+                Some(*did).filter(|_| matches)
+            },
+        )
+    }
+
+    /// Returns up to `limit` items stored under the DID in the double map `M` following the second key `cursor`.
+    fn double_map_page<M, K, V, I, F>(did: &Did, cursor: Option<K>, limit: u32, f: F) -> Page<K, I>
+    where
+        M: generator::StorageDoubleMap<Did, K, V>,
+        M::Hasher2: ReversibleStorageHasher,
+        K: FullCodec + Clone,
+        V: FullCodec,
+        F: Fn(K, V) -> Option<I>,
+    {
+        let prefix = M::storage_double_map_final_key1(did);
+        let previous_key = cursor.map_or_else(
+            || prefix.clone(),
+            |cursor| M::storage_double_map_final_key(did, cursor),
+        );
+
+        Self::collect_page(
+            prefix,
+            previous_key,
+            limit,
+            |key| K::decode(&mut M::Hasher2::reverse(key)).ok(),
+            |key, raw_key| f(key.clone(), unhashed::get(raw_key)?),
+        )
+    }
+
+    /// Walks over the raw storage keys starting with `prefix` which follow `previous_key` and collects up to `limit`
+    /// items produced by `f`. The hashed key without the prefix is decoded into the cursor by `decode_cursor`.
+    fn collect_page<C, I, D, F>(
+        prefix: Vec<u8>,
+        mut previous_key: Vec<u8>,
+        limit: u32,
+        decode_cursor: D,
+        f: F,
+    ) -> Page<C, I>
+    where
+        D: Fn(&[u8]) -> Option<C>,
+        F: Fn(&C, &[u8]) -> Option<I>,
+    {
+        let limit = limit.max(1).min(MAX_PAGE_SIZE) as usize;
+        let mut items = Vec::new();
+        let mut last_cursor = None;
+
+        while let Some(raw_key) =
+            sp_io::storage::next_key(&previous_key).filter(|key| key.starts_with(&prefix))
+        {
+            if let Some(cursor) = decode_cursor(&raw_key[prefix.len()..]) {
+                if let Some(item) = f(&cursor, &raw_key) {
+                    if items.len() == limit {
+                        // There is at least one more item, so the client should request the next page
+                        return Page {
+                            items,
+                            next_cursor: last_cursor,
+                        };
+                    }
+
+                    items.push(item);
+                    last_cursor = Some(cursor);
+                }
+            }
+
+            previous_key = raw_key;
+        }
+
+        Page {
+            items,
+            next_cursor: None,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use super::*;
+use crate::ToStateChange;
+use sp_runtime::traits::Saturating;
+
+/// Guardians allowed to recover the DID after all of its control keys are lost.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct RecoveryConfig<T: frame_system::Config> {
+    /// DIDs which can initiate the recovery.
+    pub guardians: BTreeSet<Did>,
+    /// Amount of distinct guardians required to sign the recovery request.
+    pub threshold: u32,
+    /// Amount of blocks which must pass after the recovery was initiated before it can be executed.
+    pub delay: T::BlockNumber,
+}
+
+/// Recovery initiated by the guardians which can be executed once `executable_at` block is reached.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct RecoveryRequest<T: frame_system::Config> {
+    /// Controller key which will be added to the DID.
+    pub new_key: DidKey,
+    /// Guardians who signed the request.
+    pub initiated_by: BTreeSet<Did>,
+    /// Block number starting from which the recovery can be executed.
+    pub executable_at: T::BlockNumber,
+}
+
+/// Recovery config of the DID along with the pending recovery request if any.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Sized", deserialize = "T: Sized"))
+)]
+pub struct DidRecoveryStatus<T: Config> {
+    pub config: RecoveryConfig<T>,
+    pub request: Option<RecoveryRequest<T>>,
+}
+
+impl<T: Config> RecoveryConfig<T> {
+    /// Checks that the config has at least one guardian, doesn't list the DID itself as a guardian and has
+    /// a threshold which can be met by the guardians.
+    pub fn is_valid(&self, did: &Did) -> bool {
+        !self.guardians.is_empty()
+            && self.guardians.len() <= T::MaxRecoveryGuardians::get() as usize
+            && !self.guardians.contains(did)
+            && self.threshold > 0
+            && self.threshold as usize <= self.guardians.len()
+            && self.delay >= T::MinRecoveryDelay::get()
+    }
+}
+
+impl<T: Config + Debug> Module<T> {
+    pub(crate) fn set_recovery_config_(
+        SetRecoveryConfig { did, config, .. }: SetRecoveryConfig<T>,
+        _: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        ensure!(
+            config.as_ref().map_or(true, |config| config.is_valid(&did)),
+            Error::<T>::InvalidRecoveryConfig
+        );
+        // Pending recovery must be cancelled before guardians can be changed
+        ensure!(
+            !DidRecoveryRequests::<T>::contains_key(did),
+            Error::<T>::RecoveryInProgress
+        );
+
+        match config {
+            Some(config) => DidRecoveryConfigs::<T>::insert(did, config),
+            None => DidRecoveryConfigs::<T>::remove(did),
+        }
+
+        deposit_indexed_event!(DidRecoveryConfigSet(did));
+        Ok(())
+    }
+
+    pub(crate) fn initiate_recovery_(
+        InitiateRecovery { did, new_key, .. }: InitiateRecovery<T>,
+        initiated_by: BTreeSet<Did>,
+        _: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        let config = Self::did_recovery_config(did).ok_or(Error::<T>::NoRecoveryConfig)?;
+        ensure!(
+            !DidRecoveryRequests::<T>::contains_key(did),
+            Error::<T>::RecoveryInProgress
+        );
+
+        let (mut keys, controller_keys_count) =
+            Self::prepare_keys_to_insert(sp_std::vec![new_key])?;
+        ensure!(controller_keys_count == 1, Error::<T>::InvalidRecoveryKey);
+
+        let executable_at = <system::Module<T>>::block_number().saturating_add(config.delay);
+        DidRecoveryRequests::<T>::insert(
+            did,
+            RecoveryRequest {
+                new_key: keys.pop().unwrap(),
+                initiated_by,
+                executable_at,
+            },
+        );
+
+        deposit_indexed_event!(DidRecoveryInitiated(did));
+        Ok(())
+    }
+
+    /// Cancels the pending recovery. Can be done by any controller of the DID until the recovery is executed.
+    pub(crate) fn cancel_recovery_(
+        CancelRecovery { did, .. }: CancelRecovery<T>,
+        _: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        DidRecoveryRequests::<T>::take(did).ok_or(Error::<T>::NoRecoveryInProgress)?;
+
+        deposit_indexed_event!(DidRecoveryCancelled(did));
+        Ok(())
+    }
+
+    /// Adds the key from the recovery request as a controller key of the DID once the delay has passed.
+    /// The controller threshold is removed, so the new key alone is enough to update the DID.
+    /// Deposits for the new key and the new version of the DID Document are reserved from the account executing
+    /// the recovery.
+    pub(crate) fn execute_recovery_(depositor: T::AccountId, did: Did) -> Result<(), Error<T>> {
+        let RecoveryRequest {
+            new_key,
+            executable_at,
+            ..
+        } = Self::did_recovery_request(did).ok_or(Error::<T>::NoRecoveryInProgress)?;
+        ensure!(
+            <system::Module<T>>::block_number() >= executable_at,
+            Error::<T>::RecoveryDelayNotElapsed
+        );
+        Self::ensure_can_reserve_deposits(&depositor, Some(new_key.encoded_size()))?;
+
+        let key_id = Self::exec_doc_update(did, &depositor, || {
+            Dids::<T>::try_mutate_exists(did, |details_opt| {
+                WithNonce::try_update_opt_without_increasing_nonce_with(details_opt, |data_opt| {
+                    let details: &mut OnChainDidDetails = data_opt.as_mut().unwrap();
+
+                    let key_id = details.last_key_id.inc();
+                    Self::insert_key_with_deposit(&depositor, did, key_id, new_key)?;
+                    details.active_controller_keys += 1;
+                    if !Self::is_self_controlled(&did) {
+                        DidControllers::insert(&did, &Controller(did), ());
+                        details.active_controllers += 1;
+                    }
+                    details.controller_threshold = None;
+
+                    Ok::<_, Error<T>>(key_id)
+                })
+                .ok_or(Error::<T>::DidDoesNotExist)?
+            })
+        })?;
+        DidRecoveryRequests::<T>::remove(did);
+
+        deposit_indexed_event!(DidRecovered(did, key_id) over did);
+        Ok(())
+    }
+
+    /// Verifies that the action is signed by the threshold amount of distinct guardians of the target DID using
+    /// their control keys. Returns the set of guardians who signed the action.
+    pub(crate) fn verify_sigs_from_guardians<A>(
+        action: &A,
+        signatures: &[DidSignature<Did>],
+    ) -> Result<BTreeSet<Did>, Error<T>>
+    where
+        A: Action<T, Target = Did> + ToStateChange<T>,
+    {
+        let RecoveryConfig {
+            guardians,
+            threshold,
+            ..
+        } = Self::did_recovery_config(action.target()).ok_or(Error::<T>::NoRecoveryConfig)?;
+        ensure!(
+            signatures.len() as u32 >= threshold,
+            Error::<T>::NotEnoughGuardianSignatures
+        );
+
+        let encoded_state_change = action.to_state_change().encode();
+        let mut signers = BTreeSet::new();
+        for signature in signatures {
+            ensure!(guardians.contains(&signature.did), Error::<T>::NotAGuardian);
+            ensure!(
+                signers.insert(signature.did),
+                Error::<T>::DuplicateGuardianSignature
+            );
+
+            let signer_pubkey = Self::control_key(&Controller(signature.did), signature.key_id)?;
+            ensure!(
+                signature.verify::<T>(&encoded_state_change, &signer_pubkey)?,
+                Error::<T>::InvalidSignature
+            );
+        }
+
+        Ok(signers)
+    }
+
+    /// Returns recovery config of the DID along with the pending recovery request. Returns `None` if the DID
+    /// has no recovery config.
+    pub fn did_recovery(did: &Did) -> Option<DidRecoveryStatus<T>> {
+        Some(DidRecoveryStatus {
+            config: Self::did_recovery_config(did)?,
+            request: Self::did_recovery_request(did),
+        })
+    }
+
+    /// Removes recovery config and request of the removed DID.
+    pub(crate) fn remove_recovery(did: &Did) {
+        DidRecoveryConfigs::<T>::remove(did);
+        DidRecoveryRequests::<T>::remove(did);
+    }
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+/// Contents of an on-chain DID required to build its W3C DID Document.
+#[derive(Encode, Decode, Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OnChainDidDocument {
+    /// Controllers of the DID sorted in ascending order.
+    pub controllers: Vec<Controller>,
+    /// Keys of the DID sorted by their identifiers.
+    pub keys: Vec<DidKeyWithId>,
+    /// Service endpoints of the DID sorted by their identifiers.
+    pub service_endpoints: Vec<ServiceEndpointWithId>,
+}
+
+impl OnChainDidDocument {
+    /// Returns total amount of keys, controllers and service endpoints in the document.
+    pub fn item_count(&self) -> u32 {
+        (self.controllers.len() + self.keys.len() + self.service_endpoints.len()) as u32
+    }
+}
+
+/// Result of the DID resolution. Contains everything needed to produce a W3C DID Document
+/// along with the resolution metadata.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Sized", deserialize = "T: Sized"))
+)]
+pub enum DidResolution<T: Config> {
+    /// DID is stored on chain along with all its keys, controllers and service endpoints.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    OnChain {
+        document: OnChainDidDocument,
+        nonce: T::BlockNumber,
+    },
+    /// DID Document is stored off-chain and can be found using the supplied reference.
+    OffChain(OffChainDidDetails<T>),
+    /// DID was removed and can't be registered again.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    Deactivated { deactivated_at: T::BlockNumber },
+}
+
+impl<T: Config + Debug> Module<T> {
+    /// Resolves given DID. Returns `None` if the DID was never registered.
+    pub fn resolve_did(did: &Did) -> Option<DidResolution<T>> {
+        let resolution = match Self::did(did) {
+            Some(StoredDidDetails::OnChain(details)) => DidResolution::OnChain {
+                document: Self::onchain_did_document(did),
+                nonce: details.nonce,
+            },
+            Some(StoredDidDetails::OffChain(details)) => DidResolution::OffChain(details),
+            None => DidResolution::Deactivated {
+                deactivated_at: Self::deactivated_did(did)?,
+            },
+        };
+
+        Some(resolution)
+    }
+
+    /// Collects keys, controllers and service endpoints of the given on-chain DID.
+    /// Storage iteration order depends on the hashers so everything is sorted to keep
+    /// the produced document stable.
+    pub(crate) fn onchain_did_document(did: &Did) -> OnChainDidDocument {
+        Self::onchain_did_document_with_max_items(did, u32::MAX)
+    }
+
+    /// Same as `Self::onchain_did_document` except that at most `max_items` keys, controllers and service endpoints
+    /// are read, so the document misses some items if the DID has more than `max_items` of them.
+    pub(crate) fn onchain_did_document_with_max_items(
+        did: &Did,
+        max_items: u32,
+    ) -> OnChainDidDocument {
+        let mut remaining = max_items as usize;
+
+        let mut controllers: Vec<_> = DidControllers::iter_prefix(did)
+            .take(remaining)
+            .map(|(controller, ())| controller)
+            .collect();
+        controllers.sort();
+        remaining -= controllers.len();
+
+        let mut keys: Vec<_> = DidKeys::iter_prefix(did)
+            .take(remaining)
+            .map(|(id, key)| DidKeyWithId { id, key })
+            .collect();
+        keys.sort_by_key(|key| key.id);
+        remaining -= keys.len();
+
+        let mut service_endpoints: Vec<_> = DidServiceEndpoints::iter_prefix(did)
+            .take(remaining)
+            .map(|(id, endpoint)| ServiceEndpointWithId { id, endpoint })
+            .collect();
+        service_endpoints.sort_by(|a, b| a.id.cmp(&b.id));
+
+        OnChainDidDocument {
+            controllers,
+            keys,
+            service_endpoints,
+        }
+    }
+}
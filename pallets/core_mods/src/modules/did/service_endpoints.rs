@@ -7,33 +7,109 @@ use core::fmt::Debug;
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct ServiceEndpoint {
-    pub types: ServiceEndpointType,
+    pub types: BTreeSet<ServiceEndpointType>,
     pub origins: Vec<WrappedBytes>,
+    /// Additional properties of the service such as `routingKeys` or `accept`
+    pub properties: Vec<ServiceEndpointProperty>,
 }
 
-bitflags::bitflags! {
-    /// Different service endpoint types specified in the DID spec here https://www.w3.org/TR/did-core/#services
-    #[derive(Encode, Decode)]
-    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-    #[cfg_attr(feature = "serde", serde(try_from = "u16", into = "u16"))]
-    pub struct ServiceEndpointType: u16 {
-        /// No service endpoint set.
-        const NONE = 0;
-        const LINKED_DOMAINS = 0b0001;
+/// Service endpoint types. Types specified in the DID spec registries https://www.w3.org/TR/did-spec-registries/#service-types
+/// have their own variants while any other type can be set using `Custom`.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ServiceEndpointType {
+    LinkedDomains,
+    DIDCommMessaging,
+    CredentialRegistry,
+    /// Type not listed above
+    Custom(WrappedBytes),
+}
+
+/// Named property of the service endpoint having one or more values, i.e. `routingKeys` of the `DIDCommMessaging` service.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ServiceEndpointProperty {
+    pub name: WrappedBytes,
+    pub values: Vec<WrappedBytes>,
+}
+
+impl ServiceEndpointType {
+    /// Returns name of the type as used in the DID Document.
+    pub fn name(&self) -> &[u8] {
+        match self {
+            Self::LinkedDomains => b"LinkedDomains",
+            Self::DIDCommMessaging => b"DIDCommMessaging",
+            Self::CredentialRegistry => b"CredentialRegistry",
+            Self::Custom(name) => &name[..],
+        }
     }
 }
 
-impl_bits_conversion! { ServiceEndpointType, u16 }
+impl ServiceEndpointProperty {
+    /// Names of the Service members set from the endpoint itself. Properties are flattened into the Service
+    /// of the DID Document, so properties with these names would overwrite them.
+    pub const RESERVED_NAMES: [&'static [u8]; 3] = [b"id", b"type", b"serviceEndpoint"];
+}
 
 impl ServiceEndpoint {
-    pub fn is_valid(&self, max_origins: usize, max_origin_length: usize) -> bool {
+    /// Checks that the endpoint has at least one type and origin, that neither of the types,
+    /// origins and properties exceeds the limits set by the config and that no property has a reserved name.
+    pub fn is_valid<T: Config>(&self) -> bool {
+        let valid_bytes = |bytes: &WrappedBytes, max_size: u16| {
+            !bytes.is_empty() && bytes.len() <= max_size as usize
+        };
+        let mut property_names = BTreeSet::new();
+
         !self.types.is_empty()
+            && self.types.len() <= T::MaxServiceEndpointTypes::get() as usize
+            && self.types.iter().all(|typ| match typ {
+                ServiceEndpointType::Custom(name) => {
+                    valid_bytes(name, T::MaxServiceEndpointTypeSize::get())
+                }
+                _ => true,
+            })
             && !self.origins.is_empty()
-            && self.origins.len() <= max_origins
-            && !self
+            && self.origins.len() <= T::MaxServiceEndpointOrigins::get() as usize
+            && self
                 .origins
                 .iter()
-                .any(|o| o.is_empty() || o.len() > max_origin_length)
+                .all(|origin| valid_bytes(origin, T::MaxServiceEndpointOriginSize::get()))
+            && self.properties.len() <= T::MaxServiceEndpointProperties::get() as usize
+            && self
+                .properties
+                .iter()
+                .all(|ServiceEndpointProperty { name, values }| {
+                    property_names.insert(name)
+                        && valid_bytes(name, T::MaxServiceEndpointPropertySize::get())
+                        && !ServiceEndpointProperty::RESERVED_NAMES.contains(&&name[..])
+                        && !values.is_empty()
+                        && values.len() <= T::MaxServiceEndpointPropertyValues::get() as usize
+                        && values.iter().all(|value| {
+                            valid_bytes(value, T::MaxServiceEndpointPropertySize::get())
+                        })
+                })
+    }
+
+    /// Returns total byte size of the custom types.
+    pub fn custom_types_size(&self) -> u32 {
+        self.types
+            .iter()
+            .map(|typ| match typ {
+                ServiceEndpointType::Custom(name) => name.len() as u32,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Returns total byte size of the properties' names and values.
+    pub fn properties_size(&self) -> u32 {
+        self.properties
+            .iter()
+            .map(|ServiceEndpointProperty { name, values }| {
+                name.len() as u32 + values.iter().map(|value| value.len() as u32).sum::<u32>()
+            })
+            .sum()
     }
 }
 
@@ -42,6 +118,7 @@ where
     T: Debug,
 {
     pub(crate) fn add_service_endpoint_(
+        depositor: &T::AccountId,
         AddServiceEndpoint {
             did, id, endpoint, ..
         }: AddServiceEndpoint<T>,
@@ -52,23 +129,52 @@ where
             T::MaxServiceEndpointIdSize::get() as usize >= id.len(),
             Error::<T>::InvalidServiceEndpoint
         );
-        ensure!(
-            endpoint.is_valid(
-                T::MaxServiceEndpointOrigins::get() as usize,
-                T::MaxServiceEndpointOriginSize::get() as usize
-            ),
-            Error::<T>::InvalidServiceEndpoint
-        );
+        ensure!(endpoint.is_valid::<T>(), Error::<T>::InvalidServiceEndpoint);
 
         if Self::did_service_endpoints(&did, &id).is_some() {
             fail!(Error::<T>::ServiceEndpointAlreadyExists)
         }
+        Self::reserve_deposit(
+            depositor,
+            DepositItem::ServiceEndpoint(did, id.clone()),
+            id.encoded_size() + endpoint.encoded_size(),
+        )?;
         DidServiceEndpoints::insert(did, id, endpoint);
 
         deposit_indexed_event!(DidServiceEndpointAdded(did));
         Ok(())
     }
 
+    /// Replaces the existing service endpoint with the given one keeping its id. The deposit of the endpoint
+    /// is resized and reserved from the depositor's balance.
+    pub(crate) fn update_service_endpoint_(
+        depositor: &T::AccountId,
+        UpdateServiceEndpoint {
+            did, id, endpoint, ..
+        }: UpdateServiceEndpoint<T>,
+        _: &mut OnChainDidDetails,
+    ) -> Result<(), Error<T>> {
+        ensure!(!id.is_empty(), Error::<T>::InvalidServiceEndpoint);
+        ensure!(endpoint.is_valid::<T>(), Error::<T>::InvalidServiceEndpoint);
+
+        DidServiceEndpoints::try_mutate(did, id.clone(), |stored_endpoint| {
+            let stored_endpoint = stored_endpoint
+                .as_mut()
+                .ok_or(Error::<T>::ServiceEndpointDoesNotExist)?;
+            Self::reserve_deposit(
+                depositor,
+                DepositItem::ServiceEndpoint(did, id.clone()),
+                id.encoded_size() + endpoint.encoded_size(),
+            )?;
+            *stored_endpoint = endpoint;
+
+            Ok::<_, Error<T>>(())
+        })?;
+
+        deposit_indexed_event!(DidServiceEndpointUpdated(did));
+        Ok(())
+    }
+
     pub(crate) fn remove_service_endpoint_(
         RemoveServiceEndpoint { did, id, .. }: RemoveServiceEndpoint<T>,
         _: &mut OnChainDidDetails,
@@ -78,9 +184,17 @@ where
         if Self::did_service_endpoints(&did, &id).is_none() {
             fail!(Error::<T>::ServiceEndpointDoesNotExist)
         }
-        DidServiceEndpoints::remove(did, id);
+        DidServiceEndpoints::remove(did, id.clone());
+        Self::refund_deposit(&DepositItem::ServiceEndpoint(did, id));
 
         deposit_indexed_event!(DidServiceEndpointRemoved(did));
         Ok(())
     }
+
+    /// Removes all service endpoints of the removed DID refunding their deposits.
+    pub(crate) fn remove_all_service_endpoints(did: Did) {
+        for (id, _) in DidServiceEndpoints::drain_prefix(did) {
+            Self::refund_deposit(&DepositItem::ServiceEndpoint(did, id));
+        }
+    }
 }
@@ -2,10 +2,11 @@ use super::*;
 use crate::ToStateChange;
 
 use crate::{
-    did::service_endpoints::ServiceEndpointType,
-    keys_and_sigs::{get_secp256k1_keypair, SigValue},
+    keys_and_sigs::{
+        get_secp256k1_keypair, get_secp256r1_keypair, sign_with_webauthn_for_rp, SigValue,
+    },
     test_common::*,
-    util::{Bytes64, Bytes65},
+    util::{Bytes64, Bytes65, WithDeadline},
 };
 use frame_support::{assert_noop, assert_ok};
 use sp_core::{ed25519, sr25519, Pair};
@@ -26,6 +27,19 @@ fn only_key_agreement(key: &DidKey) {
     assert!(key.for_key_agreement());
 }
 
+/// CIDv1 with the `raw` content type and the `sha2-256` multihash made of the given digest byte.
+fn cid_doc_ref(digest_byte: u8) -> OffChainDidDocRef {
+    let mut cid = vec![0x01, 0x55, 0x12, 0x20];
+    cid.extend_from_slice(&[digest_byte; 32]);
+
+    OffChainDidDocRef::CID(cid.into())
+}
+
+/// `https` URL with the given path.
+fn url_doc_ref(path: &str) -> OffChainDidDocRef {
+    OffChainDidDocRef::URL(format!("https://example.com/{}", path).into_bytes().into())
+}
+
 pub fn check_did_detail(
     did: &Did,
     last_key_id: u32,
@@ -93,7 +107,7 @@ fn offchain_did() {
         );
 
         // Try to add the same DID and different uri and fail
-        let doc_ref_1 = OffChainDidDocRef::URL(vec![205; 99].into());
+        let doc_ref_1 = url_doc_ref("did-1.json");
         assert_noop!(
             DIDModule::new_offchain(Origin::signed(alice), did, doc_ref_1),
             Error::<Test>::DidAlreadyExists
@@ -116,7 +130,7 @@ fn offchain_did() {
         assert_eq!(fetched_ref, doc_ref);
 
         let bob = 2u64;
-        let new_ref = OffChainDidDocRef::CID(vec![235; 99].into());
+        let new_ref = cid_doc_ref(235);
         assert_noop!(
             DIDModule::set_offchain_did_doc_ref(Origin::signed(bob), did, new_ref.clone()),
             Error::<Test>::DidNotOwnedByAccount
@@ -150,6 +164,105 @@ fn offchain_did() {
     });
 }
 
+#[test]
+fn offchain_did_doc_ref_validation() {
+    // Off-chain DID Doc references must be well-formed
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did: Did = [6; Did::BYTE_SIZE].into();
+
+        let cid = |bytes: &[u8]| OffChainDidDocRef::CID(bytes.to_vec().into());
+        let url = |url: &str| OffChainDidDocRef::URL(url.as_bytes().to_vec().into());
+        let mut cid_v0 = vec![0x12, 0x20];
+        cid_v0.extend_from_slice(&[1; 32]);
+
+        for (doc_ref, err) in vec![
+            (cid(&[]), Error::<Test>::InvalidCidVersion),
+            (
+                cid(&[0x02, 0x55, 0x12, 0x01, 0x00]),
+                Error::<Test>::InvalidCidVersion,
+            ),
+            (cid(&cid_v0[..33]), Error::<Test>::InvalidCidVersion),
+            (
+                cid(&[0x01, 0x99, 0x12, 0x01, 0x00]),
+                Error::<Test>::UnsupportedCidCodec,
+            ),
+            (cid(&[0x01, 0x55]), Error::<Test>::InvalidCidMultihash),
+            (
+                cid(&[0x01, 0x55, 0x12, 0x20, 0x00]),
+                Error::<Test>::InvalidCidMultihash,
+            ),
+            (
+                cid(&[0x01, 0x55, 0x12, 0x01, 0x00]),
+                Error::<Test>::InvalidCidMultihash,
+            ),
+            (url(""), Error::<Test>::InvalidUrl),
+            (url("example.com"), Error::<Test>::InvalidUrl),
+            (url("https://"), Error::<Test>::InvalidUrl),
+            (url("https:example.com"), Error::<Test>::InvalidUrl),
+            (
+                url("https://example.com/did doc"),
+                Error::<Test>::InvalidUrl,
+            ),
+            (
+                url("ftp://example.com"),
+                Error::<Test>::UnsupportedUrlScheme,
+            ),
+            (
+                url("javascript://example.com"),
+                Error::<Test>::UnsupportedUrlScheme,
+            ),
+        ] {
+            assert_noop!(
+                DIDModule::new_offchain(Origin::signed(alice), did, doc_ref),
+                err
+            );
+        }
+
+        let valid_refs = vec![
+            cid(&cid_v0),
+            cid_doc_ref(1),
+            cid(&[0x01, 0x71, 0x00, 0x03, 1, 2, 3]),
+            url("https://example.com/did.json"),
+            url("HTTP://example.com?did=1"),
+            url("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+            OffChainDidDocRef::Custom(vec![0; 10].into()),
+        ];
+        assert_ok!(DIDModule::new_offchain(
+            Origin::signed(alice),
+            did,
+            valid_refs[0].clone()
+        ));
+        for doc_ref in valid_refs {
+            assert_ok!(DIDModule::set_offchain_did_doc_ref(
+                Origin::signed(alice),
+                did,
+                doc_ref
+            ));
+        }
+        assert_noop!(
+            DIDModule::set_offchain_did_doc_ref(
+                Origin::signed(alice),
+                did,
+                url("ftp://example.com")
+            ),
+            Error::<Test>::UnsupportedUrlScheme
+        );
+
+        // DIDs registered with malformed references before the validation was introduced are kept
+        let old_ref = OffChainDidDocRef::URL(vec![193; 40].into());
+        Dids::<Test>::insert(
+            did,
+            StoredDidDetails::from(OffChainDidDetails::new(alice, old_ref.clone())),
+        );
+        assert_eq!(
+            DIDModule::offchain_did_details(&did).unwrap().doc_ref,
+            old_ref
+        );
+        assert_ok!(DIDModule::remove_offchain_did(Origin::signed(alice), did));
+    });
+}
+
 #[test]
 fn onchain_keyless_did_creation() {
     // Creating an on-chain DID with no keys but only controllers, i.e. DID is controlled by other DIDs
@@ -248,10 +361,7 @@ fn onchain_keyed_did_creation_with_self_control() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_1.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::NONE.into()
-            }],
+            vec![DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::NONE)],
             vec![].into_iter().collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_1));
@@ -266,10 +376,7 @@ fn onchain_keyed_did_creation_with_self_control() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: VerRelType::NONE.into()
-            }],
+            vec![DidKey::new(PublicKey::ed25519(pk_ed), VerRelType::NONE)],
             vec![did_1].into_iter().map(Controller).collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_2));
@@ -284,10 +391,7 @@ fn onchain_keyed_did_creation_with_self_control() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_3.clone(),
-            vec![DidKey {
-                public_key: pk_secp.clone(),
-                ver_rels: VerRelType::NONE.into()
-            }],
+            vec![DidKey::new(pk_secp.clone(), VerRelType::NONE)],
             vec![did_1, did_2].into_iter().map(Controller).collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_3));
@@ -302,10 +406,7 @@ fn onchain_keyed_did_creation_with_self_control() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_4.clone(),
-            vec![DidKey {
-                public_key: PublicKey::x25519(pk_ed),
-                ver_rels: VerRelType::NONE.into()
-            }],
+            vec![DidKey::new(PublicKey::x25519(pk_ed), VerRelType::NONE)],
             vec![Controller(did_3.clone())].into_iter().collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_4));
@@ -324,10 +425,7 @@ fn onchain_keyed_did_creation_with_self_control() {
                 DIDModule::new_onchain(
                     Origin::signed(alice),
                     did_5.clone(),
-                    vec![DidKey {
-                        public_key: PublicKey::x25519(pk_ed),
-                        ver_rels: vr.into()
-                    }],
+                    vec![DidKey::new(PublicKey::x25519(pk_ed), vr)],
                     vec![].into_iter().collect()
                 ),
                 Error::<Test>::IncompatibleVerificationRelation
@@ -343,10 +441,7 @@ fn onchain_keyed_did_creation_with_self_control() {
                 DIDModule::new_onchain(
                     Origin::signed(alice),
                     did_5.clone(),
-                    vec![DidKey {
-                        public_key: pk,
-                        ver_rels: VerRelType::KEY_AGREEMENT.into()
-                    }],
+                    vec![DidKey::new(pk, VerRelType::KEY_AGREEMENT)],
                     vec![].into_iter().collect()
                 ),
                 Error::<Test>::IncompatibleVerificationRelation
@@ -364,10 +459,7 @@ fn onchain_keyed_did_creation_with_self_control() {
             assert_ok!(DIDModule::new_onchain(
                 Origin::signed(alice),
                 did.clone(),
-                vec![DidKey {
-                    public_key: pk,
-                    ver_rels: VerRelType::CAPABILITY_INVOCATION.into()
-                }],
+                vec![DidKey::new(pk, VerRelType::CAPABILITY_INVOCATION)],
                 vec![].into_iter().collect()
             ));
             assert!(DIDModule::is_self_controlled(&did));
@@ -415,10 +507,7 @@ fn onchain_keyed_did_creation_with_self_control() {
             assert_ok!(DIDModule::new_onchain(
                 Origin::signed(alice),
                 did.clone(),
-                vec![DidKey {
-                    public_key: pk,
-                    ver_rels: vr.into()
-                }],
+                vec![DidKey::new(pk, vr)],
                 vec![Controller(did_1.clone())].into_iter().collect()
             ));
             assert!(!DIDModule::is_self_controlled(&did));
@@ -439,10 +528,10 @@ fn onchain_keyed_did_creation_with_self_control() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_8.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: (VerRelType::AUTHENTICATION | VerRelType::ASSERTION).into()
-            }],
+            vec![DidKey::new(
+                PublicKey::ed25519(pk_ed),
+                VerRelType::AUTHENTICATION | VerRelType::ASSERTION
+            )],
             vec![Controller(did_9)].into_iter().collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_8));
@@ -459,18 +548,12 @@ fn onchain_keyed_did_creation_with_self_control() {
             Origin::signed(alice),
             did_9.clone(),
             vec![
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed),
-                    ver_rels: VerRelType::AUTHENTICATION.into()
-                },
-                DidKey {
-                    public_key: PublicKey::sr25519(pk_sr),
-                    ver_rels: VerRelType::ASSERTION.into()
-                },
-                DidKey {
-                    public_key: pk_secp.clone(),
-                    ver_rels: (VerRelType::ASSERTION | VerRelType::AUTHENTICATION).into()
-                },
+                DidKey::new(PublicKey::ed25519(pk_ed), VerRelType::AUTHENTICATION),
+                DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::ASSERTION),
+                DidKey::new(
+                    pk_secp.clone(),
+                    VerRelType::ASSERTION | VerRelType::AUTHENTICATION
+                ),
             ],
             vec![Controller(did_8.clone())].into_iter().collect()
         ));
@@ -496,18 +579,12 @@ fn onchain_keyed_did_creation_with_self_control() {
             Origin::signed(alice),
             did_10.clone(),
             vec![
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed),
-                    ver_rels: (VerRelType::AUTHENTICATION | VerRelType::ASSERTION).into()
-                },
-                DidKey {
-                    public_key: PublicKey::sr25519(pk_sr),
-                    ver_rels: VerRelType::ASSERTION.into()
-                },
-                DidKey {
-                    public_key: pk_secp,
-                    ver_rels: VerRelType::CAPABILITY_INVOCATION.into()
-                },
+                DidKey::new(
+                    PublicKey::ed25519(pk_ed),
+                    VerRelType::AUTHENTICATION | VerRelType::ASSERTION
+                ),
+                DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::ASSERTION),
+                DidKey::new(pk_secp, VerRelType::CAPABILITY_INVOCATION),
             ],
             vec![].into_iter().collect()
         ));
@@ -533,14 +610,11 @@ fn onchain_keyed_did_creation_with_self_control() {
             Origin::signed(alice),
             did_11.clone(),
             vec![
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed),
-                    ver_rels: (VerRelType::AUTHENTICATION | VerRelType::ASSERTION).into()
-                },
-                DidKey {
-                    public_key: PublicKey::sr25519(pk_sr),
-                    ver_rels: VerRelType::CAPABILITY_INVOCATION.into()
-                },
+                DidKey::new(
+                    PublicKey::ed25519(pk_ed),
+                    VerRelType::AUTHENTICATION | VerRelType::ASSERTION
+                ),
+                DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::CAPABILITY_INVOCATION),
             ],
             vec![did_1, did_2].into_iter().map(Controller).collect()
         ));
@@ -586,10 +660,10 @@ fn onchain_keyed_did_creation_with_and_without_self_control() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_1.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::AUTHENTICATION.into()
-            }],
+            vec![DidKey::new(
+                PublicKey::sr25519(pk_sr),
+                VerRelType::AUTHENTICATION
+            )],
             vec![controller_1].into_iter().collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_1));
@@ -602,10 +676,10 @@ fn onchain_keyed_did_creation_with_and_without_self_control() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: VerRelType::ASSERTION.into()
-            }],
+            vec![DidKey::new(
+                PublicKey::ed25519(pk_ed),
+                VerRelType::ASSERTION
+            )],
             vec![controller_2].into_iter().collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
@@ -618,10 +692,10 @@ fn onchain_keyed_did_creation_with_and_without_self_control() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_3.clone(),
-            vec![DidKey {
-                public_key: PublicKey::x25519(pk_ed),
-                ver_rels: VerRelType::KEY_AGREEMENT.into()
-            }],
+            vec![DidKey::new(
+                PublicKey::x25519(pk_ed),
+                VerRelType::KEY_AGREEMENT
+            )],
             vec![controller_3].into_iter().collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_3));
@@ -635,14 +709,8 @@ fn onchain_keyed_did_creation_with_and_without_self_control() {
             Origin::signed(alice),
             did_4.clone(),
             vec![
-                DidKey {
-                    public_key: PublicKey::sr25519(pk_sr),
-                    ver_rels: VerRelType::AUTHENTICATION.into()
-                },
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed),
-                    ver_rels: VerRelType::ASSERTION.into()
-                }
+                DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::AUTHENTICATION),
+                DidKey::new(PublicKey::ed25519(pk_ed), VerRelType::ASSERTION)
             ],
             vec![controller_4].into_iter().collect()
         ));
@@ -657,15 +725,11 @@ fn onchain_keyed_did_creation_with_and_without_self_control() {
             Origin::signed(alice),
             did_5.clone(),
             vec![
-                DidKey {
-                    public_key: pk_secp.clone(),
-                    ver_rels: (VerRelType::AUTHENTICATION | VerRelType::CAPABILITY_INVOCATION)
-                        .into()
-                },
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed),
-                    ver_rels: VerRelType::ASSERTION.into()
-                }
+                DidKey::new(
+                    pk_secp.clone(),
+                    VerRelType::AUTHENTICATION | VerRelType::CAPABILITY_INVOCATION
+                ),
+                DidKey::new(PublicKey::ed25519(pk_ed), VerRelType::ASSERTION)
             ],
             vec![controller_1].into_iter().collect()
         ));
@@ -680,15 +744,14 @@ fn onchain_keyed_did_creation_with_and_without_self_control() {
             Origin::signed(alice),
             did_6.clone(),
             vec![
-                DidKey {
-                    public_key: pk_secp,
-                    ver_rels: (VerRelType::AUTHENTICATION | VerRelType::CAPABILITY_INVOCATION)
-                        .into()
-                },
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed),
-                    ver_rels: (VerRelType::ASSERTION | VerRelType::CAPABILITY_INVOCATION).into()
-                }
+                DidKey::new(
+                    pk_secp,
+                    VerRelType::AUTHENTICATION | VerRelType::CAPABILITY_INVOCATION
+                ),
+                DidKey::new(
+                    PublicKey::ed25519(pk_ed),
+                    VerRelType::ASSERTION | VerRelType::CAPABILITY_INVOCATION
+                )
             ],
             vec![controller_1].into_iter().collect()
         ));
@@ -721,10 +784,7 @@ fn add_keys_to_did() {
         // Add keys to a DID that has not been registered yet should fail
         let add_keys = AddKeys {
             did: did_1.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr_1),
-                ver_rels: VerRelType::NONE.into(),
-            }],
+            keys: vec![DidKey::new(PublicKey::sr25519(pk_sr_1), VerRelType::NONE)],
             nonce: 4,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr_1);
@@ -732,11 +792,11 @@ fn add_keys_to_did() {
             DIDModule::add_keys(
                 Origin::signed(alice),
                 add_keys,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -754,21 +814,18 @@ fn add_keys_to_did() {
             DIDModule::add_keys(
                 Origin::signed(alice),
                 add_keys,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
 
         let add_keys = AddKeys {
             did: did_1.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr_1),
-                ver_rels: VerRelType::NONE.into(),
-            }],
+            keys: vec![DidKey::new(PublicKey::sr25519(pk_sr_1), VerRelType::NONE)],
             nonce: 5,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr_1);
@@ -776,11 +833,11 @@ fn add_keys_to_did() {
             DIDModule::add_keys(
                 Origin::signed(alice),
                 add_keys,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -789,18 +846,9 @@ fn add_keys_to_did() {
             Origin::signed(alice),
             did_1.clone(),
             vec![
-                DidKey {
-                    public_key: PublicKey::sr25519(pk_sr_1),
-                    ver_rels: VerRelType::NONE.into()
-                },
-                DidKey {
-                    public_key: PublicKey::sr25519(pk_sr_2),
-                    ver_rels: VerRelType::NONE.into()
-                },
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed_2),
-                    ver_rels: VerRelType::AUTHENTICATION.into()
-                },
+                DidKey::new(PublicKey::sr25519(pk_sr_1), VerRelType::NONE),
+                DidKey::new(PublicKey::sr25519(pk_sr_2), VerRelType::NONE),
+                DidKey::new(PublicKey::ed25519(pk_ed_2), VerRelType::AUTHENTICATION),
             ],
             vec![].into_iter().collect()
         ));
@@ -813,10 +861,10 @@ fn add_keys_to_did() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed_1),
-                ver_rels: VerRelType::AUTHENTICATION.into()
-            }],
+            vec![DidKey::new(
+                PublicKey::ed25519(pk_ed_1),
+                VerRelType::AUTHENTICATION
+            )],
             vec![did_1].into_iter().map(Controller).collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
@@ -827,10 +875,7 @@ fn add_keys_to_did() {
         // Since did_2 does not control itself, it cannot add keys to itself
         let add_keys = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: pk_secp_1.clone(),
-                ver_rels: VerRelType::NONE.into(),
-            }],
+            keys: vec![DidKey::new(pk_secp_1.clone(), VerRelType::NONE)],
             nonce: 5 + 1,
         };
         let sig = SigValue::ed25519(&add_keys.to_state_change().encode(), &pair_ed_1);
@@ -838,11 +883,11 @@ fn add_keys_to_did() {
             DIDModule::add_keys(
                 Origin::signed(alice),
                 add_keys,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_2.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -851,10 +896,7 @@ fn add_keys_to_did() {
         for nonce in vec![5, 7, 9, 10, 100, 10245] {
             let add_keys = AddKeys {
                 did: did_2.clone(),
-                keys: vec![DidKey {
-                    public_key: pk_secp_1.clone(),
-                    ver_rels: VerRelType::NONE.into(),
-                }],
+                keys: vec![DidKey::new(pk_secp_1.clone(), VerRelType::NONE)],
                 nonce,
             };
             let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr_1);
@@ -862,11 +904,11 @@ fn add_keys_to_did() {
                 DIDModule::add_keys(
                     Origin::signed(alice),
                     add_keys,
-                    DidSignature {
+                    vec![DidSignature {
                         did: Controller(did_1.clone()),
                         key_id: 1u32.into(),
                         sig
-                    }
+                    }]
                 ),
                 Error::<Test>::IncorrectNonce
             );
@@ -875,10 +917,7 @@ fn add_keys_to_did() {
         // Invalid signature should fail
         let add_keys = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: pk_secp_1.clone(),
-                ver_rels: VerRelType::NONE.into(),
-            }],
+            keys: vec![DidKey::new(pk_secp_1.clone(), VerRelType::NONE)],
             nonce: 5 + 1,
         };
         // Using some arbitrary bytes as signature
@@ -887,11 +926,11 @@ fn add_keys_to_did() {
             DIDModule::add_keys(
                 Origin::signed(alice),
                 add_keys,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::InvalidSignature
         );
@@ -899,10 +938,7 @@ fn add_keys_to_did() {
         // Using wrong key_id should fail
         let add_keys = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: pk_secp_1.clone(),
-                ver_rels: VerRelType::NONE.into(),
-            }],
+            keys: vec![DidKey::new(pk_secp_1.clone(), VerRelType::NONE)],
             nonce: 5 + 1,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr_1);
@@ -910,11 +946,11 @@ fn add_keys_to_did() {
             DIDModule::add_keys(
                 Origin::signed(alice),
                 add_keys,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 2u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::InvalidSignature
         );
@@ -922,10 +958,7 @@ fn add_keys_to_did() {
         // Using wrong key type should fail
         let add_keys = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: pk_secp_1.clone(),
-                ver_rels: VerRelType::KEY_AGREEMENT.into(),
-            }],
+            keys: vec![DidKey::new(pk_secp_1.clone(), VerRelType::KEY_AGREEMENT)],
             nonce: 5 + 1,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr_1);
@@ -933,11 +966,11 @@ fn add_keys_to_did() {
             DIDModule::add_keys(
                 Origin::signed(alice),
                 add_keys,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::IncompatibleVerificationRelation
         );
@@ -945,21 +978,21 @@ fn add_keys_to_did() {
         // Add x25519 key
         let add_keys = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::x25519(pk_ed_1),
-                ver_rels: VerRelType::KEY_AGREEMENT.into(),
-            }],
+            keys: vec![DidKey::new(
+                PublicKey::x25519(pk_ed_1),
+                VerRelType::KEY_AGREEMENT,
+            )],
             nonce: 5 + 1,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr_1);
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
         check_did_detail(&did_2, 2, 0, 1, 7);
@@ -971,18 +1004,12 @@ fn add_keys_to_did() {
         let add_keys = AddKeys {
             did: did_2.clone(),
             keys: vec![
-                DidKey {
-                    public_key: PublicKey::x25519(pk_sr_2),
-                    ver_rels: VerRelType::KEY_AGREEMENT.into(),
-                },
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed_1),
-                    ver_rels: VerRelType::ASSERTION.into(),
-                },
-                DidKey {
-                    public_key: pk_secp_2,
-                    ver_rels: (VerRelType::AUTHENTICATION | VerRelType::ASSERTION).into(),
-                },
+                DidKey::new(PublicKey::x25519(pk_sr_2), VerRelType::KEY_AGREEMENT),
+                DidKey::new(PublicKey::ed25519(pk_ed_1), VerRelType::ASSERTION),
+                DidKey::new(
+                    pk_secp_2,
+                    VerRelType::AUTHENTICATION | VerRelType::ASSERTION,
+                ),
             ],
             nonce: 6 + 1,
         };
@@ -993,11 +1020,11 @@ fn add_keys_to_did() {
             DIDModule::add_keys(
                 Origin::signed(alice),
                 add_keys.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 3u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::InsufficientVerificationRelationship
         );
@@ -1007,11 +1034,11 @@ fn add_keys_to_did() {
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 2u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
         check_did_detail(&did_2, 5, 0, 1, 7);
@@ -1022,21 +1049,18 @@ fn add_keys_to_did() {
 
         let add_keys = AddKeys {
             did: did_1.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed_1),
-                ver_rels: VerRelType::NONE.into(),
-            }],
+            keys: vec![DidKey::new(PublicKey::ed25519(pk_ed_1), VerRelType::NONE)],
             nonce: 7 + 1,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr_1);
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
 
         check_did_detail(&did_1, 4, 3, 1, 8);
@@ -1081,10 +1105,10 @@ fn remove_keys_from_did() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed_1),
-                ver_rels: VerRelType::AUTHENTICATION.into()
-            },],
+            vec![DidKey::new(
+                PublicKey::ed25519(pk_ed_1),
+                VerRelType::AUTHENTICATION
+            ),],
             vec![did_1].into_iter().map(Controller).collect()
         ));
         check_did_detail(&did_2, 1, 0, 1, 5);
@@ -1103,11 +1127,11 @@ fn remove_keys_from_did() {
                 DIDModule::remove_keys(
                     Origin::signed(alice),
                     remove_keys,
-                    DidSignature {
+                    vec![DidSignature {
                         did: Controller(did_1.clone()),
                         key_id: 1u32.into(),
                         sig
-                    }
+                    }]
                 ),
                 Error::<Test>::IncorrectNonce
             );
@@ -1124,11 +1148,11 @@ fn remove_keys_from_did() {
             DIDModule::remove_keys(
                 Origin::signed(alice),
                 remove_keys,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_2.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -1142,11 +1166,11 @@ fn remove_keys_from_did() {
         assert_ok!(DIDModule::remove_keys(
             Origin::signed(alice),
             remove_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 2u32.into(),
                 sig
-            }
+            }]
         ));
         check_did_detail(&did_1, 4, 1, 1, 3);
 
@@ -1159,11 +1183,11 @@ fn remove_keys_from_did() {
         assert_ok!(DIDModule::remove_keys(
             Origin::signed(alice),
             remove_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 2u32.into(),
                 sig
-            }
+            }]
         ));
         check_did_detail(&did_1, 4, 1, 1, 4);
 
@@ -1176,11 +1200,11 @@ fn remove_keys_from_did() {
         assert_ok!(DIDModule::remove_keys(
             Origin::signed(alice),
             remove_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 2u32.into(),
                 sig
-            }
+            }]
         ));
         check_did_detail(&did_1, 4, 1, 1, 5);
         check_did_detail(&did_2, 1, 0, 1, 5);
@@ -1210,11 +1234,11 @@ fn remove_keys_from_did() {
         assert_ok!(DIDModule::remove_keys(
             Origin::signed(alice),
             remove_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 2u32.into(),
                 sig
-            }
+            }]
         ));
         check_did_detail(&did_5, 1, 0, 1, 30);
         check_did_detail(&did_1, 4, 1, 1, 6);
@@ -1228,11 +1252,11 @@ fn remove_keys_from_did() {
         assert_ok!(DIDModule::remove_controllers(
             Origin::signed(alice),
             remove_controllers,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 2u32.into(),
                 sig
-            }
+            }]
         ));
         check_did_detail(&did_5, 1, 0, 0, 30);
         check_did_detail(&did_1, 4, 1, 1, 7);
@@ -1278,10 +1302,10 @@ fn remove_controllers_from_did() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed_1),
-                ver_rels: VerRelType::AUTHENTICATION.into()
-            }],
+            vec![DidKey::new(
+                PublicKey::ed25519(pk_ed_1),
+                VerRelType::AUTHENTICATION
+            )],
             vec![did_1].into_iter().map(Controller).collect()
         ));
         check_did_detail(&did_2, 1, 0, 1, 5);
@@ -1309,11 +1333,11 @@ fn remove_controllers_from_did() {
                 DIDModule::remove_controllers(
                     Origin::signed(alice),
                     remove_controllers,
-                    DidSignature {
+                    vec![DidSignature {
                         did: Controller(did_1.clone()),
                         key_id: 1u32.into(),
                         sig
-                    }
+                    }]
                 ),
                 Error::<Test>::IncorrectNonce
             );
@@ -1330,11 +1354,11 @@ fn remove_controllers_from_did() {
             DIDModule::remove_controllers(
                 Origin::signed(alice),
                 remove_controllers,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 2u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::NoControllerForDid
         );
@@ -1350,11 +1374,11 @@ fn remove_controllers_from_did() {
             DIDModule::remove_controllers(
                 Origin::signed(alice),
                 remove_controllers,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_2.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -1368,11 +1392,11 @@ fn remove_controllers_from_did() {
         assert_ok!(DIDModule::remove_controllers(
             Origin::signed(alice),
             remove_controllers,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 2u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(!DIDModule::is_self_controlled(&did_1));
         check_did_detail(&did_1, 4, 2, 0, 3);
@@ -1387,11 +1411,11 @@ fn remove_controllers_from_did() {
         assert_ok!(DIDModule::remove_controllers(
             Origin::signed(alice),
             remove_controllers,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(!DIDModule::is_controller(&did_3, &Controller(did_2)));
         check_did_detail(&did_1, 4, 2, 0, 4);
@@ -1430,11 +1454,11 @@ fn add_controllers_to_did() {
             DIDModule::add_controllers(
                 Origin::signed(alice),
                 add_controllers,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -1449,11 +1473,11 @@ fn add_controllers_to_did() {
             DIDModule::add_controllers(
                 Origin::signed(alice),
                 add_controllers,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -1463,14 +1487,8 @@ fn add_controllers_to_did() {
             Origin::signed(alice),
             did_1.clone(),
             vec![
-                DidKey {
-                    public_key: pk_secp_1.clone(),
-                    ver_rels: VerRelType::NONE.into()
-                },
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed),
-                    ver_rels: VerRelType::AUTHENTICATION.into()
-                },
+                DidKey::new(pk_secp_1.clone(), VerRelType::NONE),
+                DidKey::new(PublicKey::ed25519(pk_ed), VerRelType::AUTHENTICATION),
             ],
             vec![].into_iter().collect()
         ));
@@ -1483,10 +1501,7 @@ fn add_controllers_to_did() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_3.clone(),
-            vec![DidKey {
-                public_key: pk_secp_2.clone(),
-                ver_rels: VerRelType::NONE.into()
-            },],
+            vec![DidKey::new(pk_secp_2.clone(), VerRelType::NONE),],
             vec![did_1].into_iter().map(Controller).collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_1));
@@ -1498,10 +1513,10 @@ fn add_controllers_to_did() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::AUTHENTICATION.into()
-            }],
+            vec![DidKey::new(
+                PublicKey::sr25519(pk_sr),
+                VerRelType::AUTHENTICATION
+            )],
             vec![did_1].into_iter().map(Controller).collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
@@ -1521,11 +1536,11 @@ fn add_controllers_to_did() {
             DIDModule::add_controllers(
                 Origin::signed(alice),
                 add_controllers,
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_2.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -1542,11 +1557,11 @@ fn add_controllers_to_did() {
                 DIDModule::add_controllers(
                     Origin::signed(alice),
                     add_controllers,
-                    DidSignature {
+                    vec![DidSignature {
                         did: Controller(did_1.clone()),
                         key_id: 1u32.into(),
                         sig
-                    }
+                    }]
                 ),
                 Error::<Test>::IncorrectNonce
             );
@@ -1563,11 +1578,11 @@ fn add_controllers_to_did() {
             DIDModule::add_controllers(
                 Origin::signed(alice),
                 add_controllers.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::InvalidSignature
         );
@@ -1577,11 +1592,11 @@ fn add_controllers_to_did() {
         assert_ok!(DIDModule::add_controllers(
             Origin::signed(alice),
             add_controllers,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
         assert!(DIDModule::is_controller(&did_2, &Controller(did_3)));
@@ -1600,11 +1615,11 @@ fn add_controllers_to_did() {
         assert_ok!(DIDModule::add_controllers(
             Origin::signed(alice),
             add_controllers,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_3.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
         assert!(DIDModule::is_controller(&did_2, &Controller(did_4)));
@@ -1622,11 +1637,11 @@ fn add_controllers_to_did() {
         assert_ok!(DIDModule::add_controllers(
             Origin::signed(alice),
             add_controllers,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_3.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(DIDModule::is_controller(&did_3, &Controller(did_4)));
         assert!(DIDModule::is_controller(&did_3, &Controller(did_5)));
@@ -1654,10 +1669,7 @@ fn becoming_controller() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_1.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::NONE.into()
-            },],
+            vec![DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::NONE),],
             vec![].into_iter().collect()
         ));
         check_did_detail(&did_1, 1, 1, 1, 5);
@@ -1667,10 +1679,10 @@ fn becoming_controller() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::x25519(pk_ed),
-                ver_rels: VerRelType::KEY_AGREEMENT.into()
-            },],
+            vec![DidKey::new(
+                PublicKey::x25519(pk_ed),
+                VerRelType::KEY_AGREEMENT
+            ),],
             vec![did_1].into_iter().map(Controller).collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
@@ -1680,21 +1692,21 @@ fn becoming_controller() {
 
         let add_keys = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: VerRelType::ASSERTION.into(),
-            }],
+            keys: vec![DidKey::new(
+                PublicKey::ed25519(pk_ed),
+                VerRelType::ASSERTION,
+            )],
             nonce: 5 + 1,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr);
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
         check_did_detail(&did_2, 2, 0, 1, 10);
@@ -1704,21 +1716,21 @@ fn becoming_controller() {
 
         let add_keys = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: pk_secp.clone(),
-                ver_rels: VerRelType::CAPABILITY_INVOCATION.into(),
-            }],
+            keys: vec![DidKey::new(
+                pk_secp.clone(),
+                VerRelType::CAPABILITY_INVOCATION,
+            )],
             nonce: 6 + 1,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr);
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(DIDModule::is_self_controlled(&did_2));
         check_did_detail(&did_2, 3, 1, 2, 10);
@@ -1747,10 +1759,7 @@ fn any_controller_can_update() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_1.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: VerRelType::NONE.into()
-            },],
+            vec![DidKey::new(PublicKey::ed25519(pk_ed), VerRelType::NONE),],
             vec![].into_iter().collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_1));
@@ -1761,10 +1770,7 @@ fn any_controller_can_update() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::NONE.into()
-            },],
+            vec![DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::NONE),],
             vec![].into_iter().collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_2));
@@ -1773,10 +1779,7 @@ fn any_controller_can_update() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_3.clone(),
-            vec![DidKey {
-                public_key: pk_secp.clone(),
-                ver_rels: VerRelType::NONE.into()
-            },],
+            vec![DidKey::new(pk_secp.clone(), VerRelType::NONE),],
             vec![].into_iter().collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_3));
@@ -1787,10 +1790,7 @@ fn any_controller_can_update() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_4.clone(),
-            vec![DidKey {
-                public_key: pk_secp.clone(),
-                ver_rels: VerRelType::NONE.into()
-            },],
+            vec![DidKey::new(pk_secp.clone(), VerRelType::NONE),],
             vec![did_2].into_iter().map(Controller).collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_4));
@@ -1807,11 +1807,11 @@ fn any_controller_can_update() {
         assert_ok!(DIDModule::add_controllers(
             Origin::signed(alice),
             add_controllers,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_2.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         check_did_detail(&did_4, 1, 1, 3, 7);
         check_did_detail(&did_2, 1, 1, 1, 6);
@@ -1820,21 +1820,18 @@ fn any_controller_can_update() {
 
         let add_keys = AddKeys {
             did: did_4.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::NONE.into(),
-            }],
+            keys: vec![DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::NONE)],
             nonce: 3 + 1,
         };
         let sig = SigValue::ed25519(&add_keys.to_state_change().encode(), &pair_ed);
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         check_did_detail(&did_4, 2, 2, 3, 7);
         check_did_detail(&did_1, 1, 1, 1, 4);
@@ -1864,8 +1861,11 @@ fn service_endpoints() {
             did: did.clone(),
             id: endpoint_1_id.clone(),
             endpoint: ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_1.clone(),
+                properties: vec![],
             },
             nonce: 5 + 1,
         };
@@ -1876,11 +1876,11 @@ fn service_endpoints() {
             DIDModule::add_service_endpoint(
                 Origin::signed(alice),
                 add_service_endpoint.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -1889,14 +1889,11 @@ fn service_endpoints() {
             Origin::signed(alice),
             did.clone(),
             vec![
-                DidKey {
-                    public_key: PublicKey::sr25519(pk_sr),
-                    ver_rels: VerRelType::NONE.into()
-                },
-                DidKey {
-                    public_key: PublicKey::ed25519(pk_ed),
-                    ver_rels: (VerRelType::AUTHENTICATION | VerRelType::ASSERTION).into()
-                },
+                DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::NONE),
+                DidKey::new(
+                    PublicKey::ed25519(pk_ed),
+                    VerRelType::AUTHENTICATION | VerRelType::ASSERTION
+                ),
             ],
             vec![].into_iter().collect()
         ));
@@ -1910,8 +1907,11 @@ fn service_endpoints() {
             did: did.clone(),
             id: endpoint_1_id.clone(),
             endpoint: ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_1.clone(),
+                properties: vec![],
             },
             nonce: 5 + 1,
         };
@@ -1921,11 +1921,11 @@ fn service_endpoints() {
             DIDModule::add_service_endpoint(
                 Origin::signed(alice),
                 add_service_endpoint.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did.clone()),
                     key_id: 2u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::InsufficientVerificationRelationship
         );
@@ -1935,57 +1935,159 @@ fn service_endpoints() {
             (
                 vec![].into(), // Empty id not allowed
                 ServiceEndpoint {
-                    types: ServiceEndpointType::LINKED_DOMAINS,
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
                     origins: origins_1.clone(),
+                    properties: vec![],
                 },
             ),
             (
                 vec![20; 512].into(), // too big id not allowed
                 ServiceEndpoint {
-                    types: ServiceEndpointType::LINKED_DOMAINS,
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
                     origins: origins_1.clone(),
+                    properties: vec![],
                 },
             ),
             (
                 endpoint_1_id.clone(),
                 ServiceEndpoint {
-                    types: ServiceEndpointType::NONE, // Empty type not allowed
+                    types: BTreeSet::new(), // Empty type not allowed
                     origins: origins_1.clone(),
+                    properties: vec![],
                 },
             ),
             (
                 endpoint_1_id.clone(),
                 ServiceEndpoint {
-                    types: ServiceEndpointType::LINKED_DOMAINS,
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
                     origins: vec![], // Empty origin not allowed
+                    properties: vec![],
                 },
             ),
             (
                 endpoint_1_id.clone(),
                 ServiceEndpoint {
-                    types: ServiceEndpointType::LINKED_DOMAINS,
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
                     origins: vec![vec![].into()], // Empty origin not allowed
+                    properties: vec![],
                 },
             ),
             (
                 endpoint_1_id.clone(),
                 ServiceEndpoint {
-                    types: ServiceEndpointType::LINKED_DOMAINS,
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
                     origins: vec![vec![45; 55].into(), vec![].into()], // All provided origins mut be non-empty
+                    properties: vec![],
                 },
             ),
             (
                 endpoint_1_id.clone(),
                 ServiceEndpoint {
-                    types: ServiceEndpointType::LINKED_DOMAINS,
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
                     origins: vec![vec![30; 561].into()], // too big origin not allowed
+                    properties: vec![],
                 },
             ),
             (
                 endpoint_1_id.clone(),
                 ServiceEndpoint {
-                    types: ServiceEndpointType::LINKED_DOMAINS,
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
                     origins: vec![vec![30; 20].into(); 300], // too many origins not allowed
+                    properties: vec![],
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::Custom(vec![].into())] // Empty custom type not allowed
+                        .into_iter()
+                        .collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![],
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::Custom(vec![40; 65].into())] // too big custom type not allowed
+                        .into_iter()
+                        .collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![],
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: (0..5) // too many types not allowed
+                        .map(|i| ServiceEndpointType::Custom(vec![i; 10].into()))
+                        .collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![],
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![ServiceEndpointProperty { name: vec![].into(), values: vec![vec![1; 10].into()] }], // Empty property name not allowed
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![ServiceEndpointProperty { name: b"accept".to_vec().into(), values: vec![] }], // Property without values not allowed
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![ServiceEndpointProperty { name: b"accept".to_vec().into(), values: vec![vec![].into()] }], // Empty property value not allowed
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![ServiceEndpointProperty { name: b"accept".to_vec().into(), values: vec![vec![1; 129].into()] }], // too big property value not allowed
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![ServiceEndpointProperty { name: b"accept".to_vec().into(), values: vec![vec![1; 10].into(); 5] }], // too many property values not allowed
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![ServiceEndpointProperty { name: b"accept".to_vec().into(), values: vec![vec![1; 10].into()] }; 2], // Duplicate property names not allowed
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
+                    origins: origins_1.clone(),
+                    properties: vec![ServiceEndpointProperty { name: b"serviceEndpoint".to_vec().into(), values: vec![vec![1; 10].into()] }], // Reserved property name not allowed
+                },
+            ),
+            (
+                endpoint_1_id.clone(),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains].into_iter().collect(),
+                    origins: origins_1.clone(),
+                    properties: (0..5).map(|i| ServiceEndpointProperty { name: vec![i; 10].into(), values: vec![vec![1; 10].into()] }).collect(), // too many properties not allowed
                 },
             ),
         ] {
@@ -2001,11 +2103,11 @@ fn service_endpoints() {
                 DIDModule::add_service_endpoint(
                     Origin::signed(alice),
                     add_service_endpoint.clone(),
-                    DidSignature {
+                    vec![DidSignature {
                         did: Controller(did.clone()),
                         key_id: 1u32.into(),
                         sig
-                    }
+                    }]
                 ),
                 Error::<Test>::InvalidServiceEndpoint
             );
@@ -2017,8 +2119,11 @@ fn service_endpoints() {
             did: did.clone(),
             id: endpoint_1_id.clone(),
             endpoint: ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_1.clone(),
+                properties: vec![],
             },
             nonce: 5 + 1,
         };
@@ -2027,18 +2132,21 @@ fn service_endpoints() {
         assert_ok!(DIDModule::add_service_endpoint(
             Origin::signed(alice),
             add_service_endpoint.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
 
         assert_eq!(
             DIDModule::did_service_endpoints(&did, &endpoint_1_id).unwrap(),
             ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_1.clone(),
+                properties: vec![],
             }
         );
         check_did_detail(&did, 2, 1, 1, 6);
@@ -2050,8 +2158,11 @@ fn service_endpoints() {
             did: did.clone(),
             id: endpoint_1_id.clone(),
             endpoint: ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_2.clone(),
+                properties: vec![],
             },
             nonce: 6 + 1,
         };
@@ -2061,11 +2172,11 @@ fn service_endpoints() {
             DIDModule::add_service_endpoint(
                 Origin::signed(alice),
                 add_service_endpoint.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::ServiceEndpointAlreadyExists
         );
@@ -2074,8 +2185,11 @@ fn service_endpoints() {
             did: did.clone(),
             id: endpoint_2_id.clone(),
             endpoint: ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_2.clone(),
+                properties: vec![],
             },
             nonce: 6 + 1,
         };
@@ -2084,18 +2198,21 @@ fn service_endpoints() {
         assert_ok!(DIDModule::add_service_endpoint(
             Origin::signed(alice),
             add_service_endpoint.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
 
         assert_eq!(
             DIDModule::did_service_endpoints(&did, &endpoint_2_id).unwrap(),
             ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_2.clone(),
+                properties: vec![],
             }
         );
         check_did_detail(&did, 2, 1, 1, 7);
@@ -2114,11 +2231,11 @@ fn service_endpoints() {
             DIDModule::remove_service_endpoint(
                 Origin::signed(alice),
                 rem_service_endpoint.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did.clone()),
                     key_id: 2u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::InsufficientVerificationRelationship
         );
@@ -2135,11 +2252,11 @@ fn service_endpoints() {
             DIDModule::remove_service_endpoint(
                 Origin::signed(alice),
                 rem_service_endpoint.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::InvalidServiceEndpoint
         );
@@ -2154,11 +2271,11 @@ fn service_endpoints() {
         assert_ok!(DIDModule::remove_service_endpoint(
             Origin::signed(alice),
             rem_service_endpoint.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(DIDModule::did_service_endpoints(&did, &endpoint_1_id).is_none());
         check_did_detail(&did, 2, 1, 1, 8);
@@ -2174,11 +2291,11 @@ fn service_endpoints() {
             DIDModule::remove_service_endpoint(
                 Origin::signed(alice),
                 rem_service_endpoint.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::ServiceEndpointDoesNotExist
         );
@@ -2193,11 +2310,11 @@ fn service_endpoints() {
         assert_ok!(DIDModule::remove_service_endpoint(
             Origin::signed(alice),
             rem_service_endpoint.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert!(DIDModule::did_service_endpoints(&did, &endpoint_2_id).is_none());
         check_did_detail(&did, 2, 1, 1, 9);
@@ -2212,11 +2329,11 @@ fn service_endpoints() {
             DIDModule::remove_onchain_did(
                 Origin::signed(alice),
                 rem_did.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did.clone()),
                     key_id: 2u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::InsufficientVerificationRelationship
         );
@@ -2232,11 +2349,11 @@ fn service_endpoints() {
         assert_ok!(DIDModule::remove_onchain_did(
             Origin::signed(alice),
             rem_did.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         ensure_onchain_did_gone(&did);
     });
@@ -2263,10 +2380,7 @@ fn did_removal() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_1.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::NONE.into()
-            }],
+            vec![DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::NONE)],
             vec![].into_iter().collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_1));
@@ -2278,10 +2392,10 @@ fn did_removal() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: VerRelType::AUTHENTICATION.into()
-            }],
+            vec![DidKey::new(
+                PublicKey::ed25519(pk_ed),
+                VerRelType::AUTHENTICATION
+            )],
             vec![did_1.clone()].into_iter().map(Controller).collect()
         ));
         assert!(!DIDModule::is_self_controlled(&did_2));
@@ -2293,10 +2407,7 @@ fn did_removal() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_3.clone(),
-            vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: VerRelType::NONE.into()
-            }],
+            vec![DidKey::new(PublicKey::ed25519(pk_ed), VerRelType::NONE)],
             vec![did_1.clone()].into_iter().map(Controller).collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_3));
@@ -2308,10 +2419,7 @@ fn did_removal() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_4.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::NONE.into()
-            }],
+            vec![DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::NONE)],
             vec![did_3.clone()].into_iter().map(Controller).collect()
         ));
         assert!(DIDModule::is_self_controlled(&did_4));
@@ -2327,11 +2435,11 @@ fn did_removal() {
             DIDModule::remove_onchain_did(
                 Origin::signed(alice),
                 rem_did.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_2.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::OnlyControllerCanUpdate
         );
@@ -2346,11 +2454,11 @@ fn did_removal() {
         assert_ok!(DIDModule::remove_onchain_did(
             Origin::signed(alice),
             rem_did.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         ensure_onchain_did_gone(&did_2);
         check_did_detail(&did_1, 1, 1, 1, 6);
@@ -2365,11 +2473,11 @@ fn did_removal() {
             DIDModule::remove_onchain_did(
                 Origin::signed(alice),
                 rem_did.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_1.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::IncorrectNonce
         );
@@ -2385,11 +2493,11 @@ fn did_removal() {
         assert_ok!(DIDModule::remove_onchain_did(
             Origin::signed(alice),
             rem_did.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         ensure_onchain_did_gone(&did_3);
         check_did_detail(&did_1, 1, 1, 1, 7);
@@ -2405,11 +2513,11 @@ fn did_removal() {
             DIDModule::remove_onchain_did(
                 Origin::signed(alice),
                 rem_did.clone(),
-                DidSignature {
+                vec![DidSignature {
                     did: Controller(did_3.clone()),
                     key_id: 1u32.into(),
                     sig
-                }
+                }]
             ),
             Error::<Test>::NoKeyForDid
         );
@@ -2424,11 +2532,11 @@ fn did_removal() {
         assert_ok!(DIDModule::remove_onchain_did(
             Origin::signed(alice),
             rem_did.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_4.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         ensure_onchain_did_gone(&did_4);
 
@@ -2441,11 +2549,11 @@ fn did_removal() {
         assert_ok!(DIDModule::remove_onchain_did(
             Origin::signed(alice),
             rem_did.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         ensure_onchain_did_gone(&did_1);
     });
@@ -2474,10 +2582,7 @@ fn batched_did_changes() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_1.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::NONE.into()
-            }],
+            vec![DidKey::new(PublicKey::sr25519(pk_sr), VerRelType::NONE)],
             vec![].into_iter().collect()
         ));
         check_did_detail(&did_1, 1, 1, 1, 10);
@@ -2487,10 +2592,10 @@ fn batched_did_changes() {
         // did_1 adds a key and service endpoint to itself in the same block. Checks that nonce is correct.
         let add_keys = AddKeys {
             did: did_1.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::x25519(pk_ed),
-                ver_rels: VerRelType::KEY_AGREEMENT.into(),
-            }],
+            keys: vec![DidKey::new(
+                PublicKey::x25519(pk_ed),
+                VerRelType::KEY_AGREEMENT,
+            )],
             nonce: 10 + 1,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr);
@@ -2499,8 +2604,11 @@ fn batched_did_changes() {
             did: did_1.clone(),
             id: endpoint_1_id.clone(),
             endpoint: ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_1.clone(),
+                properties: vec![],
             },
             nonce: 10 + 2,
         };
@@ -2509,28 +2617,31 @@ fn batched_did_changes() {
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert_ok!(DIDModule::add_service_endpoint(
             Origin::signed(alice),
             add_service_endpoint.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig: sig_1
-            }
+            }]
         ));
 
         check_did_detail(&did_1, 2, 1, 1, 12);
         assert_eq!(
             DIDModule::did_service_endpoints(&did_1, &endpoint_1_id).unwrap(),
             ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_1.clone(),
+                properties: vec![],
             }
         );
         only_key_agreement(&DidKeys::get(&did_1, IncId::from(2u32)).unwrap());
@@ -2541,20 +2652,20 @@ fn batched_did_changes() {
         // both DIDs is correct.
         let add_keys = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::x25519(pk_ed),
-                ver_rels: VerRelType::KEY_AGREEMENT.into(),
-            }],
+            keys: vec![DidKey::new(
+                PublicKey::x25519(pk_ed),
+                VerRelType::KEY_AGREEMENT,
+            )],
             nonce: 12 + 1,
         };
         let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr);
 
         let add_keys_2 = AddKeys {
             did: did_2.clone(),
-            keys: vec![DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: VerRelType::ASSERTION.into(),
-            }],
+            keys: vec![DidKey::new(
+                PublicKey::ed25519(pk_ed),
+                VerRelType::ASSERTION,
+            )],
             nonce: 12 + 2,
         };
         let sig_2 = SigValue::sr25519(&add_keys_2.to_state_change().encode(), &pair_sr);
@@ -2563,8 +2674,11 @@ fn batched_did_changes() {
             did: did_2.clone(),
             id: endpoint_2_id.clone(),
             endpoint: ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_2.clone(),
+                properties: vec![],
             },
             nonce: 12 + 3,
         };
@@ -2573,38 +2687,38 @@ fn batched_did_changes() {
         assert_ok!(DIDModule::new_onchain(
             Origin::signed(alice),
             did_2.clone(),
-            vec![DidKey {
-                public_key: PublicKey::sr25519(pk_sr),
-                ver_rels: VerRelType::AUTHENTICATION.into()
-            }],
+            vec![DidKey::new(
+                PublicKey::sr25519(pk_sr),
+                VerRelType::AUTHENTICATION
+            )],
             vec![did_1].into_iter().map(Controller).collect()
         ));
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig
-            }
+            }]
         ));
         assert_ok!(DIDModule::add_keys(
             Origin::signed(alice),
             add_keys_2,
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig: sig_2
-            }
+            }]
         ));
         assert_ok!(DIDModule::add_service_endpoint(
             Origin::signed(alice),
             add_service_endpoint.clone(),
-            DidSignature {
+            vec![DidSignature {
                 did: Controller(did_1.clone()),
                 key_id: 1u32.into(),
                 sig: sig_3
-            }
+            }]
         ));
 
         check_did_detail(&did_2, 3, 0, 1, 13);
@@ -2612,18 +2726,2624 @@ fn batched_did_changes() {
         assert_eq!(
             DIDModule::did_service_endpoints(&did_2, &endpoint_2_id).unwrap(),
             ServiceEndpoint {
-                types: ServiceEndpointType::LINKED_DOMAINS,
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
                 origins: origins_2.clone(),
+                properties: vec![],
             }
         );
         only_key_agreement(&DidKeys::get(&did_2, IncId::from(2u32)).unwrap());
         assert_eq!(
             DidKeys::get(&did_2, IncId::from(3u32)).unwrap(),
-            DidKey {
-                public_key: PublicKey::ed25519(pk_ed),
-                ver_rels: VerRelType::ASSERTION.into()
-            }
+            DidKey::new(PublicKey::ed25519(pk_ed), VerRelType::ASSERTION)
         );
     });
 }
+
+#[test]
+fn rotate_keys() {
+    // Rotating keys removes and adds keys atomically
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_1: Did = [61; Did::BYTE_SIZE].into();
+
+        let (pair_sr, _, _) = sr25519::Pair::generate_with_phrase(None);
+        let pk_sr = pair_sr.public().0;
+        let (pair_ed_1, _, _) = ed25519::Pair::generate_with_phrase(None);
+        let pk_ed_1 = pair_ed_1.public().0;
+        let (pair_ed_2, _, _) = ed25519::Pair::generate_with_phrase(None);
+        let pk_ed_2 = pair_ed_2.public().0;
+
+        run_to_block(3);
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_1.clone(),
+            vec![
+                DidKey::new_with_all_relationships(PublicKey::sr25519(pk_sr)),
+                DidKey::new(PublicKey::ed25519(pk_ed_1), VerRelType::ASSERTION),
+            ],
+            vec![].into_iter().collect()
+        ));
+        check_did_detail(&did_1, 2, 1, 1, 3);
+
+        // Rotation fails as a whole if any of the keys to remove doesn't exist
+        let rotate_keys = RotateKeys {
+            did: did_1.clone(),
+            remove: vec![1u32.into(), 5u32.into()].into_iter().collect(),
+            add: vec![DidKey::new_with_all_relationships(PublicKey::ed25519(
+                pk_ed_2,
+            ))],
+            nonce: 3 + 1,
+        };
+        let sig = SigValue::sr25519(&rotate_keys.to_state_change().encode(), &pair_sr);
+        assert_noop!(
+            DIDModule::rotate_keys(
+                Origin::signed(alice),
+                rotate_keys,
+                vec![DidSignature {
+                    did: Controller(did_1.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::NoKeyForDid
+        );
+
+        // Rotation fails as a whole if any of the keys to add is invalid
+        let rotate_keys = RotateKeys {
+            did: did_1.clone(),
+            remove: vec![1u32.into()].into_iter().collect(),
+            add: vec![DidKey::new(
+                PublicKey::x25519(pk_ed_2),
+                VerRelType::AUTHENTICATION,
+            )],
+            nonce: 3 + 1,
+        };
+        let sig = SigValue::sr25519(&rotate_keys.to_state_change().encode(), &pair_sr);
+        assert_noop!(
+            DIDModule::rotate_keys(
+                Origin::signed(alice),
+                rotate_keys,
+                vec![DidSignature {
+                    did: Controller(did_1.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::IncompatibleVerificationRelation
+        );
+
+        let rotate_keys = RotateKeys {
+            did: did_1.clone(),
+            remove: BTreeSet::new(),
+            add: vec![],
+            nonce: 3 + 1,
+        };
+        let sig = SigValue::sr25519(&rotate_keys.to_state_change().encode(), &pair_sr);
+        assert_noop!(
+            DIDModule::rotate_keys(
+                Origin::signed(alice),
+                rotate_keys,
+                vec![DidSignature {
+                    did: Controller(did_1.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::NoKeyProvided
+        );
+
+        // Replace the controller key with a new one
+        let rotate_keys = RotateKeys {
+            did: did_1.clone(),
+            remove: vec![1u32.into()].into_iter().collect(),
+            add: vec![DidKey::new_with_all_relationships(PublicKey::ed25519(
+                pk_ed_2,
+            ))],
+            nonce: 3 + 1,
+        };
+        let sig = SigValue::sr25519(&rotate_keys.to_state_change().encode(), &pair_sr);
+        assert_ok!(DIDModule::rotate_keys(
+            Origin::signed(alice),
+            rotate_keys,
+            vec![DidSignature {
+                did: Controller(did_1.clone()),
+                key_id: 1u32.into(),
+                sig
+            }]
+        ));
+        check_did_detail(&did_1, 3, 1, 1, 4);
+        assert!(DIDModule::did_key(&did_1, IncId::from(1u32)).is_none());
+        assert_eq!(
+            DIDModule::did_key(&did_1, IncId::from(3u32)),
+            Some(DidKey::new_with_all_relationships(PublicKey::ed25519(
+                pk_ed_2
+            )))
+        );
+        assert!(DIDModule::is_self_controlled(&did_1));
+
+        // Old key can't be used anymore
+        let rotate_keys = RotateKeys {
+            did: did_1.clone(),
+            remove: vec![2u32.into()].into_iter().collect(),
+            add: vec![],
+            nonce: 4 + 1,
+        };
+        let sig = SigValue::sr25519(&rotate_keys.to_state_change().encode(), &pair_sr);
+        assert_noop!(
+            DIDModule::rotate_keys(
+                Origin::signed(alice),
+                rotate_keys,
+                vec![DidSignature {
+                    did: Controller(did_1.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::NoKeyForDid
+        );
+
+        // Replacing the controller key with a non-controller key removes self-control
+        let rotate_keys = RotateKeys {
+            did: did_1.clone(),
+            remove: vec![3u32.into()].into_iter().collect(),
+            add: vec![DidKey::new(
+                PublicKey::sr25519(pk_sr),
+                VerRelType::AUTHENTICATION,
+            )],
+            nonce: 4 + 1,
+        };
+        let sig = SigValue::ed25519(&rotate_keys.to_state_change().encode(), &pair_ed_2);
+        assert_ok!(DIDModule::rotate_keys(
+            Origin::signed(alice),
+            rotate_keys,
+            vec![DidSignature {
+                did: Controller(did_1.clone()),
+                key_id: 3u32.into(),
+                sig
+            }]
+        ));
+        check_did_detail(&did_1, 4, 0, 0, 5);
+        assert!(!DIDModule::is_self_controlled(&did_1));
+    });
+}
+
+#[test]
+fn key_validity_window() {
+    // Keys can only be used within their validity window
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_1: Did = [71; Did::BYTE_SIZE].into();
+
+        let (pair_sr, _, _) = sr25519::Pair::generate_with_phrase(None);
+        let pk_sr = pair_sr.public().0;
+        let (pair_ed, _, _) = ed25519::Pair::generate_with_phrase(None);
+        let pk_ed = pair_ed.public().0;
+
+        run_to_block(2);
+
+        assert_noop!(
+            DIDModule::new_onchain(
+                Origin::signed(alice),
+                did_1.clone(),
+                vec![
+                    DidKey::new_with_all_relationships(PublicKey::sr25519(pk_sr))
+                        .with_validity_window(Some(10), Some(9))
+                ],
+                vec![].into_iter().collect()
+            ),
+            Error::<Test>::InvalidKeyValidityWindow
+        );
+
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_1.clone(),
+            vec![
+                DidKey::new_with_all_relationships(PublicKey::sr25519(pk_sr))
+                    .with_validity_window(None, Some(5)),
+                DidKey::new_with_all_relationships(PublicKey::ed25519(pk_ed))
+                    .with_validity_window(Some(10), None),
+            ],
+            vec![].into_iter().collect()
+        ));
+        check_did_detail(&did_1, 2, 2, 1, 2);
+
+        let add_keys = |nonce| AddKeys::<Test> {
+            did: did_1.clone(),
+            keys: vec![DidKey::new(
+                PublicKey::x25519(pk_ed),
+                VerRelType::KEY_AGREEMENT,
+            )],
+            nonce,
+        };
+
+        // Key 2 is not valid yet
+        let keys = add_keys(2 + 1);
+        let sig = SigValue::ed25519(&keys.to_state_change().encode(), &pair_ed);
+        assert_noop!(
+            DIDModule::add_keys(
+                Origin::signed(alice),
+                keys,
+                vec![DidSignature {
+                    did: Controller(did_1.clone()),
+                    key_id: 2u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::KeyOutsideValidityWindow
+        );
+
+        // Key 1 is valid till block 5 inclusive
+        run_to_block(5);
+        let keys = add_keys(2 + 1);
+        let sig = SigValue::sr25519(&keys.to_state_change().encode(), &pair_sr);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            keys,
+            vec![DidSignature {
+                did: Controller(did_1.clone()),
+                key_id: 1u32.into(),
+                sig
+            }]
+        ));
+        check_did_detail(&did_1, 3, 2, 1, 3);
+
+        run_to_block(6);
+        let keys = add_keys(3 + 1);
+        let sig = SigValue::sr25519(&keys.to_state_change().encode(), &pair_sr);
+        assert_noop!(
+            DIDModule::add_keys(
+                Origin::signed(alice),
+                keys,
+                vec![DidSignature {
+                    did: Controller(did_1.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::KeyOutsideValidityWindow
+        );
+
+        run_to_block(10);
+        let keys = add_keys(3 + 1);
+        let sig = SigValue::ed25519(&keys.to_state_change().encode(), &pair_ed);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            keys,
+            vec![DidSignature {
+                did: Controller(did_1.clone()),
+                key_id: 2u32.into(),
+                sig
+            }]
+        ));
+        check_did_detail(&did_1, 4, 2, 1, 4);
+
+        assert_eq!(
+            DIDModule::did_key(&did_1, IncId::from(2u32)),
+            Some(
+                DidKey::new_with_all_relationships(PublicKey::ed25519(pk_ed))
+                    .with_validity_window(Some(10), None)
+            )
+        );
+    });
+}
+
+#[test]
+fn controller_threshold() {
+    // DID with a controller threshold can only be updated by enough distinct controllers
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_org: Did = [81; Did::BYTE_SIZE].into();
+        let did_a: Did = [82; Did::BYTE_SIZE].into();
+        let did_b: Did = [83; Did::BYTE_SIZE].into();
+        let did_c: Did = [84; Did::BYTE_SIZE].into();
+
+        let (pair_ed, _, _) = ed25519::Pair::generate_with_phrase(None);
+        let pk_ed = pair_ed.public().0;
+
+        run_to_block(10);
+
+        let kp_a = create_did(did_a);
+        let kp_b = create_did(did_b);
+        let kp_c = create_did(did_c);
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_org.clone(),
+            vec![],
+            vec![did_a, did_b, did_c]
+                .into_iter()
+                .map(Controller)
+                .collect()
+        ));
+        check_did_detail(&did_org, 0, 0, 3, 10);
+
+        // Without a threshold, any single controller can update the DID using its own nonce
+        let set_threshold = SetControllerThreshold {
+            did: did_org.clone(),
+            threshold: Some(2),
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&set_threshold, &kp_a, Controller(did_a), 1);
+        assert_ok!(DIDModule::set_controller_threshold(
+            Origin::signed(alice),
+            set_threshold,
+            vec![sig]
+        ));
+        check_nonce(&did_a, 11);
+        check_nonce(&did_org, 10);
+        assert_eq!(
+            DIDModule::onchain_did_details(&did_org)
+                .unwrap()
+                .data()
+                .controller_threshold,
+            Some(2)
+        );
+
+        let add_keys = AddKeys {
+            did: did_org.clone(),
+            keys: vec![DidKey::new(
+                PublicKey::x25519(pk_ed),
+                VerRelType::KEY_AGREEMENT,
+            )],
+            nonce: 10 + 1,
+        };
+
+        // A single controller isn't enough anymore
+        let sig = did_sig::<Test, _, _>(&add_keys, &kp_b, Controller(did_b), 1);
+        assert_noop!(
+            DIDModule::add_keys(Origin::signed(alice), add_keys.clone(), vec![sig]),
+            Error::<Test>::NotEnoughControllerSignatures
+        );
+
+        // Signatures must come from distinct controllers
+        let payload = MultiControllerAction::new(&add_keys);
+        let sig = did_sig::<Test, _, _>(&payload, &kp_a, Controller(did_a), 1);
+        assert_noop!(
+            DIDModule::add_keys(
+                Origin::signed(alice),
+                add_keys.clone(),
+                vec![sig.clone(), sig]
+            ),
+            Error::<Test>::DuplicateControllerSignature
+        );
+
+        // All signatures must be valid
+        let sig_a = did_sig::<Test, _, _>(&payload, &kp_a, Controller(did_a), 1);
+        let wrong_sig_b = did_sig::<Test, _, _>(&payload, &kp_c, Controller(did_b), 1);
+        assert_noop!(
+            DIDModule::add_keys(
+                Origin::signed(alice),
+                add_keys.clone(),
+                vec![sig_a.clone(), wrong_sig_b]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        // Signatures of the action itself aren't accepted from multiple controllers
+        let unwrapped_sig_a = did_sig::<Test, _, _>(&add_keys, &kp_a, Controller(did_a), 1);
+        let unwrapped_sig_b = did_sig::<Test, _, _>(&add_keys, &kp_b, Controller(did_b), 1);
+        assert_noop!(
+            DIDModule::add_keys(
+                Origin::signed(alice),
+                add_keys.clone(),
+                vec![unwrapped_sig_a, unwrapped_sig_b]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        // Multiple controllers use the nonce of the controlled DID
+        let sig_b = did_sig::<Test, _, _>(&payload, &kp_b, Controller(did_b), 1);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            add_keys.clone(),
+            vec![sig_a, sig_b.clone()]
+        ));
+        check_did_detail(&did_org, 1, 0, 3, 11);
+        check_nonce(&did_a, 11);
+        check_nonce(&did_b, 10);
+
+        for (threshold, err) in vec![
+            (Some(0), Error::<Test>::InvalidControllerThreshold),
+            (Some(4), Error::<Test>::ControllerThresholdNotReachable),
+        ] {
+            let set_threshold = SetControllerThreshold {
+                did: did_org.clone(),
+                threshold,
+                nonce: 11 + 1,
+            };
+            let payload = MultiControllerAction::new(&set_threshold);
+            let sig_a = did_sig::<Test, _, _>(&payload, &kp_a, Controller(did_a), 1);
+            let sig_c = did_sig::<Test, _, _>(&payload, &kp_c, Controller(did_c), 1);
+            assert_noop!(
+                DIDModule::set_controller_threshold(
+                    Origin::signed(alice),
+                    set_threshold,
+                    vec![sig_a, sig_c]
+                ),
+                err
+            );
+        }
+
+        // Controllers can't be removed if the threshold can't be met afterwards
+        let remove_controllers = RemoveControllers {
+            did: did_org.clone(),
+            controllers: vec![did_b, did_c].into_iter().map(Controller).collect(),
+            nonce: 11 + 1,
+        };
+        let payload = MultiControllerAction::new(&remove_controllers);
+        let sig_a = did_sig::<Test, _, _>(&payload, &kp_a, Controller(did_a), 1);
+        let sig_c = did_sig::<Test, _, _>(&payload, &kp_c, Controller(did_c), 1);
+        assert_noop!(
+            DIDModule::remove_controllers(
+                Origin::signed(alice),
+                remove_controllers,
+                vec![sig_a, sig_c]
+            ),
+            Error::<Test>::ControllerThresholdNotReachable
+        );
+
+        let remove_controllers = RemoveControllers {
+            did: did_org.clone(),
+            controllers: vec![did_c].into_iter().map(Controller).collect(),
+            nonce: 11 + 1,
+        };
+        let payload = MultiControllerAction::new(&remove_controllers);
+        let sig_a = did_sig::<Test, _, _>(&payload, &kp_a, Controller(did_a), 1);
+        let sig_c = did_sig::<Test, _, _>(&payload, &kp_c, Controller(did_c), 1);
+        assert_ok!(DIDModule::remove_controllers(
+            Origin::signed(alice),
+            remove_controllers,
+            vec![sig_a, sig_c]
+        ));
+        check_did_detail(&did_org, 1, 0, 2, 12);
+
+        // Removing the threshold makes a single controller enough again
+        let set_threshold = SetControllerThreshold {
+            did: did_org.clone(),
+            threshold: None,
+            nonce: 12 + 1,
+        };
+        let payload = MultiControllerAction::new(&set_threshold);
+        let sig_a = did_sig::<Test, _, _>(&payload, &kp_a, Controller(did_a), 1);
+        let sig_b = did_sig::<Test, _, _>(&payload, &kp_b, Controller(did_b), 1);
+        assert_ok!(DIDModule::set_controller_threshold(
+            Origin::signed(alice),
+            set_threshold,
+            vec![sig_a, sig_b]
+        ));
+        check_did_detail(&did_org, 1, 0, 2, 13);
+
+        // The signature given along with another controller's one at the nonce of the controlled DID can't be
+        // replayed as the only signature now that the nonce of the signer matches
+        assert_eq!(add_keys.nonce, 10 + 1);
+        assert_noop!(
+            DIDModule::add_keys(Origin::signed(alice), add_keys, vec![sig_b]),
+            Error::<Test>::InvalidSignature
+        );
+
+        let remove_controllers = RemoveControllers {
+            did: did_org.clone(),
+            controllers: vec![did_a].into_iter().map(Controller).collect(),
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&remove_controllers, &kp_b, Controller(did_b), 1);
+        assert_ok!(DIDModule::remove_controllers(
+            Origin::signed(alice),
+            remove_controllers,
+            vec![sig]
+        ));
+        check_did_detail(&did_org, 1, 0, 1, 13);
+        check_nonce(&did_b, 11);
+    });
+}
+
+#[test]
+fn convert_offchain_to_onchain() {
+    // Off-chain DID can be converted to the on-chain DID by its owner
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let bob = 2u64;
+        let did_1: Did = [91; Did::BYTE_SIZE].into();
+        let did_2: Did = [92; Did::BYTE_SIZE].into();
+
+        let (pair_sr, _, _) = sr25519::Pair::generate_with_phrase(None);
+        let pk_sr = pair_sr.public().0;
+        let keys = vec![DidKey::new_with_all_relationships(PublicKey::sr25519(
+            pk_sr,
+        ))];
+
+        assert_noop!(
+            DIDModule::convert_offchain_to_onchain(
+                Origin::signed(alice),
+                did_1.clone(),
+                keys.clone(),
+                vec![].into_iter().collect()
+            ),
+            Error::<Test>::DidDoesNotExist
+        );
+
+        run_to_block(3);
+
+        let doc_ref = url_doc_ref("did.json");
+        assert_ok!(DIDModule::new_offchain(
+            Origin::signed(alice),
+            did_1.clone(),
+            doc_ref
+        ));
+
+        assert_noop!(
+            DIDModule::convert_offchain_to_onchain(
+                Origin::signed(bob),
+                did_1.clone(),
+                keys.clone(),
+                vec![].into_iter().collect()
+            ),
+            Error::<Test>::DidNotOwnedByAccount
+        );
+        assert_noop!(
+            DIDModule::convert_offchain_to_onchain(
+                Origin::signed(alice),
+                did_1.clone(),
+                vec![],
+                vec![].into_iter().collect()
+            ),
+            Error::<Test>::NoControllerProvided
+        );
+
+        run_to_block(7);
+
+        assert_ok!(DIDModule::convert_offchain_to_onchain(
+            Origin::signed(alice),
+            did_1.clone(),
+            keys.clone(),
+            vec![did_2].into_iter().map(Controller).collect()
+        ));
+        assert!(DIDModule::is_onchain_did(&did_1).unwrap());
+        assert!(DIDModule::is_self_controlled(&did_1));
+        assert!(DIDModule::is_controller(&did_1, &Controller(did_2)));
+        check_did_detail(&did_1, 1, 1, 2, 7);
+        assert_eq!(
+            DIDModule::did_key(&did_1, IncId::from(1u32)),
+            Some(keys[0].clone())
+        );
+        // The owner stays linked to the converted DID
+        assert_eq!(DIDModule::linked_accounts(&did_1), vec![alice]);
+        assert_eq!(DIDModule::dids_by_account(&alice), vec![did_1]);
+
+        // On-chain DID can't be converted again
+        assert_noop!(
+            DIDModule::convert_offchain_to_onchain(
+                Origin::signed(alice),
+                did_1.clone(),
+                keys,
+                vec![].into_iter().collect()
+            ),
+            Error::<Test>::CannotGetDetailForOffChainDid
+        );
+    });
+}
+
+#[test]
+fn deactivated_did_cannot_be_registered_again() {
+    // Removed DIDs leave a tombstone which prevents them from being registered again
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_1: Did = [61; Did::BYTE_SIZE].into();
+        let did_2: Did = [62; Did::BYTE_SIZE].into();
+
+        run_to_block(4);
+
+        let pair_sr = create_did(did_1);
+        let rem_did = DidRemoval {
+            did: did_1.clone(),
+            nonce: 4 + 1,
+        };
+        let sig = SigValue::sr25519(&rem_did.to_state_change().encode(), &pair_sr);
+        assert_ok!(DIDModule::remove_onchain_did(
+            Origin::signed(alice),
+            rem_did,
+            vec![DidSignature {
+                did: Controller(did_1.clone()),
+                key_id: 1u32.into(),
+                sig
+            }]
+        ));
+        ensure_onchain_did_gone(&did_1);
+        assert_eq!(DIDModule::deactivated_did(&did_1), Some(4));
+        assert_eq!(
+            DIDModule::resolve_did(&did_1),
+            Some(DidResolution::Deactivated { deactivated_at: 4 })
+        );
+
+        let doc_ref = cid_doc_ref(129);
+        assert_ok!(DIDModule::new_offchain(
+            Origin::signed(alice),
+            did_2.clone(),
+            doc_ref.clone()
+        ));
+
+        run_to_block(9);
+
+        assert_ok!(DIDModule::remove_offchain_did(Origin::signed(alice), did_2));
+        assert!(Dids::<Test>::get(&did_2).is_none());
+        assert_eq!(
+            DIDModule::resolve_did(&did_2),
+            Some(DidResolution::Deactivated { deactivated_at: 9 })
+        );
+
+        for did in vec![did_1, did_2] {
+            assert_noop!(
+                DIDModule::new_onchain(
+                    Origin::signed(alice),
+                    did.clone(),
+                    vec![DidKey::new_with_all_relationships(PublicKey::sr25519(
+                        pair_sr.public().0
+                    ))],
+                    vec![].into_iter().collect()
+                ),
+                Error::<Test>::DidIsDeactivated
+            );
+            assert_noop!(
+                DIDModule::new_offchain(Origin::signed(alice), did, doc_ref.clone()),
+                Error::<Test>::DidIsDeactivated
+            );
+        }
+    });
+}
+
+#[test]
+fn secp256r1_keys() {
+    // DID controlled by a P-256 key
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did: Did = [71; Did::BYTE_SIZE].into();
+
+        let (sk_p256, pk_p256) = get_secp256r1_keypair(&[21; 32]);
+        let (_, pk_p256_2) = get_secp256r1_keypair(&[22; 32]);
+
+        // Bytes which aren't a compressed P-256 point can't be used as a key
+        assert_noop!(
+            DIDModule::new_onchain(
+                Origin::signed(alice),
+                did.clone(),
+                vec![DidKey::new_with_all_relationships(PublicKey::secp256r1(
+                    [5; 33]
+                ))],
+                vec![].into_iter().collect()
+            ),
+            Error::<Test>::InvalidPublicKey
+        );
+
+        run_to_block(3);
+
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did.clone(),
+            vec![DidKey::new_with_all_relationships(pk_p256.clone())],
+            vec![].into_iter().collect()
+        ));
+        assert!(DIDModule::is_self_controlled(&did));
+        not_key_agreement(&DIDModule::did_key(&did, IncId::from(1u32)).unwrap());
+
+        let add_keys = AddKeys {
+            did: did.clone(),
+            keys: vec![DidKey::new(pk_p256_2.clone(), VerRelType::ASSERTION)],
+            nonce: 3 + 1,
+        };
+
+        // Signature by another key type isn't accepted for the P-256 key
+        let (pair_sr, _, _) = sr25519::Pair::generate_with_phrase(None);
+        let sig = SigValue::sr25519(&add_keys.to_state_change().encode(), &pair_sr);
+        assert_noop!(
+            DIDModule::add_keys(
+                Origin::signed(alice),
+                add_keys.clone(),
+                vec![DidSignature {
+                    did: Controller(did.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::IncompatSigPubkey
+        );
+
+        let sig = SigValue::secp256r1(&add_keys.to_state_change().encode(), &sk_p256);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            add_keys,
+            vec![DidSignature {
+                did: Controller(did.clone()),
+                key_id: 1u32.into(),
+                sig
+            }]
+        ));
+        check_did_detail(&did, 2, 1, 1, 4);
+        assert_eq!(
+            DIDModule::did_key(&did, IncId::from(2u32)),
+            Some(DidKey::new(pk_p256_2, VerRelType::ASSERTION))
+        );
+
+        // P-256 key can also be used through a WebAuthn assertion
+        let remove_keys = RemoveKeys {
+            did: did.clone(),
+            keys: vec![2u32.into()].into_iter().collect(),
+            nonce: 4 + 1,
+        };
+
+        // Assertion over another action isn't accepted
+        let other_remove_keys = RemoveKeys {
+            nonce: 4 + 2,
+            ..remove_keys.clone()
+        };
+        let sig = SigValue::webauthn(&other_remove_keys.to_state_change().encode(), &sk_p256);
+        assert_noop!(
+            DIDModule::remove_keys(
+                Origin::signed(alice),
+                remove_keys.clone(),
+                vec![DidSignature {
+                    did: Controller(did.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        // Assertion made for another Relying Party isn't accepted
+        let sig = sign_with_webauthn_for_rp(
+            &remove_keys.to_state_change().encode(),
+            &sk_p256,
+            b"example.com",
+        );
+        assert_noop!(
+            DIDModule::remove_keys(
+                Origin::signed(alice),
+                remove_keys.clone(),
+                vec![DidSignature {
+                    did: Controller(did.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        let sig = SigValue::webauthn(&remove_keys.to_state_change().encode(), &sk_p256);
+        assert_ok!(DIDModule::remove_keys(
+            Origin::signed(alice),
+            remove_keys,
+            vec![DidSignature {
+                did: Controller(did.clone()),
+                key_id: 1u32.into(),
+                sig
+            }]
+        ));
+        check_did_detail(&did, 2, 1, 1, 5);
+        assert_eq!(DIDModule::did_key(&did, IncId::from(2u32)), None);
+    });
+}
+
+#[test]
+fn secp256k1_eip191_signatures() {
+    // DID controlled by a Secp256k1 key of an Ethereum wallet
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did: Did = [72; Did::BYTE_SIZE].into();
+
+        let (sk_secp, pk_secp) = get_secp256k1_keypair(&[21; 32]);
+        let (sk_secp_2, _) = get_secp256k1_keypair(&[22; 32]);
+        let (pair_ed, _, _) = ed25519::Pair::generate_with_phrase(None);
+        let pk_ed = pair_ed.public().0;
+
+        run_to_block(6);
+
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did.clone(),
+            vec![DidKey::new_with_all_relationships(pk_secp)],
+            vec![].into_iter().collect()
+        ));
+
+        let add_keys = AddKeys {
+            did: did.clone(),
+            keys: vec![DidKey::new_with_all_relationships(PublicKey::ed25519(
+                pk_ed,
+            ))],
+            nonce: 6 + 1,
+        };
+
+        // Signature recovering to another key isn't accepted
+        let sig = SigValue::secp256k1_eip191(&add_keys.to_state_change().encode(), &sk_secp_2);
+        assert_noop!(
+            DIDModule::add_keys(
+                Origin::signed(alice),
+                add_keys.clone(),
+                vec![DidSignature {
+                    did: Controller(did.clone()),
+                    key_id: 1u32.into(),
+                    sig
+                }]
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        let sig = SigValue::secp256k1_eip191(&add_keys.to_state_change().encode(), &sk_secp);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            add_keys,
+            vec![DidSignature {
+                did: Controller(did.clone()),
+                key_id: 1u32.into(),
+                sig
+            }]
+        ));
+        check_did_detail(&did, 2, 2, 1, 7);
+    });
+}
+
+#[test]
+fn batch_update() {
+    // Applying several updates to the DID doc with a single signature
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_1: Did = [81; Did::BYTE_SIZE].into();
+        let did_2: Did = [82; Did::BYTE_SIZE].into();
+
+        run_to_block(10);
+
+        let pair_sr = create_did(did_1);
+        let (pair_ed, _, _) = ed25519::Pair::generate_with_phrase(None);
+        let pk_ed = pair_ed.public().0;
+        let endpoint_id: WrappedBytes = vec![102; 50].into();
+        let endpoint = ServiceEndpoint {
+            types: vec![ServiceEndpointType::LinkedDomains]
+                .into_iter()
+                .collect(),
+            origins: vec![vec![112; 100].into()],
+            properties: vec![],
+        };
+
+        let sign_batch = |batch: &DidBatchUpdate<Test>| {
+            vec![DidSignature {
+                did: Controller(did_1.clone()),
+                key_id: 1u32.into(),
+                sig: SigValue::sr25519(&batch.to_state_change().encode(), &pair_sr),
+            }]
+        };
+
+        let batch = DidBatchUpdate {
+            did: did_1.clone(),
+            operations: vec![],
+            nonce: 10 + 1,
+        };
+        assert_noop!(
+            DIDModule::batch_update(Origin::signed(alice), batch.clone(), sign_batch(&batch)),
+            Error::<Test>::NoOperationProvided
+        );
+
+        // Nothing is applied if any of the operations fails
+        let batch = DidBatchUpdate {
+            did: did_1.clone(),
+            operations: vec![
+                DidUpdateOperation::AddKeys(vec![DidKey::new(
+                    PublicKey::ed25519(pk_ed),
+                    VerRelType::ASSERTION,
+                )]),
+                DidUpdateOperation::AddServiceEndpoint {
+                    id: endpoint_id.clone(),
+                    endpoint: endpoint.clone(),
+                },
+                DidUpdateOperation::RemoveServiceEndpoint(vec![103; 50].into()),
+            ],
+            nonce: 10 + 1,
+        };
+        assert_noop!(
+            DIDModule::batch_update(Origin::signed(alice), batch.clone(), sign_batch(&batch)),
+            Error::<Test>::ServiceEndpointDoesNotExist
+        );
+
+        let batch = DidBatchUpdate {
+            did: did_1.clone(),
+            operations: vec![DidUpdateOperation::AddControllers(
+                vec![].into_iter().collect(),
+            )],
+            nonce: 10 + 1,
+        };
+        assert_noop!(
+            DIDModule::batch_update(Origin::signed(alice), batch.clone(), sign_batch(&batch)),
+            Error::<Test>::NoControllerProvided
+        );
+
+        let batch = DidBatchUpdate {
+            did: did_1.clone(),
+            operations: vec![
+                DidUpdateOperation::AddKeys(vec![DidKey::new(
+                    PublicKey::ed25519(pk_ed),
+                    VerRelType::ASSERTION,
+                )]),
+                DidUpdateOperation::AddControllers(
+                    vec![did_2].into_iter().map(Controller).collect(),
+                ),
+                DidUpdateOperation::AddServiceEndpoint {
+                    id: endpoint_id.clone(),
+                    endpoint: endpoint.clone(),
+                },
+            ],
+            nonce: 10 + 1,
+        };
+        assert_ok!(DIDModule::batch_update(
+            Origin::signed(alice),
+            batch.clone(),
+            sign_batch(&batch)
+        ));
+        // Single nonce is consumed by the whole batch
+        check_did_detail(&did_1, 2, 1, 2, 11);
+        assert!(DIDModule::is_controller(&did_1, &Controller(did_2)));
+        assert_eq!(
+            DIDModule::did_key(&did_1, IncId::from(2u32)),
+            Some(DidKey::new(
+                PublicKey::ed25519(pk_ed),
+                VerRelType::ASSERTION
+            ))
+        );
+        assert_eq!(
+            DIDModule::did_service_endpoints(&did_1, &endpoint_id),
+            Some(endpoint)
+        );
+
+        // Operations are applied in order, so an added entity can be removed within the same batch
+        let batch = DidBatchUpdate {
+            did: did_1.clone(),
+            operations: vec![
+                DidUpdateOperation::RemoveKeys(vec![2u32.into()].into_iter().collect()),
+                DidUpdateOperation::RemoveServiceEndpoint(endpoint_id.clone()),
+                DidUpdateOperation::AddServiceEndpoint {
+                    id: endpoint_id.clone(),
+                    endpoint: ServiceEndpoint {
+                        types: vec![ServiceEndpointType::LinkedDomains]
+                            .into_iter()
+                            .collect(),
+                        origins: vec![vec![113; 100].into()],
+                        properties: vec![],
+                    },
+                },
+                DidUpdateOperation::RemoveControllers(
+                    vec![did_2].into_iter().map(Controller).collect(),
+                ),
+            ],
+            nonce: 11 + 1,
+        };
+        assert_ok!(DIDModule::batch_update(
+            Origin::signed(alice),
+            batch.clone(),
+            sign_batch(&batch)
+        ));
+        check_did_detail(&did_1, 2, 1, 1, 12);
+        assert_eq!(DIDModule::did_key(&did_1, IncId::from(2u32)), None);
+        assert!(!DIDModule::is_controller(&did_1, &Controller(did_2)));
+        assert_eq!(
+            DIDModule::did_service_endpoints(&did_1, &endpoint_id)
+                .unwrap()
+                .origins,
+            vec![vec![113; 100].into()]
+        );
+    });
+}
+
+#[test]
+fn social_recovery() {
+    // Guardians can add a new controller key to the DID after the recovery delay unless a controller cancels it
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did: Did = [91; Did::BYTE_SIZE].into();
+        let guardian_1: Did = [92; Did::BYTE_SIZE].into();
+        let guardian_2: Did = [93; Did::BYTE_SIZE].into();
+        let guardian_3: Did = [94; Did::BYTE_SIZE].into();
+        let stranger: Did = [95; Did::BYTE_SIZE].into();
+
+        run_to_block(10);
+
+        let kp = create_did(did);
+        let kp_1 = create_did(guardian_1);
+        let kp_2 = create_did(guardian_2);
+        let kp_3 = create_did(guardian_3);
+        let kp_stranger = create_did(stranger);
+        let guardians: BTreeSet<_> = vec![guardian_1, guardian_2, guardian_3]
+            .into_iter()
+            .collect();
+
+        for (guardians, threshold, delay) in vec![
+            (BTreeSet::new(), 1, 5),
+            (guardians.clone(), 0, 5),
+            (guardians.clone(), 4, 5),
+            (guardians.clone(), 2, 4),
+            (vec![did, guardian_1].into_iter().collect(), 1, 5),
+            (
+                (100..106).map(|i| [i; Did::BYTE_SIZE].into()).collect(),
+                1,
+                5,
+            ),
+        ] {
+            let set_config = SetRecoveryConfig {
+                did,
+                config: Some(RecoveryConfig {
+                    guardians,
+                    threshold,
+                    delay,
+                }),
+                nonce: 10 + 1,
+            };
+            let sig = did_sig::<Test, _, _>(&set_config, &kp, Controller(did), 1);
+            assert_noop!(
+                DIDModule::set_recovery_config(Origin::signed(alice), set_config, vec![sig]),
+                Error::<Test>::InvalidRecoveryConfig
+            );
+        }
+
+        let new_kp = gen_kp();
+        let initiate = InitiateRecovery {
+            did,
+            new_key: DidKey::new_with_all_relationships(PublicKey::sr25519(new_kp.public().0)),
+            nonce: 10 + 1,
+        };
+        let sig_1 = did_sig::<Test, _, _>(&initiate, &kp_1, guardian_1, 1);
+        let sig_2 = did_sig::<Test, _, _>(&initiate, &kp_2, guardian_2, 1);
+        assert_noop!(
+            DIDModule::initiate_recovery(
+                Origin::signed(alice),
+                initiate.clone(),
+                vec![sig_1.clone(), sig_2.clone()]
+            ),
+            Error::<Test>::NoRecoveryConfig
+        );
+
+        let config = RecoveryConfig {
+            guardians,
+            threshold: 2,
+            delay: 5,
+        };
+        let set_config = SetRecoveryConfig {
+            did,
+            config: Some(config.clone()),
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&set_config, &kp, Controller(did), 1);
+        assert_ok!(DIDModule::set_recovery_config(
+            Origin::signed(alice),
+            set_config,
+            vec![sig]
+        ));
+        check_nonce(&did, 11);
+        assert_eq!(
+            DIDModule::did_recovery(&did),
+            Some(DidRecoveryStatus {
+                config: config.clone(),
+                request: None
+            })
+        );
+
+        let initiate = InitiateRecovery {
+            nonce: 11 + 1,
+            ..initiate
+        };
+        let sig_1 = did_sig::<Test, _, _>(&initiate, &kp_1, guardian_1, 1);
+        let sig_2 = did_sig::<Test, _, _>(&initiate, &kp_2, guardian_2, 1);
+        let sig_stranger = did_sig::<Test, _, _>(&initiate, &kp_stranger, stranger, 1);
+        let wrong_sig_3 = did_sig::<Test, _, _>(&initiate, &kp_1, guardian_3, 1);
+        for (sigs, err) in vec![
+            (
+                vec![sig_1.clone()],
+                Error::<Test>::NotEnoughGuardianSignatures,
+            ),
+            (
+                vec![sig_1.clone(), sig_stranger],
+                Error::<Test>::NotAGuardian,
+            ),
+            (
+                vec![sig_1.clone(), sig_1.clone()],
+                Error::<Test>::DuplicateGuardianSignature,
+            ),
+            (
+                vec![sig_1.clone(), wrong_sig_3],
+                Error::<Test>::InvalidSignature,
+            ),
+        ] {
+            assert_noop!(
+                DIDModule::initiate_recovery(Origin::signed(alice), initiate.clone(), sigs),
+                err
+            );
+        }
+
+        let key_agreement = InitiateRecovery {
+            new_key: DidKey::new(
+                PublicKey::x25519(new_kp.public().0),
+                VerRelType::KEY_AGREEMENT,
+            ),
+            ..initiate.clone()
+        };
+        let sigs = vec![
+            did_sig::<Test, _, _>(&key_agreement, &kp_1, guardian_1, 1),
+            did_sig::<Test, _, _>(&key_agreement, &kp_2, guardian_2, 1),
+        ];
+        assert_noop!(
+            DIDModule::initiate_recovery(Origin::signed(alice), key_agreement, sigs),
+            Error::<Test>::InvalidRecoveryKey
+        );
+
+        // Guardians use the nonce of the recovered DID
+        assert_ok!(DIDModule::initiate_recovery(
+            Origin::signed(alice),
+            initiate.clone(),
+            vec![sig_1, sig_2]
+        ));
+        check_nonce(&did, 12);
+        check_nonce(&guardian_1, 10);
+        let request = DIDModule::did_recovery_request(&did).unwrap();
+        assert_eq!(request.executable_at, 15);
+        assert_eq!(
+            request.initiated_by,
+            vec![guardian_1, guardian_2].into_iter().collect()
+        );
+
+        // Guardians can't be changed while the recovery is pending
+        let set_config = SetRecoveryConfig {
+            did,
+            config: None,
+            nonce: 12 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&set_config, &kp, Controller(did), 1);
+        assert_noop!(
+            DIDModule::set_recovery_config(Origin::signed(alice), set_config, vec![sig]),
+            Error::<Test>::RecoveryInProgress
+        );
+
+        assert_noop!(
+            DIDModule::execute_recovery(Origin::signed(alice), did),
+            Error::<Test>::RecoveryDelayNotElapsed
+        );
+
+        // Any controller of the DID can cancel the recovery
+        let cancel = CancelRecovery { did, nonce: 12 + 1 };
+        let sig = did_sig::<Test, _, _>(&cancel, &kp, Controller(did), 1);
+        assert_ok!(DIDModule::cancel_recovery(
+            Origin::signed(alice),
+            cancel,
+            sig
+        ));
+        check_nonce(&did, 13);
+        assert!(DIDModule::did_recovery_request(&did).is_none());
+        assert_noop!(
+            DIDModule::execute_recovery(Origin::signed(alice), did),
+            Error::<Test>::NoRecoveryInProgress
+        );
+
+        run_to_block(12);
+
+        let initiate = InitiateRecovery {
+            nonce: 13 + 1,
+            ..initiate
+        };
+        let sigs = vec![
+            did_sig::<Test, _, _>(&initiate, &kp_2, guardian_2, 1),
+            did_sig::<Test, _, _>(&initiate, &kp_3, guardian_3, 1),
+        ];
+        assert_ok!(DIDModule::initiate_recovery(
+            Origin::signed(alice),
+            initiate.clone(),
+            sigs.clone()
+        ));
+        assert_noop!(
+            DIDModule::initiate_recovery(Origin::signed(alice), initiate, sigs),
+            Error::<Test>::RecoveryInProgress
+        );
+
+        run_to_block(16);
+
+        assert_noop!(
+            DIDModule::execute_recovery(Origin::signed(alice), did),
+            Error::<Test>::RecoveryDelayNotElapsed
+        );
+
+        run_to_block(17);
+
+        // Anyone can execute the recovery once the delay has passed
+        assert_ok!(DIDModule::execute_recovery(Origin::signed(alice), did));
+        check_did_detail(&did, 2, 2, 1, 14);
+        assert_eq!(
+            DIDModule::did_recovery(&did),
+            Some(DidRecoveryStatus {
+                config,
+                request: None
+            })
+        );
+
+        // The recovered key can update the DID
+        let add_keys = AddKeys {
+            did,
+            keys: vec![DidKey::new(
+                PublicKey::x25519(new_kp.public().0),
+                VerRelType::KEY_AGREEMENT,
+            )],
+            nonce: 14 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_keys, &new_kp, Controller(did), 2);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            add_keys,
+            vec![sig]
+        ));
+        check_did_detail(&did, 3, 2, 1, 15);
+
+        // Removing the DID removes its recovery config
+        let removal = DidRemoval { did, nonce: 15 + 1 };
+        let sig = did_sig::<Test, _, _>(&removal, &new_kp, Controller(did), 2);
+        assert_ok!(DIDModule::remove_onchain_did(
+            Origin::signed(alice),
+            removal,
+            vec![sig]
+        ));
+        assert!(DIDModule::did_recovery(&did).is_none());
+    });
+}
+
+#[test]
+fn service_endpoint_properties_and_updates() {
+    // Service endpoints with arbitrary types and properties can be updated in place
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did: Did = [96; Did::BYTE_SIZE].into();
+        let endpoint_id: WrappedBytes = b"#didcomm".to_vec().into();
+
+        run_to_block(10);
+
+        let kp = create_did(did);
+
+        // Endpoints stored with the bitflag types are migrated to the `LinkedDomains` type
+        let legacy_id: WrappedBytes = b"#linked-domain".to_vec().into();
+        let origins: Vec<WrappedBytes> = vec![b"https://example.com".to_vec().into()];
+        frame_support::storage::unhashed::put(
+            &DidServiceEndpoints::hashed_key_for(&did, &legacy_id),
+            &crate::migrations::did::service_endpoint_types::ServiceEndpointWithBitflagTypes {
+                types: 0b0001,
+                origins: origins.clone(),
+            },
+        );
+        crate::migrations::did::service_endpoint_types::migrate_to_typed_service_endpoints::<Test>(
+        );
+        assert_eq!(
+            DIDModule::did_service_endpoints(&did, &legacy_id),
+            Some(ServiceEndpoint {
+                types: vec![ServiceEndpointType::LinkedDomains]
+                    .into_iter()
+                    .collect(),
+                origins,
+                properties: vec![],
+            })
+        );
+
+        let endpoint = ServiceEndpoint {
+            types: vec![
+                ServiceEndpointType::DIDCommMessaging,
+                ServiceEndpointType::Custom(b"DIDCommMessagingV1".to_vec().into()),
+            ]
+            .into_iter()
+            .collect(),
+            origins: vec![b"https://example.com/path".to_vec().into()],
+            properties: vec![
+                ServiceEndpointProperty {
+                    name: b"routingKeys".to_vec().into(),
+                    values: vec![b"did:example:somemediator#somekey".to_vec().into()],
+                },
+                ServiceEndpointProperty {
+                    name: b"accept".to_vec().into(),
+                    values: vec![
+                        b"didcomm/v2".to_vec().into(),
+                        b"didcomm/aip2;env=rfc587".to_vec().into(),
+                    ],
+                },
+            ],
+        };
+
+        let add_service_endpoint = AddServiceEndpoint {
+            did,
+            id: endpoint_id.clone(),
+            endpoint: endpoint.clone(),
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_service_endpoint, &kp, Controller(did), 1);
+        assert_ok!(DIDModule::add_service_endpoint(
+            Origin::signed(alice),
+            add_service_endpoint,
+            vec![sig]
+        ));
+        assert_eq!(
+            DIDModule::did_service_endpoints(&did, &endpoint_id),
+            Some(endpoint.clone())
+        );
+
+        let updated_endpoint = ServiceEndpoint {
+            types: vec![ServiceEndpointType::DIDCommMessaging]
+                .into_iter()
+                .collect(),
+            origins: vec![b"https://example.com/other".to_vec().into()],
+            properties: vec![],
+        };
+
+        // Only existing endpoints can be updated
+        let update_service_endpoint = UpdateServiceEndpoint {
+            did,
+            id: b"#missing".to_vec().into(),
+            endpoint: updated_endpoint.clone(),
+            nonce: 11 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&update_service_endpoint, &kp, Controller(did), 1);
+        assert_noop!(
+            DIDModule::update_service_endpoint(
+                Origin::signed(alice),
+                update_service_endpoint,
+                vec![sig]
+            ),
+            Error::<Test>::ServiceEndpointDoesNotExist
+        );
+
+        let update_service_endpoint = UpdateServiceEndpoint {
+            did,
+            id: endpoint_id.clone(),
+            endpoint: ServiceEndpoint {
+                origins: vec![],
+                ..updated_endpoint.clone()
+            },
+            nonce: 11 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&update_service_endpoint, &kp, Controller(did), 1);
+        assert_noop!(
+            DIDModule::update_service_endpoint(
+                Origin::signed(alice),
+                update_service_endpoint,
+                vec![sig]
+            ),
+            Error::<Test>::InvalidServiceEndpoint
+        );
+
+        let update_service_endpoint = UpdateServiceEndpoint {
+            did,
+            id: endpoint_id.clone(),
+            endpoint: updated_endpoint.clone(),
+            nonce: 11 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&update_service_endpoint, &kp, Controller(did), 1);
+        assert_ok!(DIDModule::update_service_endpoint(
+            Origin::signed(alice),
+            update_service_endpoint,
+            vec![sig]
+        ));
+        assert_eq!(
+            DIDModule::did_service_endpoints(&did, &endpoint_id),
+            Some(updated_endpoint)
+        );
+        check_did_detail(&did, 1, 1, 1, 12);
+
+        // Updates can also be a part of the batch
+        let batch = DidBatchUpdate {
+            did,
+            operations: vec![DidUpdateOperation::UpdateServiceEndpoint {
+                id: endpoint_id.clone(),
+                endpoint: endpoint.clone(),
+            }],
+            nonce: 12 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&batch, &kp, Controller(did), 1);
+        assert_ok!(DIDModule::batch_update(
+            Origin::signed(alice),
+            batch,
+            vec![sig]
+        ));
+        assert_eq!(
+            DIDModule::did_service_endpoints(&did, &endpoint_id),
+            Some(endpoint)
+        );
+        check_did_detail(&did, 1, 1, 1, 13);
+    });
+}
+
+#[test]
+fn account_linking() {
+    // Accounts can be linked to on-chain DIDs and DIDs can be listed by account
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let bob = 2u64;
+        let did_1: Did = [97; Did::BYTE_SIZE].into();
+        let did_2: Did = [98; Did::BYTE_SIZE].into();
+        let did_3: Did = [99; Did::BYTE_SIZE].into();
+
+        run_to_block(10);
+
+        let kp_1 = create_did(did_1);
+        let kp_2 = create_did(did_2);
+        assert_ok!(DIDModule::new_offchain(
+            Origin::signed(bob),
+            did_3,
+            url_doc_ref("did.json")
+        ));
+        assert!(DIDModule::dids_by_account(&alice).is_empty());
+        assert_eq!(DIDModule::dids_by_account(&bob), vec![did_3]);
+
+        let link = LinkAccount {
+            did: did_1,
+            account: bob,
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&link, &kp_1, Controller(did_1), 1);
+
+        // The account co-signs the link by submitting it
+        assert_noop!(
+            DIDModule::link_account(Origin::signed(alice), link.clone(), vec![sig.clone()]),
+            Error::<Test>::NotSignedByAccount
+        );
+
+        let wrong_sig = did_sig::<Test, _, _>(&link, &kp_2, Controller(did_1), 1);
+        assert_noop!(
+            DIDModule::link_account(Origin::signed(bob), link.clone(), vec![wrong_sig]),
+            Error::<Test>::InvalidSignature
+        );
+
+        assert_ok!(DIDModule::link_account(
+            Origin::signed(bob),
+            link,
+            vec![sig]
+        ));
+        check_nonce(&did_1, 11);
+        assert_eq!(DIDModule::linked_accounts(&did_1), vec![bob]);
+
+        let link = LinkAccount {
+            did: did_1,
+            account: bob,
+            nonce: 11 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&link, &kp_1, Controller(did_1), 1);
+        assert_noop!(
+            DIDModule::link_account(Origin::signed(bob), link, vec![sig]),
+            Error::<Test>::AccountAlreadyLinked
+        );
+
+        for (did, kp) in vec![(did_2, &kp_2), (did_1, &kp_1)] {
+            let link = LinkAccount {
+                did,
+                account: alice,
+                nonce: DIDModule::onchain_did_details(&did).unwrap().next_nonce(),
+            };
+            let sig = did_sig::<Test, _, _>(&link, kp, Controller(did), 1);
+            assert_ok!(DIDModule::link_account(
+                Origin::signed(alice),
+                link,
+                vec![sig]
+            ));
+        }
+
+        let mut dids = DIDModule::dids_by_account(&bob);
+        dids.sort();
+        assert_eq!(dids, vec![did_1, did_3]);
+        let mut dids = DIDModule::dids_by_account(&alice);
+        dids.sort();
+        assert_eq!(dids, vec![did_1, did_2]);
+
+        let unlink = UnlinkAccount {
+            did: did_2,
+            account: bob,
+            nonce: 11 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&unlink, &kp_2, Controller(did_2), 1);
+        assert_noop!(
+            DIDModule::unlink_account(Origin::signed(alice), unlink, vec![sig]),
+            Error::<Test>::AccountNotLinked
+        );
+
+        let unlink = UnlinkAccount {
+            did: did_2,
+            account: alice,
+            nonce: 11 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&unlink, &kp_2, Controller(did_2), 1);
+        assert_ok!(DIDModule::unlink_account(
+            Origin::signed(bob),
+            unlink,
+            vec![sig]
+        ));
+        assert!(DIDModule::linked_accounts(&did_2).is_empty());
+        assert_eq!(DIDModule::dids_by_account(&alice), vec![did_1]);
+
+        // Removing DIDs removes them from the index
+        let removal = DidRemoval {
+            did: did_1,
+            nonce: 12 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&removal, &kp_1, Controller(did_1), 1);
+        assert_ok!(DIDModule::remove_onchain_did(
+            Origin::signed(alice),
+            removal,
+            vec![sig]
+        ));
+        assert!(DIDModule::linked_accounts(&did_1).is_empty());
+        assert!(DIDModule::dids_by_account(&alice).is_empty());
+        assert_eq!(DIDModule::dids_by_account(&bob), vec![did_3]);
+
+        assert_ok!(DIDModule::remove_offchain_did(Origin::signed(bob), did_3));
+        assert!(DIDModule::dids_by_account(&bob).is_empty());
+    });
+}
+
+#[test]
+fn public_key_index() {
+    // DIDs can be found by their public keys
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_1: Did = [101; Did::BYTE_SIZE].into();
+        let did_2: Did = [102; Did::BYTE_SIZE].into();
+
+        run_to_block(20);
+
+        let kp_1 = create_did(did_1);
+        let kp_2 = create_did(did_2);
+        let pk_1 = PublicKey::sr25519(kp_1.public().0);
+        let pk_2 = PublicKey::sr25519(kp_2.public().0);
+
+        assert_eq!(
+            DIDModule::dids_by_public_key(&pk_1),
+            vec![(did_1, 1u32.into())]
+        );
+        assert_eq!(
+            DIDModule::dids_by_public_key(&pk_2),
+            vec![(did_2, 1u32.into())]
+        );
+
+        // The same key added to another DID is indexed for both DIDs
+        let add_keys = AddKeys {
+            did: did_2,
+            keys: vec![DidKey::new(pk_1.clone(), VerRelType::ASSERTION)],
+            nonce: 20 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_keys, &kp_2, Controller(did_2), 1);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            add_keys,
+            vec![sig]
+        ));
+        let mut dids = DIDModule::dids_by_public_key(&pk_1);
+        dids.sort();
+        assert_eq!(dids, vec![(did_1, 1u32.into()), (did_2, 2u32.into())]);
+
+        // Existing keys are indexed by the migration
+        PublicKeyDids::remove(&pk_1, (did_2, IncId::from(2u32)));
+        assert_eq!(
+            DIDModule::dids_by_public_key(&pk_1),
+            vec![(did_1, 1u32.into())]
+        );
+        crate::migrations::did::public_key_index::index_public_keys::<Test>();
+        let mut dids = DIDModule::dids_by_public_key(&pk_1);
+        dids.sort();
+        assert_eq!(dids, vec![(did_1, 1u32.into()), (did_2, 2u32.into())]);
+
+        let remove_keys = RemoveKeys {
+            did: did_2,
+            keys: vec![2u32.into()].into_iter().collect(),
+            nonce: 21 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&remove_keys, &kp_2, Controller(did_2), 1);
+        assert_ok!(DIDModule::remove_keys(
+            Origin::signed(alice),
+            remove_keys,
+            vec![sig]
+        ));
+        assert_eq!(
+            DIDModule::dids_by_public_key(&pk_1),
+            vec![(did_1, 1u32.into())]
+        );
+
+        let removal = DidRemoval {
+            did: did_1,
+            nonce: 20 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&removal, &kp_1, Controller(did_1), 1);
+        assert_ok!(DIDModule::remove_onchain_did(
+            Origin::signed(alice),
+            removal,
+            vec![sig]
+        ));
+        assert!(DIDModule::dids_by_public_key(&pk_1).is_empty());
+        assert_eq!(
+            DIDModule::dids_by_public_key(&pk_2),
+            vec![(did_2, 1u32.into())]
+        );
+    });
+}
+
+#[test]
+fn did_history() {
+    // Versions of the on-chain DID Document can be resolved from the recorded history
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_1: Did = [111; Did::BYTE_SIZE].into();
+        let did_2: Did = [112; Did::BYTE_SIZE].into();
+
+        run_to_block(10);
+
+        let kp_1 = create_did(did_1);
+        let pk_1 = PublicKey::sr25519(kp_1.public().0);
+        assert_eq!(
+            DIDModule::did_version(&did_1),
+            Some(DidVersion {
+                version_id: 1,
+                oldest_version_id: 1,
+                created_at: 10,
+                updated_at: 10,
+                document_items: 2
+            })
+        );
+
+        run_to_block(12);
+
+        let pk_2 = PublicKey::sr25519(gen_kp().public().0);
+        let add_keys = AddKeys {
+            did: did_1,
+            keys: vec![DidKey::new(pk_2.clone(), VerRelType::ASSERTION)],
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_keys, &kp_1, Controller(did_1), 1);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            add_keys,
+            vec![sig]
+        ));
+
+        run_to_block(15);
+
+        // Updates which don't change the DID Document don't produce new versions
+        let link = LinkAccount {
+            did: did_1,
+            account: alice,
+            nonce: 11 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&link, &kp_1, Controller(did_1), 1);
+        assert_ok!(DIDModule::link_account(
+            Origin::signed(alice),
+            link,
+            vec![sig]
+        ));
+        assert_eq!(DIDModule::did_version(&did_1).unwrap().version_id, 2);
+
+        let remove_keys = RemoveKeys {
+            did: did_1,
+            keys: vec![2u32.into()].into_iter().collect(),
+            nonce: 12 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&remove_keys, &kp_1, Controller(did_1), 1);
+        assert_ok!(DIDModule::remove_keys(
+            Origin::signed(alice),
+            remove_keys,
+            vec![sig]
+        ));
+        assert_eq!(
+            DIDModule::did_version(&did_1),
+            Some(DidVersion {
+                version_id: 3,
+                oldest_version_id: 1,
+                created_at: 10,
+                updated_at: 15,
+                document_items: 2
+            })
+        );
+
+        let key_1 = DidKeyWithId {
+            id: 1u32.into(),
+            key: DidKey::new_with_all_relationships(pk_1),
+        };
+        let key_2 = DidKeyWithId {
+            id: 2u32.into(),
+            key: DidKey::new(pk_2, VerRelType::ASSERTION),
+        };
+        let document = |keys| OnChainDidDocument {
+            controllers: vec![Controller(did_1)],
+            keys,
+            service_endpoints: vec![],
+        };
+
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionId(1)),
+            Some(HistoricalDidResolution {
+                document: document(vec![key_1.clone()]),
+                version_id: 1,
+                created_at: 10,
+                updated_at: 10,
+                next_version: Some((2, 12)),
+                deactivated: false,
+            })
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionTime(11)),
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionId(1))
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionTime(14)),
+            Some(HistoricalDidResolution {
+                document: document(vec![key_1.clone(), key_2]),
+                version_id: 2,
+                created_at: 10,
+                updated_at: 12,
+                next_version: Some((3, 15)),
+                deactivated: false,
+            })
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionTime(100)),
+            Some(HistoricalDidResolution {
+                document: document(vec![key_1.clone()]),
+                version_id: 3,
+                created_at: 10,
+                updated_at: 15,
+                next_version: None,
+                deactivated: false,
+            })
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionTime(9)),
+            None
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionId(4)),
+            None
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_2, DidVersionQuery::VersionId(1)),
+            None
+        );
+
+        run_to_block(16);
+
+        // History is kept after the DID is removed
+        let removal = DidRemoval {
+            did: did_1,
+            nonce: 13 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&removal, &kp_1, Controller(did_1), 1);
+        assert_ok!(DIDModule::remove_onchain_did(
+            Origin::signed(alice),
+            removal,
+            vec![sig]
+        ));
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionId(1))
+                .unwrap()
+                .document,
+            document(vec![key_1.clone()])
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionTime(15)),
+            Some(HistoricalDidResolution {
+                document: document(vec![key_1]),
+                version_id: 3,
+                created_at: 10,
+                updated_at: 15,
+                next_version: Some((4, 16)),
+                deactivated: false,
+            })
+        );
+
+        // The removal is recorded as the last version with an empty DID Document
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_1, DidVersionQuery::VersionTime(16)),
+            Some(HistoricalDidResolution {
+                document: Default::default(),
+                version_id: 4,
+                created_at: 10,
+                updated_at: 16,
+                next_version: None,
+                deactivated: true,
+            })
+        );
+
+        // DIDs registered before the history was recorded get their first version on their first update
+        run_to_block(20);
+
+        let kp_2 = create_did(did_2);
+        DidVersions::<Test>::remove(did_2);
+        DidDocumentHistory::<Test>::remove(did_2, 1);
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_2, DidVersionQuery::VersionId(1)),
+            None
+        );
+
+        run_to_block(21);
+
+        crate::migrations::did::did_history::start_recording_history::<Test>();
+        assert_eq!(DIDModule::did_version(&did_2), None);
+        let initial_version = HistoricalDidResolution {
+            document: DIDModule::onchain_did_document(&did_2),
+            version_id: 1,
+            created_at: 21,
+            updated_at: 21,
+            next_version: None,
+            deactivated: false,
+        };
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_2, DidVersionQuery::VersionId(1)),
+            Some(initial_version.clone())
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_2, DidVersionQuery::VersionTime(30)),
+            Some(initial_version.clone())
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_2, DidVersionQuery::VersionTime(20)),
+            None
+        );
+
+        run_to_block(23);
+
+        let add_keys = AddKeys {
+            did: did_2,
+            keys: vec![DidKey::new(
+                PublicKey::sr25519(gen_kp().public().0),
+                VerRelType::ASSERTION,
+            )],
+            nonce: 20 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_keys, &kp_2, Controller(did_2), 1);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(alice),
+            add_keys,
+            vec![sig]
+        ));
+        assert_eq!(
+            DIDModule::did_version(&did_2),
+            Some(DidVersion {
+                version_id: 2,
+                oldest_version_id: 1,
+                created_at: 21,
+                updated_at: 23,
+                document_items: 3
+            })
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did_2, DidVersionQuery::VersionId(1)),
+            Some(HistoricalDidResolution {
+                next_version: Some((2, 23)),
+                ..initial_version
+            })
+        );
+        assert_eq!(DIDModule::did_version(&did_1).unwrap().version_id, 4);
+    });
+}
+
+#[test]
+fn did_history_max_items() {
+    // DID Documents having more than `MaxDidDocumentItems` items can't be recorded in the history
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did: Did = [111; Did::BYTE_SIZE].into();
+        let max_items = <Test as Config>::MaxDidDocumentItems::get();
+
+        run_to_block(5);
+
+        let kp = gen_kp();
+        let assertion_key = || {
+            DidKey::new(
+                PublicKey::sr25519(gen_kp().public().0),
+                VerRelType::ASSERTION,
+            )
+        };
+        let keys = |amount: u32| {
+            let mut keys = vec![DidKey::new_with_all_relationships(PublicKey::sr25519(
+                kp.public().0,
+            ))];
+            keys.extend((1..amount).map(|_| assertion_key()));
+
+            keys
+        };
+
+        // The DID itself is added as a controller, so `max_items` keys are too many
+        assert_noop!(
+            DIDModule::new_onchain(
+                Origin::signed(alice),
+                did,
+                keys(max_items),
+                Default::default()
+            ),
+            Error::<Test>::TooManyDidDocumentItems
+        );
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did,
+            keys(max_items - 1),
+            Default::default()
+        ));
+        assert_eq!(
+            DIDModule::did_version(&did).unwrap().document_items,
+            max_items
+        );
+
+        let add_keys = AddKeys {
+            did,
+            keys: vec![assertion_key()],
+            nonce: 5 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_keys, &kp, Controller(did), 1);
+        assert_noop!(
+            DIDModule::add_keys(Origin::signed(alice), add_keys, vec![sig]),
+            Error::<Test>::TooManyDidDocumentItems
+        );
+    });
+}
+
+#[test]
+fn did_history_pruning() {
+    // Replaced versions can be pruned once the retention period passes and their deposits are refunded
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let bob = 2u64;
+        let did: Did = [111; Did::BYTE_SIZE].into();
+        let retention_period = <Test as Config>::DidVersionRetentionPeriod::get();
+
+        assert_noop!(
+            DIDModule::prune_did_history(Origin::signed(bob), did, 1),
+            Error::<Test>::NoPrunableDidVersions
+        );
+
+        run_to_block(5);
+
+        let kp = gen_kp();
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did,
+            vec![DidKey::new_with_all_relationships(PublicKey::sr25519(
+                kp.public().0
+            ))],
+            Default::default()
+        ));
+
+        run_to_block(8);
+
+        let add_keys = AddKeys {
+            did,
+            keys: vec![DidKey::new(
+                PublicKey::sr25519(gen_kp().public().0),
+                VerRelType::ASSERTION,
+            )],
+            nonce: 5 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_keys, &kp, Controller(did), 1);
+        assert_ok!(DIDModule::add_keys(
+            Origin::signed(bob),
+            add_keys,
+            vec![sig]
+        ));
+
+        run_to_block(12);
+
+        let remove_keys = RemoveKeys {
+            did,
+            keys: vec![2u32.into()].into_iter().collect(),
+            nonce: 6 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&remove_keys, &kp, Controller(did), 1);
+        assert_ok!(DIDModule::remove_keys(
+            Origin::signed(bob),
+            remove_keys,
+            vec![sig]
+        ));
+
+        let deposit = |version_id| {
+            DIDModule::deposit(DepositItem::DidDocumentVersion(did, version_id))
+                .unwrap()
+                .amount
+        };
+        let (deposit_1, deposit_2) = (deposit(1), deposit(2));
+        let (alice_reserved, bob_reserved) = (
+            Balances::reserved_balance(alice),
+            Balances::reserved_balance(bob),
+        );
+
+        // The first version was replaced at block 8
+        run_to_block(8 + retention_period - 1);
+        assert_noop!(
+            DIDModule::prune_did_history(Origin::signed(bob), did, 5),
+            Error::<Test>::NoPrunableDidVersions
+        );
+
+        run_to_block(8 + retention_period);
+        assert_ok!(DIDModule::prune_did_history(Origin::signed(bob), did, 5));
+        assert_eq!(DIDModule::did_version(&did).unwrap().oldest_version_id, 2);
+        assert_eq!(DIDModule::did_document_version(did, 1), None);
+        assert_eq!(
+            DIDModule::deposit(DepositItem::DidDocumentVersion(did, 1)),
+            None
+        );
+        assert_eq!(
+            Balances::reserved_balance(alice),
+            alice_reserved - deposit_1
+        );
+        assert_eq!(
+            DIDModule::resolve_did_at(&did, DidVersionQuery::VersionId(1)),
+            None
+        );
+        assert!(DIDModule::resolve_did_at(&did, DidVersionQuery::VersionId(2)).is_some());
+
+        // The latest version is never pruned
+        run_to_block(100);
+        assert_ok!(DIDModule::prune_did_history(Origin::signed(alice), did, 5));
+        assert_eq!(DIDModule::did_version(&did).unwrap().oldest_version_id, 3);
+        assert_eq!(Balances::reserved_balance(bob), bob_reserved - deposit_2);
+        assert!(DIDModule::resolve_did_at(&did, DidVersionQuery::VersionId(3)).is_some());
+        assert_noop!(
+            DIDModule::prune_did_history(Origin::signed(alice), did, 5),
+            Error::<Test>::NoPrunableDidVersions
+        );
+    });
+}
+
+#[test]
+fn storage_deposits() {
+    // Deposits are reserved from the submitter of the item and refunded once the item is removed
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let bob = 2u64;
+        let poor = 100u64;
+        let did: Did = [111; Did::BYTE_SIZE].into();
+
+        let (pair_sr, _, _) = sr25519::Pair::generate_with_phrase(None);
+        let key = DidKey::new_with_all_relationships(PublicKey::sr25519(pair_sr.public().0));
+
+        run_to_block(5);
+
+        // Account without any balance can't afford the deposits
+        assert_noop!(
+            DIDModule::new_onchain(
+                Origin::signed(poor),
+                did,
+                vec![key.clone()],
+                vec![].into_iter().collect()
+            ),
+            Error::<Test>::InsufficientBalanceForDeposit
+        );
+
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did,
+            vec![key.clone()],
+            vec![].into_iter().collect()
+        ));
+        let did_deposit = DIDModule::deposit(DepositItem::Did(did)).unwrap();
+        let key_deposit = DIDModule::deposit(DepositItem::DidKey(did, 1u32.into())).unwrap();
+        assert_eq!(did_deposit.depositor, alice);
+        assert_eq!(key_deposit.depositor, alice);
+        assert_eq!(
+            key_deposit.amount,
+            DIDModule::deposit_for(key.encoded_size())
+        );
+        // The first version of the DID Document is paid by the DID's creator as well
+        let version_deposit = DIDModule::deposit(DepositItem::DidDocumentVersion(did, 1)).unwrap();
+        assert_eq!(version_deposit.depositor, alice);
+        assert_eq!(
+            version_deposit.amount,
+            DIDModule::deposit_for(
+                DIDModule::did_document_version(did, 1)
+                    .unwrap()
+                    .document
+                    .encoded_size()
+            )
+        );
+        assert_eq!(
+            Balances::reserved_balance(alice),
+            did_deposit.amount + key_deposit.amount + version_deposit.amount
+        );
+
+        // The service endpoint is paid by the account submitting the update rather than the DID's creator
+        let id: WrappedBytes = vec![1; 20].into();
+        let endpoint = ServiceEndpoint {
+            types: vec![ServiceEndpointType::LinkedDomains]
+                .into_iter()
+                .collect(),
+            origins: vec![vec![2; 50].into()],
+            properties: vec![],
+        };
+        let add_service_endpoint = AddServiceEndpoint {
+            did,
+            id: id.clone(),
+            endpoint: endpoint.clone(),
+            nonce: 5 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_service_endpoint, &pair_sr, Controller(did), 1);
+        assert_ok!(DIDModule::add_service_endpoint(
+            Origin::signed(bob),
+            add_service_endpoint,
+            vec![sig]
+        ));
+        let endpoint_deposit = DIDModule::deposit_for(id.encoded_size() + endpoint.encoded_size());
+        assert_eq!(
+            DIDModule::deposit(DepositItem::ServiceEndpoint(did, id.clone())),
+            Some(Deposit {
+                depositor: bob,
+                amount: endpoint_deposit
+            })
+        );
+        let version_2_deposit =
+            DIDModule::deposit(DepositItem::DidDocumentVersion(did, 2)).unwrap();
+        assert_eq!(version_2_deposit.depositor, bob);
+        assert_eq!(
+            Balances::reserved_balance(bob),
+            endpoint_deposit + version_2_deposit.amount
+        );
+        assert_eq!(
+            Balances::free_balance(bob),
+            INITIAL_BALANCE - endpoint_deposit - version_2_deposit.amount
+        );
+
+        // The update is discarded if its version can't be paid for
+        let remove_service_endpoint = RemoveServiceEndpoint {
+            did,
+            id: id.clone(),
+            nonce: 6 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&remove_service_endpoint, &pair_sr, Controller(did), 1);
+        assert_noop!(
+            DIDModule::remove_service_endpoint(
+                Origin::signed(poor),
+                remove_service_endpoint,
+                vec![sig]
+            ),
+            Error::<Test>::InsufficientBalanceForDeposit
+        );
+
+        // Removing the DID refunds all deposits to their depositors except the ones for the recorded history
+        let removal = DidRemoval { did, nonce: 6 + 1 };
+        let sig = did_sig::<Test, _, _>(&removal, &pair_sr, Controller(did), 1);
+        assert_ok!(DIDModule::remove_onchain_did(
+            Origin::signed(bob),
+            removal,
+            vec![sig]
+        ));
+        assert_eq!(Balances::reserved_balance(alice), version_deposit.amount);
+        assert_eq!(
+            Balances::free_balance(alice),
+            INITIAL_BALANCE - version_deposit.amount
+        );
+        assert_eq!(Balances::reserved_balance(bob), version_2_deposit.amount);
+        assert_eq!(
+            Balances::free_balance(bob),
+            INITIAL_BALANCE - version_2_deposit.amount
+        );
+        assert!(DIDModule::deposit(DepositItem::Did(did)).is_none());
+        assert!(DIDModule::deposit(DepositItem::DidKey(did, 1u32.into())).is_none());
+        assert!(DIDModule::deposit(DepositItem::ServiceEndpoint(did, id)).is_none());
+        assert!(DIDModule::deposit(DepositItem::DidDocumentVersion(did, 2)).is_some());
+        assert!(DIDModule::deposit(DepositItem::DidDocumentVersion(did, 3)).is_none());
+    });
+}
+
+#[test]
+fn paginated_queries() {
+    // Keys, controllers and service endpoints of a DID as well as all DIDs can be listed page by page
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did: Did = [121; Did::BYTE_SIZE].into();
+        let offchain_did: Did = [122; Did::BYTE_SIZE].into();
+
+        run_to_block(5);
+
+        let keys: Vec<_> = (0..5)
+            .map(|_| DidKey::new_with_all_relationships(PublicKey::sr25519(gen_kp().public().0)))
+            .collect();
+        let controllers: BTreeSet<_> = (0..4u8)
+            .map(|i| Controller([130 + i; Did::BYTE_SIZE].into()))
+            .collect();
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did,
+            keys.clone(),
+            controllers.clone()
+        ));
+        for i in 0..3u8 {
+            DidServiceEndpoints::insert(
+                did,
+                WrappedBytes(vec![i; 10]),
+                ServiceEndpoint {
+                    types: vec![ServiceEndpointType::LinkedDomains]
+                        .into_iter()
+                        .collect(),
+                    origins: vec![vec![i; 20].into()],
+                    properties: vec![],
+                },
+            );
+        }
+        assert_ok!(DIDModule::new_offchain(
+            Origin::signed(alice),
+            offchain_did,
+            cid_doc_ref(1)
+        ));
+
+        // Collects all pages requesting `limit` items per page
+        fn collect_pages<C: Clone, I>(
+            limit: u32,
+            page_fn: impl Fn(Option<C>, u32) -> Page<C, I>,
+        ) -> Vec<I> {
+            let mut items = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page = page_fn(cursor, limit);
+                assert!(page.items.len() <= limit as usize);
+                items.extend(page.items);
+
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => break items,
+                }
+            }
+        }
+
+        for limit in 1..7 {
+            let mut page_keys: Vec<_> = collect_pages(limit, |cursor, limit| {
+                DIDModule::did_keys_page(&did, cursor, limit)
+            })
+            .into_iter()
+            .map(|DidKeyWithId { id, key }| (u32::from(id), key))
+            .collect();
+            page_keys.sort_by_key(|(id, _)| *id);
+            assert_eq!(
+                page_keys,
+                (1..).zip(keys.iter().cloned()).collect::<Vec<_>>()
+            );
+
+            let page_controllers: BTreeSet<_> = collect_pages(limit, |cursor, limit| {
+                DIDModule::did_controllers_page(&did, cursor, limit)
+            })
+            .into_iter()
+            .collect();
+            assert_eq!(page_controllers.len(), controllers.len() + 1);
+            assert!(page_controllers.contains(&Controller(did)));
+            assert!(page_controllers.is_superset(&controllers));
+
+            let page_endpoints: Vec<_> = collect_pages(limit, |cursor, limit| {
+                DIDModule::did_service_endpoints_page(&did, cursor, limit)
+            });
+            assert_eq!(page_endpoints.len(), 3);
+            for ServiceEndpointWithId { id, endpoint } in page_endpoints {
+                assert_eq!(DIDModule::did_service_endpoints(did, id), Some(endpoint));
+            }
+
+            let all_dids: BTreeSet<_> = collect_pages(limit, |cursor, limit| {
+                DIDModule::dids_page(None, cursor, limit)
+            })
+            .into_iter()
+            .collect();
+            assert_eq!(all_dids, vec![did, offchain_did].into_iter().collect());
+            assert_eq!(
+                collect_pages(limit, |cursor, limit| DIDModule::dids_page(
+                    Some(DidType::OnChain),
+                    cursor,
+                    limit
+                )),
+                vec![did]
+            );
+            assert_eq!(
+                collect_pages(limit, |cursor, limit| DIDModule::dids_page(
+                    Some(DidType::OffChain),
+                    cursor,
+                    limit
+                )),
+                vec![offchain_did]
+            );
+        }
+
+        // Page size is limited
+        let page = DIDModule::did_keys_page(&did, None, 0);
+        assert_eq!(page.items.len(), 1);
+        assert!(page.next_cursor.is_some());
+        assert_eq!(
+            DIDModule::did_keys_page(&did, None, u32::MAX).items.len(),
+            5
+        );
+
+        // DID without any items has an empty page
+        let page = DIDModule::did_keys_page(&offchain_did, None, 10);
+        assert!(page.items.is_empty());
+        assert!(page.next_cursor.is_none());
+    });
+}
+
+#[test]
+fn controller_graph() {
+    // Resolving controller paths transitively and rejecting controller cycles
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_a: Did = [91; Did::BYTE_SIZE].into();
+        let did_b: Did = [92; Did::BYTE_SIZE].into();
+        let did_c: Did = [93; Did::BYTE_SIZE].into();
+        let did_d: Did = [94; Did::BYTE_SIZE].into();
+
+        run_to_block(10);
+
+        // `did_a` is controlled by `did_b` which is controlled by `did_c`
+        let kp_c = create_did(did_c);
+        let kp_d = create_did(did_d);
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_b,
+            vec![],
+            vec![Controller(did_c)].into_iter().collect()
+        ));
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_a,
+            vec![],
+            vec![Controller(did_b)].into_iter().collect()
+        ));
+
+        let c_key = DidKeyWithId {
+            id: 1u32.into(),
+            key: DidKeys::get(did_c, IncId::from(1u32)).unwrap(),
+        };
+        assert_eq!(
+            DIDModule::controller_paths(&did_a),
+            vec![ControllerPath {
+                dids: vec![did_a, did_b, did_c],
+                key: c_key.clone(),
+            }]
+        );
+        assert_eq!(
+            DIDModule::controller_paths(&did_c),
+            vec![ControllerPath {
+                dids: vec![did_c],
+                key: c_key,
+            }]
+        );
+        assert!(DIDModule::controller_paths(&[95; Did::BYTE_SIZE].into()).is_empty());
+
+        // `did_c` can't be controlled by `did_a` as `did_c` transitively controls `did_a`
+        let add_controllers = AddControllers {
+            did: did_c,
+            controllers: vec![did_d, did_a].into_iter().map(Controller).collect(),
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_controllers, &kp_c, Controller(did_c), 1);
+        assert_noop!(
+            DIDModule::add_controllers(Origin::signed(alice), add_controllers, vec![sig]),
+            Error::<Test>::ControllerCycle
+        );
+
+        // Controllers not forming a cycle can be added
+        let add_controllers = AddControllers {
+            did: did_c,
+            controllers: vec![did_d].into_iter().map(Controller).collect(),
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_controllers, &kp_c, Controller(did_c), 1);
+        assert_ok!(DIDModule::add_controllers(
+            Origin::signed(alice),
+            add_controllers,
+            vec![sig]
+        ));
+        assert_eq!(DIDModule::controller_paths(&did_a).len(), 2);
+
+        // `did_e` can't be registered with `did_d` as its controller after being added as a controller of `did_d`
+        let did_e: Did = [96; Did::BYTE_SIZE].into();
+        let add_controllers = AddControllers {
+            did: did_d,
+            controllers: vec![did_e].into_iter().map(Controller).collect(),
+            nonce: 10 + 1,
+        };
+        let sig = did_sig::<Test, _, _>(&add_controllers, &kp_d, Controller(did_d), 1);
+        assert_ok!(DIDModule::add_controllers(
+            Origin::signed(alice),
+            add_controllers,
+            vec![sig]
+        ));
+        assert_noop!(
+            DIDModule::new_onchain(
+                Origin::signed(alice),
+                did_e,
+                vec![],
+                vec![Controller(did_d)].into_iter().collect()
+            ),
+            Error::<Test>::ControllerCycle
+        );
+        // `did_e` controlling itself doesn't form a cycle with `did_d`
+        let kp_e = gen_kp();
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_e,
+            vec![DidKey::new_with_all_relationships(PublicKey::sr25519(
+                kp_e.public().0
+            ))],
+            Default::default()
+        ));
+
+        // Controller graph which can't be walked within `MaxControllerGraphReads` reads is rejected
+        let did_w: Did = [97; Did::BYTE_SIZE].into();
+        let max_reads = <Test as Config>::MaxControllerGraphReads::get();
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_w,
+            vec![],
+            (0..max_reads)
+                .map(|_| Controller(Did(rand::random())))
+                .collect()
+        ));
+        let add_controllers = AddControllers {
+            did: did_c,
+            controllers: vec![did_w].into_iter().map(Controller).collect(),
+            nonce: 10 + 2,
+        };
+        let sig = did_sig::<Test, _, _>(&add_controllers, &kp_c, Controller(did_c), 1);
+        assert_noop!(
+            DIDModule::add_controllers(Origin::signed(alice), add_controllers, vec![sig]),
+            Error::<Test>::ControllerGraphTooLarge
+        );
+
+        // Amount of the returned controller paths is limited
+        let did_x: Did = [98; Did::BYTE_SIZE].into();
+        let did_y: Did = [99; Did::BYTE_SIZE].into();
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_x,
+            (0..=MAX_CONTROLLER_PATHS)
+                .map(|_| DidKey::new_with_all_relationships(PublicKey::sr25519(rand::random())))
+                .collect(),
+            Default::default()
+        ));
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_y,
+            vec![],
+            vec![Controller(did_x)].into_iter().collect()
+        ));
+        assert_eq!(
+            DIDModule::controller_paths(&did_x).len(),
+            MAX_CONTROLLER_PATHS
+        );
+        let paths = DIDModule::controller_paths(&did_y);
+        assert_eq!(paths.len(), MAX_CONTROLLER_PATHS);
+        assert!(paths.iter().all(|path| path.dids == vec![did_y, did_x]));
+    });
+}
+
+#[test]
+fn did_resolution() {
+    // Resolving on-chain, off-chain and non-existent DIDs
+    ext().execute_with(|| {
+        let alice = 1u64;
+        let did_1: Did = [51; Did::BYTE_SIZE].into();
+        let did_2: Did = [52; Did::BYTE_SIZE].into();
+        let did_3: Did = [53; Did::BYTE_SIZE].into();
+
+        let (pair_sr, _, _) = sr25519::Pair::generate_with_phrase(None);
+        let pk_sr = pair_sr.public().0;
+        let (pair_ed, _, _) = ed25519::Pair::generate_with_phrase(None);
+        let pk_ed = pair_ed.public().0;
+
+        assert_eq!(DIDModule::resolve_did(&did_1), None);
+
+        run_to_block(5);
+
+        assert_ok!(DIDModule::new_onchain(
+            Origin::signed(alice),
+            did_1.clone(),
+            vec![
+                DidKey::new_with_all_relationships(PublicKey::sr25519(pk_sr)),
+                DidKey::new(PublicKey::x25519(pk_ed), VerRelType::KEY_AGREEMENT),
+            ],
+            vec![did_2].into_iter().map(Controller).collect()
+        ));
+
+        let endpoint_id: WrappedBytes = vec![102; 50].into();
+        let endpoint = ServiceEndpoint {
+            types: vec![ServiceEndpointType::LinkedDomains]
+                .into_iter()
+                .collect(),
+            origins: vec![vec![112; 100].into()],
+            properties: vec![],
+        };
+        let add_service_endpoint = AddServiceEndpoint {
+            did: did_1.clone(),
+            id: endpoint_id.clone(),
+            endpoint: endpoint.clone(),
+            nonce: 5 + 1,
+        };
+        let sig = SigValue::sr25519(&add_service_endpoint.to_state_change().encode(), &pair_sr);
+        assert_ok!(DIDModule::add_service_endpoint(
+            Origin::signed(alice),
+            add_service_endpoint,
+            vec![DidSignature {
+                did: Controller(did_1.clone()),
+                key_id: 1u32.into(),
+                sig
+            }]
+        ));
+
+        let mut controllers = vec![Controller(did_1), Controller(did_2)];
+        controllers.sort();
+        assert_eq!(
+            DIDModule::resolve_did(&did_1),
+            Some(DidResolution::OnChain {
+                document: OnChainDidDocument {
+                    controllers,
+                    keys: vec![
+                        DidKeyWithId {
+                            id: 1u32.into(),
+                            key: DidKey::new_with_all_relationships(PublicKey::sr25519(pk_sr)),
+                        },
+                        DidKeyWithId {
+                            id: 2u32.into(),
+                            key: DidKey::new(PublicKey::x25519(pk_ed), VerRelType::KEY_AGREEMENT),
+                        },
+                    ],
+                    service_endpoints: vec![ServiceEndpointWithId {
+                        id: endpoint_id,
+                        endpoint,
+                    }],
+                },
+                nonce: 6,
+            })
+        );
+
+        let doc_ref = cid_doc_ref(129);
+        assert_ok!(DIDModule::new_offchain(
+            Origin::signed(alice),
+            did_3.clone(),
+            doc_ref.clone()
+        ));
+        assert_eq!(
+            DIDModule::resolve_did(&did_3),
+            Some(DidResolution::OffChain(OffChainDidDetails {
+                account_id: alice,
+                doc_ref
+            }))
+        );
+    });
+}
+
+#[test]
+fn expired_replay_ids_removal() {
+    // Only a limited amount of expired replay ids is removed per block, the rest is removed in the following blocks
+    ext().execute_with(|| {
+        let did: Did = [140; Did::BYTE_SIZE].into();
+        let used = |ids: &[u8]| {
+            ids.iter()
+                .filter(|&&id| DIDModule::replay_id_expiry(did, [id; 32]).is_some())
+                .count()
+        };
+
+        run_to_block(5);
+
+        for id in 0..6 {
+            DIDModule::use_replay_id(did, [id; 32], 6);
+        }
+        DIDModule::use_replay_id(did, [10; 32], 8);
+
+        DIDModule::remove_expired_replay_ids(7);
+        assert_eq!(used(&[0, 1, 2, 3, 4, 5]), 2);
+        assert_eq!(DIDModule::replay_ids_cleanup_cursor(), Some(6));
+
+        // Expired replay id can be used again before it's removed and the removal doesn't affect the new use
+        run_to_block(7);
+        let stale = (0..6).find(|&id| used(&[id]) == 1).unwrap();
+        let reused = WithDeadline::new((), [stale; 32], 9);
+        assert_ok!(DIDModule::ensure_replay_id_can_be_used(&did, &reused));
+        DIDModule::use_replay_id(did, [stale; 32], 9);
+
+        DIDModule::remove_expired_replay_ids(8);
+        assert_eq!(used(&[0, 1, 2, 3, 4, 5]), 1);
+        assert_eq!(DIDModule::replay_id_expiry(did, [stale; 32]), Some(9));
+        assert_eq!(DIDModule::replay_ids_cleanup_cursor(), None);
+
+        // Replay ids which haven't expired yet are kept
+        assert_eq!(DIDModule::replay_id_expiry(did, [10; 32]), Some(8));
+        DIDModule::remove_expired_replay_ids(9);
+        assert!(DIDModule::replay_id_expiry(did, [10; 32]).is_none());
+
+        DIDModule::remove_expired_replay_ids(10);
+        assert_eq!(used(&[0, 1, 2, 3, 4, 5]), 0);
+        assert_eq!(DIDModule::replay_ids_cleanup_cursor(), None);
+    });
+}
+
 // TODO: Add test for events DidAdded, KeyUpdated, DIDRemoval
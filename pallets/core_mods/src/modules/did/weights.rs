@@ -31,24 +31,31 @@ pub trait WeightInfo {
     fn add_keys_sr25519(k: u32) -> Weight;
     fn add_keys_ed25519(k: u32) -> Weight;
     fn add_keys_secp256k1(k: u32) -> Weight;
+    fn add_keys_secp256r1(k: u32) -> Weight;
     fn remove_keys_sr25519(k: u32) -> Weight;
     fn remove_keys_ed25519(k: u32) -> Weight;
     fn remove_keys_secp256k1(k: u32) -> Weight;
+    fn remove_keys_secp256r1(k: u32) -> Weight;
     fn add_controllers_sr25519(k: u32) -> Weight;
     fn add_controllers_ed25519(k: u32) -> Weight;
     fn add_controllers_secp256k1(k: u32) -> Weight;
+    fn add_controllers_secp256r1(k: u32) -> Weight;
     fn remove_controllers_sr25519(k: u32) -> Weight;
     fn remove_controllers_ed25519(k: u32) -> Weight;
     fn remove_controllers_secp256k1(k: u32) -> Weight;
+    fn remove_controllers_secp256r1(k: u32) -> Weight;
     fn add_service_endpoint_sr25519(o: u32, l: u32, i: u32) -> Weight;
     fn add_service_endpoint_ed25519(o: u32, l: u32, i: u32) -> Weight;
     fn add_service_endpoint_secp256k1(o: u32, l: u32, i: u32) -> Weight;
+    fn add_service_endpoint_secp256r1(o: u32, l: u32, i: u32) -> Weight;
     fn remove_service_endpoint_sr25519(i: u32) -> Weight;
     fn remove_service_endpoint_ed25519(i: u32) -> Weight;
     fn remove_service_endpoint_secp256k1(i: u32) -> Weight;
+    fn remove_service_endpoint_secp256r1(i: u32) -> Weight;
     fn remove_onchain_did_sr25519() -> Weight;
     fn remove_onchain_did_ed25519() -> Weight;
     fn remove_onchain_did_secp256k1() -> Weight;
+    fn remove_onchain_did_secp256r1() -> Weight;
     fn new_onchain(k: u32, c: u32) -> Weight;
     fn new_offchain(k: u32) -> Weight;
     fn set_offchain_did_doc_ref(k: u32) -> Weight;
@@ -82,6 +89,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
     }
+    fn add_keys_secp256r1(k: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (162_329_000 as Weight)
+            .saturating_add((1_296_000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+    }
     fn remove_keys_sr25519(k: u32) -> Weight {
         (62_781_000 as Weight)
             // Standard Error: 26_000
@@ -109,6 +124,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(4 as Weight))
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
     }
+    fn remove_keys_secp256r1(k: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (165_718_000 as Weight)
+            .saturating_add((2_734_000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(k as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+    }
     fn add_controllers_sr25519(k: u32) -> Weight {
         (60_892_000 as Weight)
             // Standard Error: 34_000
@@ -136,6 +160,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(2 as Weight))
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
     }
+    fn add_controllers_secp256r1(k: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (168_684_000 as Weight)
+            .saturating_add((2_770_000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(k as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+    }
     fn remove_controllers_sr25519(k: u32) -> Weight {
         (60_478_000 as Weight)
             // Standard Error: 119_000
@@ -163,6 +196,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
     }
+    fn remove_controllers_secp256r1(k: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (165_267_000 as Weight)
+            .saturating_add((3_198_000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(k as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+    }
     fn add_service_endpoint_sr25519(o: u32, l: u32, i: u32) -> Weight {
         (62_630_000 as Weight)
             // Standard Error: 15_000
@@ -196,6 +238,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(5 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn add_service_endpoint_secp256r1(o: u32, l: u32, i: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (152_391_000 as Weight)
+            .saturating_add((719_000 as Weight).saturating_mul(o as Weight))
+            .saturating_add((810_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add((80_000 as Weight).saturating_mul(i as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn remove_service_endpoint_sr25519(i: u32) -> Weight {
         (62_886_000 as Weight)
             // Standard Error: 1_000
@@ -217,6 +268,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(5 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
     }
+    fn remove_service_endpoint_secp256r1(i: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (166_356_000 as Weight)
+            .saturating_add((12_000 as Weight).saturating_mul(i as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
     fn remove_onchain_did_sr25519() -> Weight {
         (85_904_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
@@ -232,6 +290,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(34 as Weight))
     }
+    fn remove_onchain_did_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (193_139_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(34 as Weight))
+    }
     fn new_onchain(k: u32, c: u32) -> Weight {
         (10_672_000 as Weight)
             // Standard Error: 4_000
@@ -290,6 +354,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
             .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
     }
+    fn add_keys_secp256r1(k: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (162_329_000 as Weight)
+            .saturating_add((1_296_000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+    }
     fn remove_keys_sr25519(k: u32) -> Weight {
         (62_781_000 as Weight)
             // Standard Error: 26_000
@@ -317,6 +389,15 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
     }
+    fn remove_keys_secp256r1(k: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (165_718_000 as Weight)
+            .saturating_add((2_734_000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(k as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+    }
     fn add_controllers_sr25519(k: u32) -> Weight {
         (60_892_000 as Weight)
             // Standard Error: 34_000
@@ -344,6 +425,15 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(2 as Weight))
             .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
     }
+    fn add_controllers_secp256r1(k: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (168_684_000 as Weight)
+            .saturating_add((2_770_000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(k as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+    }
     fn remove_controllers_sr25519(k: u32) -> Weight {
         (60_478_000 as Weight)
             // Standard Error: 119_000
@@ -371,6 +461,15 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
     }
+    fn remove_controllers_secp256r1(k: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (165_267_000 as Weight)
+            .saturating_add((3_198_000 as Weight).saturating_mul(k as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(k as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(k as Weight)))
+    }
     fn add_service_endpoint_sr25519(o: u32, l: u32, i: u32) -> Weight {
         (62_630_000 as Weight)
             // Standard Error: 15_000
@@ -404,6 +503,15 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(5 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn add_service_endpoint_secp256r1(o: u32, l: u32, i: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (152_391_000 as Weight)
+            .saturating_add((719_000 as Weight).saturating_mul(o as Weight))
+            .saturating_add((810_000 as Weight).saturating_mul(l as Weight))
+            .saturating_add((80_000 as Weight).saturating_mul(i as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn remove_service_endpoint_sr25519(i: u32) -> Weight {
         (62_886_000 as Weight)
             // Standard Error: 1_000
@@ -425,6 +533,13 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(5 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
     }
+    fn remove_service_endpoint_secp256r1(i: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (166_356_000 as Weight)
+            .saturating_add((12_000 as Weight).saturating_mul(i as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
     fn remove_onchain_did_sr25519() -> Weight {
         (85_904_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
@@ -440,6 +555,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(34 as Weight))
     }
+    fn remove_onchain_did_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (193_139_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(34 as Weight))
+    }
     fn new_onchain(k: u32, c: u32) -> Weight {
         (10_672_000 as Weight)
             // Standard Error: 4_000
@@ -7,6 +7,16 @@ pub struct AddRegistry {
     pub registry: Registry,
 }
 
+/// Command to create a registry which tracks the status of credentials in a bitstring.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AddStatusListRegistry {
+    pub id: RegistryId,
+    pub registry: Registry,
+    /// Amount of entries in the status list
+    pub size: u32,
+}
+
 /// Command to create a set of revocations withing a registry.
 /// Creation of revocations is idempotent; creating a revocation that already exists is allowed,
 /// but has no effect.
@@ -37,6 +47,39 @@ pub struct UnRevokeRaw<T> {
     pub _marker: PhantomData<T>,
 }
 
+/// Command to set the status of a set of credentials within a registry along with an optional reason code.
+/// Revoked credentials stay revoked; revoking them again has no effect while suspending them isn't allowed.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetRevocationStatusRaw<T> {
+    /// The registry on which to operate
+    pub registry_id: RegistryId,
+    /// Credential ids which will get the status
+    pub revoke_ids: BTreeSet<RevokeId>,
+    /// New status of the credentials
+    pub status: CredentialStatus,
+    /// Optional code of the reason why the status is set
+    pub reason: Option<ReasonCode>,
+    #[codec(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _marker: PhantomData<T>,
+}
+
+/// Command to lift the suspension of a set of credentials within a registry.
+/// Lifting is idempotent; lifting the suspension of a credential which isn't suspended is allowed,
+/// but has no effect. Revoked credentials aren't allowed.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiftSuspensionRaw<T> {
+    /// The registry on which to operate
+    pub registry_id: RegistryId,
+    /// Credential ids which will get their suspension lifted
+    pub revoke_ids: BTreeSet<RevokeId>,
+    #[codec(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _marker: PhantomData<T>,
+}
+
 /// Command to remove an entire registry. Removes all revocations in the registry as well as
 /// registry metadata.
 #[derive(PartialEq, Eq, Encode, Decode, Clone, Debug)]
@@ -49,11 +92,48 @@ pub struct RemoveRegistryRaw<T> {
     pub _marker: PhantomData<T>,
 }
 
+/// Command to replace the authorization policy of a registry and its `add_only` flag.
+/// An `add_only` registry can't be made mutable again.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateRegistryPolicyRaw<T> {
+    /// The registry on which to operate
+    pub registry_id: RegistryId,
+    /// New authorization policy of the registry
+    pub policy: Policy,
+    /// New value of the registry's `add_only` flag
+    pub add_only: bool,
+    #[codec(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _marker: PhantomData<T>,
+}
+
+/// Command to set or clear entries of a registry's status list.
+/// Updates are idempotent; setting an entry which is already set is allowed, but has no effect.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateStatusListRaw<T> {
+    /// The registry on which to operate
+    pub registry_id: RegistryId,
+    /// Ranges of the entries which will be updated
+    pub ranges: Vec<StatusListRange>,
+    /// true: entries will be set, i.e. credentials revoked
+    /// false: entries will be cleared, i.e. credentials un-revoked
+    pub revoked: bool,
+    #[codec(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub _marker: PhantomData<T>,
+}
+
 crate::impl_action! {
     for RegistryId:
         RevokeRaw with revoke_ids.len() as len, registry_id as target no_state_change,
         UnRevokeRaw with revoke_ids.len() as len, registry_id as target no_state_change,
-        RemoveRegistryRaw with 1 as len, registry_id as target no_state_change
+        RemoveRegistryRaw with 1 as len, registry_id as target no_state_change,
+        UpdateRegistryPolicyRaw with 1 as len, registry_id as target no_state_change,
+        UpdateStatusListRaw with ranges.len() as len, registry_id as target no_state_change,
+        SetRevocationStatusRaw with revoke_ids.len() as len, registry_id as target no_state_change,
+        LiftSuspensionRaw with revoke_ids.len() as len, registry_id as target no_state_change
 }
 
 /// Command to create a set of revocations withing a registry.
@@ -67,10 +147,27 @@ pub type UnRevoke<T> = WithNonce<T, UnRevokeRaw<T>>;
 /// Command to remove an entire registry. Removes all revocations in the registry as well as
 /// registry metadata.
 pub type RemoveRegistry<T> = WithNonce<T, RemoveRegistryRaw<T>>;
+/// Command to replace the authorization policy of a registry and its `add_only` flag.
+/// An `add_only` registry can't be made mutable again.
+pub type UpdateRegistryPolicy<T> = WithNonce<T, UpdateRegistryPolicyRaw<T>>;
+/// Command to set or clear entries of a registry's status list.
+/// Updates are idempotent; setting an entry which is already set is allowed, but has no effect.
+pub type UpdateStatusList<T> = WithNonce<T, UpdateStatusListRaw<T>>;
+/// Command to set the status of a set of credentials within a registry along with an optional reason code.
+/// Revoked credentials stay revoked; revoking them again has no effect while suspending them isn't allowed.
+pub type SetRevocationStatus<T> = WithNonce<T, SetRevocationStatusRaw<T>>;
+/// Command to lift the suspension of a set of credentials within a registry.
+/// Lifting is idempotent; lifting the suspension of a credential which isn't suspended is allowed,
+/// but has no effect. Revoked credentials aren't allowed.
+pub type LiftSuspension<T> = WithNonce<T, LiftSuspensionRaw<T>>;
 
 crate::impl_action_with_nonce! {
     for RegistryId:
         UnRevoke with data().len() as len, data().registry_id as target,
         Revoke with data().len() as len, data().registry_id as target,
-        RemoveRegistry with data().len() as len, data().registry_id as target
+        RemoveRegistry with data().len() as len, data().registry_id as target,
+        UpdateRegistryPolicy with data().len() as len, data().registry_id as target,
+        UpdateStatusList with data().len() as len, data().registry_id as target,
+        SetRevocationStatus with data().len() as len, data().registry_id as target,
+        LiftSuspension with data().len() as len, data().registry_id as target
 }
@@ -1,5 +1,8 @@
 use super::*;
-use crate::did::{Did, DidKey};
+use crate::{
+    did::{Did, DidKey},
+    keys_and_sigs::PublicKey,
+};
 use frame_benchmarking::{benchmarks, whitelisted_caller};
 use sp_core::U256;
 use sp_std::{iter::once, prelude::*};
@@ -15,16 +18,18 @@ pub fn oneof(dids: &[Did]) -> Policy {
 
 crate::bench_with_all_pairs! {
     with_pairs:
-    revoke_sr25519 for sr25519, revoke_ed25519 for ed25519, revoke_secp256k1 for secp256k1 {
+    revoke_sr25519 for sr25519, revoke_ed25519 for ed25519, revoke_secp256k1 for secp256k1, revoke_secp256r1 for secp256r1 {
         {
             let r in 1 .. MAX_REVOCATIONS as u32;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -52,16 +57,18 @@ crate::bench_with_all_pairs! {
             .all(|id| Revocations::contains_key(reg_id, id)));
     }
 
-    unrevoke_sr25519 for sr25519, unrevoke_ed25519 for ed25519, unrevoke_secp256k1 for secp256k1 {
+    unrevoke_sr25519 for sr25519, unrevoke_ed25519 for ed25519, unrevoke_secp256k1 for secp256k1, unrevoke_secp256r1 for secp256r1 {
         {
             let r in 1 .. MAX_REVOCATIONS as u32;
         }
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let did = Did([1; Did::BYTE_SIZE]);
         let public = pair.public();
 
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -102,9 +109,10 @@ crate::bench_with_all_pairs! {
             .all(|id| !Revocations::contains_key(reg_id, id)));
     }
 
-    remove_registry_sr25519 for sr25519, remove_registry_ed25519 for ed25519, remove_registry_secp256k1 for secp256k1 {
+    remove_registry_sr25519 for sr25519, remove_registry_ed25519 for ed25519, remove_registry_secp256k1 for secp256k1, remove_registry_secp256r1 for secp256r1 {
         let pair as Pair;
         let caller = whitelisted_caller();
+        crate::did::Module::<T>::fund_for_deposits(&caller);
         let public = pair.public();
         let did = Did([3 as u8; Did::BYTE_SIZE]);
         let reg_id = [4 as u8; 32];
@@ -118,6 +126,7 @@ crate::bench_with_all_pairs! {
         };
         let revoke_ids: BTreeSet<_> = (0..100).map(|i| U256::from(i).into()).collect();
         crate::did::Module::<T>::new_onchain_(
+            caller.clone(),
             did,
             vec![DidKey::new_with_all_relationships(public)],
             Default::default(),
@@ -168,4 +177,22 @@ crate::bench_with_all_pairs! {
     verify {
         assert_eq!(Registries::get(reg_id).unwrap(), reg);
     }
+
+    verify_secp256r1_sig {
+        let pair = crate::def_pair!(secp256r1, &[5; 32]);
+        let public_key = PublicKey::from(pair.public());
+        let msg = [1u8; 32];
+        let sig = pair.sign(&msg);
+    }: {
+        assert!(sig.verify(&msg, &public_key).unwrap());
+    }
+
+    verify_webauthn_sig {
+        let pair = crate::def_pair!(secp256r1, &[5; 32]);
+        let public_key = PublicKey::from(pair.public());
+        let msg = [1u8; 32];
+        let sig = crate::keys_and_sigs::sign_with_webauthn_max_size(&msg, &pair.sk);
+    }: {
+        assert!(sig.verify(&msg, &public_key).unwrap());
+    }
 }
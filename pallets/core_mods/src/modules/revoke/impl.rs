@@ -17,6 +17,22 @@ impl<T: Config + Debug> Module<T> {
         Ok(())
     }
 
+    pub(super) fn new_status_list_registry_(
+        AddStatusListRegistry { id, registry, size }: AddStatusListRegistry,
+    ) -> DispatchResult {
+        // check
+        ensure!(
+            StatusList::is_valid_size(size, T::MaxStatusListSize::get()),
+            RevErr::<T>::InvalidStatusListSize
+        );
+
+        // execute
+        Self::new_registry_(AddRegistry { id, registry })?;
+        StatusLists::insert(&id, StatusList::new(size));
+
+        Ok(())
+    }
+
     pub(super) fn revoke_(
         RevokeRaw {
             registry_id,
@@ -25,10 +41,13 @@ impl<T: Config + Debug> Module<T> {
         }: RevokeRaw<T>,
         _: &mut Registry,
     ) -> DispatchResult {
+        ensure!(
+            !StatusLists::contains_key(&registry_id),
+            RevErr::<T>::StatusListRegistry
+        );
+
         // execute
-        for cred_id in &revoke_ids {
-            Revocations::insert(&registry_id, cred_id, ());
-        }
+        Self::set_status(&registry_id, &revoke_ids, CredentialStatus::Revoked, None);
 
         crate::deposit_indexed_event!(RevokedInRegistry(registry_id));
         Ok(())
@@ -43,16 +62,71 @@ impl<T: Config + Debug> Module<T> {
         registry: &mut Registry,
     ) -> DispatchResult {
         ensure!(!registry.add_only, RevErr::<T>::AddOnly);
+        ensure!(
+            !StatusLists::contains_key(&registry_id),
+            RevErr::<T>::StatusListRegistry
+        );
 
         // execute
         for cred_id in &revoke_ids {
             Revocations::remove(&registry_id, cred_id);
+            RevocationStatuses::<T>::remove(&registry_id, cred_id);
         }
 
         crate::deposit_indexed_event!(UnrevokedInRegistry(registry_id));
         Ok(())
     }
 
+    pub(super) fn set_revocation_status_(
+        SetRevocationStatusRaw {
+            registry_id,
+            revoke_ids,
+            status,
+            reason,
+            ..
+        }: SetRevocationStatusRaw<T>,
+        _: &mut Registry,
+    ) -> DispatchResult {
+        // check
+        ensure!(
+            !StatusLists::contains_key(&registry_id),
+            RevErr::<T>::StatusListRegistry
+        );
+        if status == CredentialStatus::Suspended {
+            Self::ensure_not_revoked(&registry_id, &revoke_ids)?;
+        }
+
+        // execute
+        Self::set_status(&registry_id, &revoke_ids, status, reason);
+
+        crate::deposit_indexed_event!(RevocationStatusSetInRegistry(registry_id, status, reason) over registry_id);
+        Ok(())
+    }
+
+    pub(super) fn lift_suspension_(
+        LiftSuspensionRaw {
+            registry_id,
+            revoke_ids,
+            ..
+        }: LiftSuspensionRaw<T>,
+        _: &mut Registry,
+    ) -> DispatchResult {
+        // check
+        ensure!(
+            !StatusLists::contains_key(&registry_id),
+            RevErr::<T>::StatusListRegistry
+        );
+        Self::ensure_not_revoked(&registry_id, &revoke_ids)?;
+
+        // execute
+        for cred_id in &revoke_ids {
+            RevocationStatuses::<T>::remove(&registry_id, cred_id);
+        }
+
+        crate::deposit_indexed_event!(SuspensionLiftedInRegistry(registry_id));
+        Ok(())
+    }
+
     pub(super) fn remove_registry_(
         RemoveRegistryRaw { registry_id, .. }: RemoveRegistryRaw<T>,
         registry: &mut Option<Registry>,
@@ -62,11 +136,120 @@ impl<T: Config + Debug> Module<T> {
 
         // execute
         Revocations::remove_prefix(&registry_id);
+        RevocationStatuses::<T>::remove_prefix(&registry_id);
+        StatusLists::remove(&registry_id);
 
         crate::deposit_indexed_event!(RegistryRemoved(registry_id));
         Ok(())
     }
 
+    pub(super) fn update_registry_policy_(
+        UpdateRegistryPolicyRaw {
+            registry_id,
+            policy,
+            add_only,
+            ..
+        }: UpdateRegistryPolicyRaw<T>,
+        registry: &mut Registry,
+    ) -> DispatchResult {
+        // check
+        ensure!(policy.valid(), RevErr::<T>::InvalidPolicy);
+        ensure!(
+            T::MaxControllers::get() >= policy.len(),
+            RevErr::<T>::TooManyControllers
+        );
+        ensure!(!registry.add_only || add_only, RevErr::<T>::AddOnly);
+
+        // execute
+        registry.policy = policy;
+        registry.add_only = add_only;
+
+        crate::deposit_indexed_event!(RegistryPolicyUpdated(registry_id));
+        Ok(())
+    }
+
+    pub(super) fn update_status_list_(
+        UpdateStatusListRaw {
+            registry_id,
+            ranges,
+            revoked,
+            ..
+        }: UpdateStatusListRaw<T>,
+        registry: &mut Registry,
+    ) -> DispatchResult {
+        // check
+        ensure!(revoked || !registry.add_only, RevErr::<T>::AddOnly);
+        let mut status_list = StatusLists::get(&registry_id).ok_or(RevErr::<T>::NotStatusList)?;
+        status_list
+            .update(&ranges, revoked)
+            .map_err(RevErr::<T>::from)?;
+
+        // execute
+        StatusLists::insert(&registry_id, status_list);
+
+        crate::deposit_indexed_event!(StatusListUpdated(registry_id));
+        Ok(())
+    }
+
+    /// Returns the status of the credential. Credentials revoked before the statuses were tracked are reported
+    /// as revoked without a reason at block zero.
+    pub fn revocation_status(
+        registry_id: RegistryId,
+        revoke_id: RevokeId,
+    ) -> Option<RevocationStatus<T>> {
+        RevocationStatuses::<T>::get(registry_id, revoke_id).or_else(|| {
+            Revocations::get(registry_id, revoke_id).map(|()| RevocationStatus {
+                status: CredentialStatus::Revoked,
+                reason: None,
+                changed_at: Zero::zero(),
+            })
+        })
+    }
+
+    /// Sets the status of the credentials at the current block. Credentials which are already revoked aren't updated.
+    fn set_status(
+        registry_id: &RegistryId,
+        revoke_ids: &BTreeSet<RevokeId>,
+        status: CredentialStatus,
+        reason: Option<ReasonCode>,
+    ) {
+        let changed_at = <system::Module<T>>::block_number();
+
+        for cred_id in revoke_ids {
+            if Revocations::contains_key(registry_id, cred_id) {
+                continue;
+            }
+
+            if status == CredentialStatus::Revoked {
+                Revocations::insert(registry_id, cred_id, ());
+            }
+            RevocationStatuses::<T>::insert(
+                registry_id,
+                cred_id,
+                RevocationStatus {
+                    status,
+                    reason,
+                    changed_at,
+                },
+            );
+        }
+    }
+
+    /// Ensures that none of the credentials is revoked.
+    fn ensure_not_revoked(
+        registry_id: &RegistryId,
+        revoke_ids: &BTreeSet<RevokeId>,
+    ) -> Result<(), RevErr<T>> {
+        for cred_id in revoke_ids {
+            ensure!(
+                !Revocations::contains_key(registry_id, cred_id),
+                RevErr::<T>::CredentialRevoked
+            );
+        }
+
+        Ok(())
+    }
+
     /// Executes action over target registry providing a mutable reference if all checks succeed.
     ///
     /// Checks:
@@ -119,14 +302,12 @@ impl<T: Config + Debug> Module<T> {
         Registries::try_mutate_exists(action.target(), |registry_opt| {
             let registry = registry_opt.take().ok_or(RevErr::<T>::NoReg)?;
             // check the signer set satisfies policy
-            match &registry.policy {
-                Policy::OneOf(controllers) => {
-                    ensure!(
-                        proof.len() == 1 && proof.iter().all(|a| controllers.contains(&a.sig.did)),
-                        RevErr::<T>::NotAuthorized
-                    );
-                }
-            }
+            let signers: BTreeSet<_> = proof.iter().map(|DidSigs { sig, .. }| sig.did).collect();
+            ensure!(signers.len() == proof.len(), RevErr::<T>::DuplicateSigner);
+            ensure!(
+                registry.policy.satisfied_by(&signers),
+                RevErr::<T>::NotAuthorized
+            );
 
             let mut new_did_details = Vec::with_capacity(proof.len());
             // check each signature is valid over payload and signed by the claimed signer
@@ -163,4 +344,83 @@ impl<T: Config + Debug> Module<T> {
             Ok(res)
         })
     }
+
+    /// Same as `try_exec_action_over_registry` except that the action is protected from replays by its replay id
+    /// and expiry block instead of the nonces of the signers.
+    pub(crate) fn try_exec_action_over_registry_with_deadline<A, F, R, E>(
+        action: WithDeadline<T, A>,
+        proof: Vec<DidSignature<Did>>,
+        f: F,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, &mut Registry) -> Result<R, E>,
+        A: Action<T, Target = RegistryId>,
+        WithNonce<T, A>: ToStateChange<T>,
+        E: From<RevErr<T>> + From<did::Error<T>>,
+    {
+        Self::try_exec_removable_action_over_registry_with_deadline(action, proof, |action, reg| {
+            f(action, reg.as_mut().unwrap())
+        })
+    }
+
+    /// Same as `try_exec_removable_action_over_registry` except that the action is protected from replays by its
+    /// replay id and expiry block instead of the nonces of the signers.
+    ///
+    /// Checks:
+    /// 1. Ensure that the registry exists, the action hasn't expired and its replay id wasn't used by any of the signers.
+    /// 2. Verify that `proof` authorizes `action` according to `policy`. Each signature is made over the action
+    /// wrapped into `WithNonce` with zero nonce and then into `WithDeadline`.
+    ///
+    /// Once the action succeeds, its replay id is marked as used by each of the signers until the action expires.
+    pub(crate) fn try_exec_removable_action_over_registry_with_deadline<A, F, R, E>(
+        action: WithDeadline<T, A>,
+        proof: Vec<DidSignature<Did>>,
+        f: F,
+    ) -> Result<R, E>
+    where
+        F: FnOnce(A, &mut Option<Registry>) -> Result<R, E>,
+        A: Action<T, Target = RegistryId>,
+        WithNonce<T, A>: ToStateChange<T>,
+        E: From<RevErr<T>> + From<did::Error<T>>,
+    {
+        ensure!(!action.is_empty(), RevErr::<T>::EmptyPayload);
+
+        Registries::try_mutate_exists(action.target(), |registry_opt| {
+            let registry = registry_opt.take().ok_or(RevErr::<T>::NoReg)?;
+            // check the signer set satisfies policy
+            let signers: BTreeSet<_> = proof.iter().map(|sig| sig.did).collect();
+            ensure!(signers.len() == proof.len(), RevErr::<T>::DuplicateSigner);
+            ensure!(
+                registry.policy.satisfied_by(&signers),
+                RevErr::<T>::NotAuthorized
+            );
+
+            let action_with_nonce =
+                action.map(|action| WithNonce::new_with_nonce(action, Zero::zero()));
+            // check each signature is valid over payload and signed by the claimed signer
+            for sig in &proof {
+                did::Module::<T>::ensure_replay_id_can_be_used(&sig.did, &action_with_nonce)?;
+                let valid =
+                    did::Module::<T>::verify_sig_from_auth_or_control_key(&action_with_nonce, sig)?;
+
+                ensure!(valid, RevErr::<T>::NotAuthorized);
+            }
+
+            let WithDeadline {
+                action,
+                replay_id,
+                expires_at,
+            } = action_with_nonce;
+            let mut data_opt = Some(registry);
+            let res = f(action.into_data(), &mut data_opt)?;
+            *registry_opt = data_opt;
+
+            // The replay id must be marked as used by each DID
+            for signer in signers {
+                did::Module::<T>::use_replay_id(signer, replay_id, expires_at);
+            }
+
+            Ok(res)
+        })
+    }
 }
@@ -1,8 +1,8 @@
 use crate as dock;
 use crate::{
     did::{self, Did, DidSignature},
-    keys_and_sigs::{SigValue, ED25519_WEIGHT, SECP256K1_WEIGHT, SR25519_WEIGHT},
-    util::{NonceError, WithNonce},
+    keys_and_sigs::{SigValue, ED25519_WEIGHT, SECP256K1_WEIGHT, SECP256R1_WEIGHT, SR25519_WEIGHT},
+    util::{NonceError, WithDeadline, WithNonce},
     Action, StorageVersion, ToStateChange,
 };
 use alloc::collections::BTreeSet;
@@ -19,13 +19,15 @@ use frame_support::{
     weights::{RuntimeDbWeight, Weight},
 };
 use frame_system::{self as system, ensure_signed};
-use sp_runtime::traits::Hash;
+use sp_runtime::traits::{Hash, Zero};
+pub use status_list::*;
 use weights::*;
 
 mod actions;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarks;
 mod r#impl;
+mod status_list;
 #[cfg(test)]
 pub mod tests;
 mod weights;
@@ -47,11 +49,16 @@ pub struct DidSigs<T: frame_system::Config> {
 }
 
 /// Authorization logic for a registry.
+/// New variants must be added at the end so that the policies of the existing registries keep decoding.
 #[derive(PartialEq, Eq, Encode, Decode, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Policy {
-    /// Set of dids allowed to modify a registry.
+    /// Set of dids allowed to modify a registry. A single one of them must sign.
     OneOf(BTreeSet<Did>),
+    /// Set of dids all of which must sign to modify a registry.
+    AllOf(BTreeSet<Did>),
+    /// Set of dids at least `threshold` of which must sign to modify a registry.
+    KOfN { dids: BTreeSet<Did>, threshold: u32 },
 }
 
 impl Default for Policy {
@@ -64,12 +71,34 @@ impl Policy {
     /// Check for user error in the construction of self.
     /// if self is invalid, return `false`, else return `true`.
     fn valid(&self) -> bool {
-        self.len() != 0
+        match self {
+            Self::OneOf(_) | Self::AllOf(_) => self.len() != 0,
+            Self::KOfN { threshold, .. } => *threshold != 0 && *threshold <= self.len(),
+        }
     }
 
     fn len(&self) -> u32 {
+        self.controllers().len() as u32
+    }
+
+    /// Returns the set of dids which can sign on behalf of the registry.
+    fn controllers(&self) -> &BTreeSet<Did> {
         match self {
-            Self::OneOf(controllers) => controllers.len() as u32,
+            Self::OneOf(controllers) | Self::AllOf(controllers) => controllers,
+            Self::KOfN { dids, .. } => dids,
+        }
+    }
+
+    /// Returns `true` if the given set of distinct signers is allowed to modify a registry having this policy.
+    fn satisfied_by(&self, signers: &BTreeSet<Did>) -> bool {
+        if !signers.is_subset(self.controllers()) {
+            return false;
+        }
+
+        match self {
+            Self::OneOf(_) => signers.len() == 1,
+            Self::AllOf(controllers) => signers.len() == controllers.len(),
+            Self::KOfN { threshold, .. } => signers.len() as u32 >= *threshold,
         }
     }
 }
@@ -85,21 +114,53 @@ pub struct Registry {
     pub add_only: bool,
 }
 
-/// Return counts of different signature types in given `DidSigs` as 3-Tuple as (no. of Sr22519 sigs,
-/// no. of Ed25519 Sigs, no. of Secp256k1 sigs). Useful for weight calculation and thus the return
+/// Code of the reason why the status of a credential was changed. The meaning of codes is defined by the issuer.
+pub type ReasonCode = u16;
+
+/// Status of a credential within a registry.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CredentialStatus {
+    /// The credential is permanently revoked.
+    Revoked,
+    /// The credential is temporarily suspended. Suspension can be lifted even in an `add_only` registry.
+    Suspended,
+}
+
+/// Status of a credential along with the reason and the block of its last change.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Sized", deserialize = "T: Sized"))
+)]
+pub struct RevocationStatus<T: frame_system::Config> {
+    pub status: CredentialStatus,
+    pub reason: Option<ReasonCode>,
+    /// Block at which the status was set. Zero for the revocations made before the statuses were tracked.
+    pub changed_at: T::BlockNumber,
+}
+
+/// Return counts of different signature types in given `DidSigs` as 4-Tuple as (no. of Sr22519 sigs,
+/// no. of Ed25519 Sigs, no. of Secp256k1 sigs including EIP-191 ones, no. of Secp256r1 sigs including WebAuthn assertions). Useful for weight calculation and thus the return
 /// type is in `Weight` but realistically, it should fit in a u8
-fn count_sig_types<T: frame_system::Config>(auth: &[DidSigs<T>]) -> (Weight, Weight, Weight) {
+fn count_sig_types<T: frame_system::Config>(
+    auth: &[DidSigs<T>],
+) -> (Weight, Weight, Weight, Weight) {
     let mut sr = 0;
     let mut ed = 0;
     let mut secp = 0;
+    let mut secp_r = 0;
     for a in auth.iter() {
         match a.sig.sig {
             SigValue::Sr25519(_) => sr += 1,
             SigValue::Ed25519(_) => ed += 1,
-            SigValue::Secp256k1(_) => secp += 1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => secp += 1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => secp_r += 1,
         }
     }
-    (sr, ed, secp)
+    (sr, ed, secp, secp_r)
 }
 
 /// Computes weight of the given `DidSigs`. Considers the no. and types of signatures and no. of reads. Disregards
@@ -109,16 +170,28 @@ pub fn get_weight_for_did_sigs<T: frame_system::Config>(
     auth: &[DidSigs<T>],
     db_weights: RuntimeDbWeight,
 ) -> Weight {
-    let (sr, ed, secp) = count_sig_types(auth);
+    let (sr, ed, secp, secp_r) = count_sig_types(auth);
     (db_weights.reads(auth.len() as u64)
         + (sr * SR25519_WEIGHT)
         + (ed * ED25519_WEIGHT)
-        + (secp * SECP256K1_WEIGHT)) as Weight
+        + (secp * SECP256K1_WEIGHT)
+        + (secp_r * SECP256R1_WEIGHT)) as Weight
+}
+
+// Approximate weight of decompressing, updating and compressing a single byte of a status list. This is not
+// based on any benchmarks and meant to overestimate.
+const STATUS_LIST_BYTE_WEIGHT: Weight = 20_000;
+
+/// Weight of processing a status list of the given size, capped by `MaxStatusListSize`.
+fn status_list_weight<T: Config>(size: u32) -> Weight {
+    STATUS_LIST_BYTE_WEIGHT.saturating_mul((size.min(T::MaxStatusListSize::get()) / 8) as Weight)
 }
 
 pub trait Config: system::Config + did::Config {
     type Event: From<Event> + Into<<Self as system::Config>::Event>;
     type MaxControllers: Get<u32>;
+    /// Maximum amount of entries in a status list
+    type MaxStatusListSize: Get<u32>;
 }
 
 decl_event!(
@@ -131,6 +204,14 @@ decl_event!(
         UnrevokedInRegistry(RegistryId),
         /// Registry with given id removed
         RegistryRemoved(RegistryId),
+        /// Policy of the registry with given id updated
+        RegistryPolicyUpdated(RegistryId),
+        /// Status list of the registry with given id updated
+        StatusListUpdated(RegistryId),
+        /// Status of some items was set with an optional reason code in given registry id
+        RevocationStatusSetInRegistry(RegistryId, CredentialStatus, Option<ReasonCode>),
+        /// Suspension of some items was lifted in given registry id
+        SuspensionLiftedInRegistry(RegistryId),
     }
 );
 
@@ -153,7 +234,21 @@ decl_error! {
         /// the registry is not allowed.
         AddOnly,
         /// Action is empty.
-        EmptyPayload
+        EmptyPayload,
+        /// More than one signature by the same DID was provided.
+        DuplicateSigner,
+        /// Status list size is below the minimum, above `MaxStatusListSize` or not a whole number of bytes.
+        InvalidStatusListSize,
+        /// Status list range is empty or exceeds the size of the list.
+        InvalidStatusListRange,
+        /// Stored status list can't be decompressed.
+        MalformedStatusList,
+        /// The registry doesn't have a status list.
+        NotStatusList,
+        /// Individual revocations can't be added to or removed from a registry with a status list.
+        StatusListRegistry,
+        /// The credential is permanently revoked so it can't be suspended or have its suspension lifted.
+        CredentialRevoked
     }
 }
 
@@ -175,6 +270,16 @@ decl_storage! {
         Revocations get(fn get_revocation_status):
             double_map hasher(blake2_128_concat) dock::revoke::RegistryId, hasher(opaque_blake2_256) dock::revoke::RevokeId => Option<()>;
 
+        /// Statuses of the credentials along with the reasons and blocks of their changes. Revoked credentials are
+        /// also kept in `Revocations` while suspended ones are stored only here. Revocations made before the statuses
+        /// were tracked don't have a status.
+        RevocationStatuses:
+            double_map hasher(blake2_128_concat) dock::revoke::RegistryId, hasher(opaque_blake2_256) dock::revoke::RevokeId => Option<RevocationStatus<T>>;
+
+        /// Compressed bitstrings of the status list registries
+        StatusLists get(fn get_status_list):
+            map hasher(blake2_128_concat) dock::revoke::RegistryId => Option<StatusList>;
+
         pub Version get(fn version): StorageVersion;
     }
 }
@@ -211,8 +316,9 @@ decl_module! {
         /// registry referenced by `revoke.registry_id` was last modified.
         ///
         /// Returns an error if `proof` does not satisfy the policy requirements of the registry
-        /// referenced by `revoke.registry_id`.
-        #[weight = SubstrateWeight::<T>::revoke(&proof[0])(revoke.len())]
+        /// referenced by `revoke.registry_id` or contains more than one signature by the same DID.
+        #[weight = SubstrateWeight::<T>::with_did_sigs(&proof, |sig| SubstrateWeight::<T>::revoke(sig)(revoke.len()))
+            .saturating_add(T::DbWeight::get().reads_writes(revoke.len() as u64, revoke.len() as u64))]
         pub fn revoke(
             origin,
             revoke: dock::revoke::RevokeRaw<T>,
@@ -234,8 +340,9 @@ decl_module! {
         /// registry referenced by `revoke.registry_id` was last modified.
         ///
         /// Returns an error if `proof` does not satisfy the policy requirements of the registry
-        /// referenced by `unrevoke.registry_id`.
-        #[weight = SubstrateWeight::<T>::unrevoke(&proof[0])(unrevoke.len())]
+        /// referenced by `unrevoke.registry_id` or contains more than one signature by the same DID.
+        #[weight = SubstrateWeight::<T>::with_did_sigs(&proof, |sig| SubstrateWeight::<T>::unrevoke(sig)(unrevoke.len()))
+            .saturating_add(T::DbWeight::get().writes(unrevoke.len() as u64))]
         pub fn unrevoke(
             origin,
             unrevoke: dock::revoke::UnRevokeRaw<T>,
@@ -259,8 +366,8 @@ decl_module! {
         /// registry referenced by `removal.registry_id` was last modified.
         ///
         /// Returns an error if `proof` does not satisfy the policy requirements of the registry
-        /// referenced by `removal.registry_id`.
-        #[weight = SubstrateWeight::<T>::remove_registry(&proof[0])]
+        /// referenced by `removal.registry_id` or contains more than one signature by the same DID.
+        #[weight = SubstrateWeight::<T>::with_did_sigs(&proof, SubstrateWeight::<T>::remove_registry)]
         pub fn remove_registry(
             origin,
             removal: dock::revoke::RemoveRegistryRaw<T>,
@@ -272,6 +379,173 @@ decl_module! {
             Ok(())
         }
 
+        /// Same as `revoke` but protected from replays by the supplied replay id and expiry block instead of
+        /// the nonces of the signers.
+        #[weight = SubstrateWeight::<T>::with_deadline_sigs(&proof, |sig| SubstrateWeight::<T>::revoke(sig)(revoke.action.len()))
+            .saturating_add(T::DbWeight::get().reads_writes(revoke.action.len() as u64, revoke.action.len() as u64))
+            .saturating_add(WithDeadline::<T, RevokeRaw<T>>::replay_protection_weight().saturating_mul(proof.len() as Weight))]
+        pub fn revoke_with_deadline(
+            origin,
+            revoke: WithDeadline<T, dock::revoke::RevokeRaw<T>>,
+            proof: Vec<DidSignature<Did>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_action_over_registry_with_deadline(revoke, proof, Self::revoke_)?;
+            Ok(())
+        }
+
+        /// Same as `unrevoke` but protected from replays by the supplied replay id and expiry block instead of
+        /// the nonces of the signers.
+        #[weight = SubstrateWeight::<T>::with_deadline_sigs(&proof, |sig| SubstrateWeight::<T>::unrevoke(sig)(unrevoke.action.len()))
+            .saturating_add(T::DbWeight::get().writes(unrevoke.action.len() as u64))
+            .saturating_add(WithDeadline::<T, UnRevokeRaw<T>>::replay_protection_weight().saturating_mul(proof.len() as Weight))]
+        pub fn unrevoke_with_deadline(
+            origin,
+            unrevoke: WithDeadline<T, dock::revoke::UnRevokeRaw<T>>,
+            proof: Vec<DidSignature<Did>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_action_over_registry_with_deadline(unrevoke, proof, Self::unrevoke_)?;
+            Ok(())
+        }
+
+        /// Same as `remove_registry` but protected from replays by the supplied replay id and expiry block
+        /// instead of the nonces of the signers.
+        #[weight = SubstrateWeight::<T>::with_deadline_sigs(&proof, SubstrateWeight::<T>::remove_registry)
+            .saturating_add(WithDeadline::<T, RemoveRegistryRaw<T>>::replay_protection_weight().saturating_mul(proof.len() as Weight))]
+        pub fn remove_registry_with_deadline(
+            origin,
+            removal: WithDeadline<T, dock::revoke::RemoveRegistryRaw<T>>,
+            proof: Vec<DidSignature<Did>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_removable_action_over_registry_with_deadline(removal, proof, Self::remove_registry_)?;
+            Ok(())
+        }
+
+        /// Replace the authorization policy and the `add_only` flag of a registry according to the `update` command.
+        /// The update must be authorized by the current policy of the registry.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if `update.policy` is invalid or has too many controllers.
+        ///
+        /// Returns an error if the registry referenced by `update.registry_id` is `add_only` and `update.add_only`
+        /// is `false`.
+        ///
+        /// Returns an error if `proof` does not satisfy the current policy requirements of the registry
+        /// referenced by `update.registry_id` or contains more than one signature by the same DID.
+        #[weight = T::DbWeight::get().reads_writes(1, 1 + proof.len() as u64)
+            .saturating_add(get_weight_for_did_sigs(&proof, T::DbWeight::get()))]
+        pub fn update_registry_policy(
+            origin,
+            update: dock::revoke::UpdateRegistryPolicyRaw<T>,
+            proof: Vec<DidSigs<T>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_action_over_registry(update, proof, Self::update_registry_policy_)?;
+            Ok(())
+        }
+
+        /// Create a new revocation registry named `id` with `registry` metadata which tracks the status of
+        /// `size` credentials in a bitstring instead of individual revocations.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if `id` is already in use as a registry id.
+        ///
+        /// Returns an error if `registry.policy` is invalid or `size` isn't allowed.
+        #[weight = SubstrateWeight::<T>::new_registry(add_status_list.registry.policy.len())
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(status_list_weight::<T>(add_status_list.size))]
+        pub fn new_status_list_registry(
+            origin,
+            add_status_list: AddStatusListRegistry
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::new_status_list_registry_(add_status_list)?;
+            Ok(())
+        }
+
+        /// Set or clear the status list entries within the ranges of the `update` command.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the registry referenced by `update.registry_id` doesn't have a status list.
+        ///
+        /// Returns an error if the registry is `add_only` and `update.revoked` is `false`.
+        ///
+        /// Returns an error if any of `update.ranges` is empty or exceeds the size of the list.
+        ///
+        /// Returns an error if `proof` does not satisfy the policy requirements of the registry
+        /// referenced by `update.registry_id` or contains more than one signature by the same DID.
+        #[weight = T::DbWeight::get().reads_writes(2, 1 + proof.len() as u64)
+            .saturating_add(get_weight_for_did_sigs(&proof, T::DbWeight::get()))
+            .saturating_add(status_list_weight::<T>(T::MaxStatusListSize::get()).saturating_mul(1 + update.ranges.len() as Weight))]
+        pub fn update_status_list(
+            origin,
+            update: dock::revoke::UpdateStatusListRaw<T>,
+            proof: Vec<DidSigs<T>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_action_over_registry(update, proof, Self::update_status_list_)?;
+            Ok(())
+        }
+
+        /// Set the status of some credentials according to the `set_status` command. Permanently revoked credentials
+        /// stay revoked, the rest get the new status along with the reason code.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the registry referenced by `set_status.registry_id` has a status list.
+        ///
+        /// Returns an error if `set_status.status` is `Suspended` and some of the credentials are revoked.
+        ///
+        /// Returns an error if `proof` does not satisfy the policy requirements of the registry
+        /// referenced by `set_status.registry_id` or contains more than one signature by the same DID.
+        #[weight = SubstrateWeight::<T>::with_did_sigs(&proof, |sig| SubstrateWeight::<T>::revoke(sig)(set_status.len()))
+            .saturating_add(T::DbWeight::get().reads_writes(set_status.len() as u64, set_status.len() as u64))]
+        pub fn set_revocation_status(
+            origin,
+            set_status: dock::revoke::SetRevocationStatusRaw<T>,
+            proof: Vec<DidSigs<T>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_action_over_registry(set_status, proof, Self::set_revocation_status_)?;
+            Ok(())
+        }
+
+        /// Lift the suspension of some credentials according to the `lift` command. Unlike `unrevoke`, this
+        /// is allowed in the `add_only` registries.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the registry referenced by `lift.registry_id` has a status list.
+        ///
+        /// Returns an error if some of the credentials are revoked.
+        ///
+        /// Returns an error if `proof` does not satisfy the policy requirements of the registry
+        /// referenced by `lift.registry_id` or contains more than one signature by the same DID.
+        #[weight = SubstrateWeight::<T>::with_did_sigs(&proof, |sig| SubstrateWeight::<T>::unrevoke(sig)(lift.len()))
+            .saturating_add(T::DbWeight::get().reads(lift.len() as u64))]
+        pub fn lift_suspension(
+            origin,
+            lift: dock::revoke::LiftSuspensionRaw<T>,
+            proof: Vec<DidSigs<T>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::try_exec_action_over_registry(lift, proof, Self::lift_suspension_)?;
+            Ok(())
+        }
+
         fn on_runtime_upgrade() -> Weight {
             T::DbWeight::get().reads(1) + if Self::version() == StorageVersion::SingleKey {
                 let weight = crate::migrations::revoke::single_key::migrate_to_multi_key::<T>();
@@ -286,27 +560,67 @@ decl_module! {
 }
 
 impl<T: frame_system::Config> SubstrateWeight<T> {
-    fn revoke(DidSigs { sig, .. }: &DidSigs<T>) -> fn(u32) -> Weight {
+    /// Weight of the action authorized by the given `DidSigs`: the benchmarked weight of the action signed
+    /// by the first signer plus the weight of verifying the rest of signatures and updating the signers' nonces.
+    fn with_did_sigs<F>(proof: &[DidSigs<T>], weight_for_sig: F) -> Weight
+    where
+        F: FnOnce(&DidSignature<Did>) -> Weight,
+    {
+        let db_weights = T::DbWeight::get();
+
+        match proof.split_first() {
+            Some((first, rest)) => weight_for_sig(&first.sig)
+                .saturating_add(get_weight_for_did_sigs(rest, db_weights))
+                .saturating_add(db_weights.writes(rest.len() as u64)),
+            None => db_weights.reads(1),
+        }
+    }
+
+    /// Weight of the action authorized by the given signatures and protected from replays by a replay id: the
+    /// benchmarked weight of the action signed by the first signer plus the weight of verifying the rest of signatures.
+    /// Unlike `with_did_sigs`, no nonces are updated.
+    fn with_deadline_sigs<F>(proof: &[DidSignature<Did>], weight_for_sig: F) -> Weight
+    where
+        F: FnOnce(&DidSignature<Did>) -> Weight,
+    {
+        let db_weights = T::DbWeight::get();
+
+        match proof.split_first() {
+            Some((first, rest)) => rest.iter().fold(weight_for_sig(first), |weight, sig| {
+                weight
+                    .saturating_add(sig.weight())
+                    .saturating_add(db_weights.reads(1))
+            }),
+            None => db_weights.reads(1),
+        }
+    }
+
+    fn revoke(sig: &DidSignature<Did>) -> fn(u32) -> Weight {
         match sig.sig {
             SigValue::Sr25519(_) => Self::revoke_sr25519,
             SigValue::Ed25519(_) => Self::revoke_ed25519,
-            SigValue::Secp256k1(_) => Self::revoke_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::revoke_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::revoke_secp256r1,
         }
     }
 
-    fn unrevoke(DidSigs { sig, .. }: &DidSigs<T>) -> fn(u32) -> Weight {
+    fn unrevoke(sig: &DidSignature<Did>) -> fn(u32) -> Weight {
         match sig.sig {
             SigValue::Sr25519(_) => Self::unrevoke_sr25519,
             SigValue::Ed25519(_) => Self::unrevoke_ed25519,
-            SigValue::Secp256k1(_) => Self::unrevoke_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => Self::unrevoke_secp256k1,
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::unrevoke_secp256r1,
         }
     }
 
-    fn remove_registry(DidSigs { sig, .. }: &DidSigs<T>) -> Weight {
+    fn remove_registry(sig: &DidSignature<Did>) -> Weight {
         (match sig.sig {
             SigValue::Sr25519(_) => Self::remove_registry_sr25519,
             SigValue::Ed25519(_) => Self::remove_registry_ed25519,
-            SigValue::Secp256k1(_) => Self::remove_registry_secp256k1,
+            SigValue::Secp256k1(_) | SigValue::Secp256k1Eip191(_) => {
+                Self::remove_registry_secp256k1
+            }
+            SigValue::Secp256r1(_) | SigValue::WebAuthn(_) => Self::remove_registry_secp256r1,
         })()
     }
 }
@@ -0,0 +1,163 @@
+use super::*;
+use crate::util::WrappedBytes;
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec};
+
+/// Minimum amount of entries in a status list. StatusList2021 requires the bitstring to be at least 16KB
+/// long so that the holders are provided with group privacy.
+pub const MIN_STATUS_LIST_SIZE: u32 = 16 * 1024 * 8;
+/// Level used to compress the bitstring.
+const COMPRESSION_LEVEL: u8 = 6;
+/// GZIP member header: magic bytes, the DEFLATE method, no flags, no modification time, no extra flags
+/// and an unknown OS.
+const GZIP_HEADER: [u8; 10] = [0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff];
+/// Size of the GZIP member trailer holding CRC-32 and the size of the uncompressed data.
+const GZIP_TRAILER_SIZE: usize = 8;
+
+/// Half-open range `[start, end)` of the status list indices.
+#[derive(Encode, Decode, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatusListRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Bitstring status list compatible with StatusList2021. The entry with index `i` is the `i % 8`th most
+/// significant bit of the `i / 8`th byte and is set if the corresponding credential is revoked.
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct StatusList {
+    /// Amount of entries in the list.
+    pub size: u32,
+    /// GZIP-compressed bitstring.
+    pub encoded_list: WrappedBytes,
+}
+
+/// Reason why the status list can't be updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusListError {
+    /// The range is empty or exceeds the size of the list
+    InvalidRange,
+    /// The stored bitstring can't be decompressed
+    MalformedEncoding,
+}
+
+impl<T: Config + Debug> From<StatusListError> for RevErr<T> {
+    fn from(err: StatusListError) -> Self {
+        match err {
+            StatusListError::InvalidRange => Self::InvalidStatusListRange,
+            StatusListError::MalformedEncoding => Self::MalformedStatusList,
+        }
+    }
+}
+
+impl StatusList {
+    /// Creates a status list of the given size with no entries set.
+    pub fn new(size: u32) -> Self {
+        Self {
+            size,
+            encoded_list: WrappedBytes(gzip(&sp_std::vec![0; size as usize / 8])),
+        }
+    }
+
+    /// Returns `true` if the given amount of entries can be stored in a status list
+    /// not bigger than `max_size`. The size must be a whole number of bytes.
+    pub fn is_valid_size(size: u32, max_size: u32) -> bool {
+        size >= MIN_STATUS_LIST_SIZE && size <= max_size && size % 8 == 0
+    }
+
+    /// Returns the uncompressed bitstring or `None` if the stored list is malformed.
+    pub fn bits(&self) -> Option<Vec<u8>> {
+        gunzip(&self.encoded_list.0).filter(|bits| bits.len() * 8 == self.size as usize)
+    }
+
+    /// Returns `Some(true)` if the entry with the given index is set or `None` if the index is out of bounds
+    /// or the stored list is malformed.
+    pub fn is_set(&self, index: u32) -> Option<bool> {
+        if index >= self.size {
+            return None;
+        }
+
+        self.bits()
+            .map(|bits| bits[index as usize / 8] & bit_mask(index) != 0)
+    }
+
+    /// Sets the entries within the given ranges if `set` is `true`, otherwise clears them.
+    pub fn update(&mut self, ranges: &[StatusListRange], set: bool) -> Result<(), StatusListError> {
+        ensure!(
+            ranges
+                .iter()
+                .all(|&StatusListRange { start, end }| start < end && end <= self.size),
+            StatusListError::InvalidRange
+        );
+        let mut bits = self.bits().ok_or(StatusListError::MalformedEncoding)?;
+
+        for &StatusListRange { start, end } in ranges {
+            for index in start..end {
+                let byte = &mut bits[index as usize / 8];
+                if set {
+                    *byte |= bit_mask(index);
+                } else {
+                    *byte &= !bit_mask(index);
+                }
+            }
+        }
+        self.encoded_list = WrappedBytes(gzip(&bits));
+
+        Ok(())
+    }
+}
+
+/// Returns the mask of the entry with the given index within its byte.
+fn bit_mask(index: u32) -> u8 {
+    0x80 >> (index % 8)
+}
+
+/// Compresses the bytes into a GZIP member as per https://datatracker.ietf.org/doc/html/rfc1952.
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let deflated = compress_to_vec(bytes, COMPRESSION_LEVEL);
+
+    let mut member = Vec::with_capacity(GZIP_HEADER.len() + deflated.len() + GZIP_TRAILER_SIZE);
+    member.extend_from_slice(&GZIP_HEADER);
+    member.extend_from_slice(&deflated);
+    member.extend_from_slice(&crc32(bytes).to_le_bytes());
+    member.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+
+    member
+}
+
+/// Decompresses the GZIP member produced by `gzip`. Returns `None` if the member is malformed or its
+/// checksum doesn't match.
+fn gunzip(member: &[u8]) -> Option<Vec<u8>> {
+    let body = member.strip_prefix(&GZIP_HEADER[..])?;
+    let deflated_size = body.len().checked_sub(GZIP_TRAILER_SIZE)?;
+    let (deflated, trailer) = body.split_at(deflated_size);
+
+    let bytes = decompress_to_vec(deflated).ok()?;
+    let (crc, size) = trailer.split_at(4);
+    if crc != crc32(&bytes).to_le_bytes() || size != (bytes.len() as u32).to_le_bytes() {
+        return None;
+    }
+
+    Some(bytes)
+}
+
+/// Computes CRC-32 checksum of the bytes as required by the GZIP trailer.
+fn crc32(bytes: &[u8]) -> u32 {
+    // Reversed polynomial of CRC-32
+    const POLYNOMIAL: u32 = 0xedb8_8320;
+
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
@@ -1,5 +1,9 @@
 use super::*;
-use crate::{test_common::*, util::WithNonce, Action, ToStateChange};
+use crate::{
+    test_common::*,
+    util::{WithDeadline, WithNonce},
+    Action, ToStateChange,
+};
 use alloc::collections::BTreeMap;
 use core::{iter::once, marker::PhantomData};
 use frame_support::assert_noop;
@@ -28,6 +32,50 @@ where
         .collect()
 }
 
+pub fn get_pauth_with_deadline<A: Action<Test> + Clone>(
+    action: &WithDeadline<Test, A>,
+    signers: &[(Did, &sr25519::Pair)],
+) -> Vec<DidSignature<Did>>
+where
+    WithNonce<Test, A>: ToStateChange<Test>,
+{
+    let payload = action
+        .clone()
+        .map(|action| WithNonce::<Test, _>::new_with_nonce(action, 0));
+
+    signers
+        .iter()
+        .map(|(did, kp)| {
+            did_sig_on_bytes::<Test, _>(&payload.to_state_change().encode(), &kp, did.clone(), 1)
+        })
+        .collect()
+}
+
+pub fn add_status_list_registry(registry_id: RegistryId, policy: Policy, add_only: bool) {
+    let add = AddStatusListRegistry {
+        id: registry_id,
+        registry: Registry { policy, add_only },
+        size: MIN_STATUS_LIST_SIZE,
+    };
+    RevoMod::new_status_list_registry(Origin::signed(ABBA), add).unwrap();
+}
+
+pub fn status_list_update(
+    registry_id: RegistryId,
+    ranges: &[(u32, u32)],
+    revoked: bool,
+) -> UpdateStatusListRaw<Test> {
+    UpdateStatusListRaw {
+        _marker: PhantomData,
+        registry_id,
+        ranges: ranges
+            .iter()
+            .map(|&(start, end)| StatusListRange { start, end })
+            .collect(),
+        revoked,
+    }
+}
+
 pub fn get_nonces(signers: &[(Did, &sr25519::Pair)]) -> BTreeMap<Did, u64> {
     let mut nonces = BTreeMap::new();
     for (d, _) in signers {
@@ -53,9 +101,10 @@ pub fn check_nonce_increase(old_nonces: BTreeMap<Did, u64>, signers: &[(Did, &sr
 /// For example, `#[test] fn invalidpolicy` exercises the RevErr::InvalidPolicy.
 mod errors {
     // Cannot do `use super::*` as that would import `Call` as `Call` which conflicts with `Call` in `test_common`
+    use super::super::StatusLists;
     use super::*;
     use alloc::collections::BTreeSet;
-    use frame_support::dispatch::DispatchError;
+    use frame_support::{dispatch::DispatchError, StorageMap};
 
     #[test]
     fn invalidpolicy() {
@@ -63,16 +112,24 @@ mod errors {
             return ext().execute_with(invalidpolicy);
         }
 
-        let ar = AddRegistry {
-            id: RGA,
-            registry: Registry {
-                policy: oneof(&[]),
-                add_only: false,
-            },
-        };
+        for policy in vec![
+            oneof(&[]),
+            allof(&[]),
+            kofn(&[], 0),
+            kofn(&[DIDA, DIDB], 0),
+            kofn(&[DIDA, DIDB], 3),
+        ] {
+            let ar = AddRegistry {
+                id: RGA,
+                registry: Registry {
+                    policy,
+                    add_only: false,
+                },
+            };
 
-        let err = RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap_err();
-        assert_eq!(err, RevErr::<Test>::InvalidPolicy.into());
+            let err = RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap_err();
+            assert_eq!(err, RevErr::<Test>::InvalidPolicy.into());
+        }
     }
 
     // this test has caught at least one bug
@@ -118,6 +175,18 @@ mod errors {
             (oneof(&[a, b]), &[(a, &kpa), (b, &kpb)], "two signers"),
             (oneof(&[a]), &[], "one controller; no sigs"),
             (oneof(&[a, b]), &[], "two controllers; no sigs"),
+            (allof(&[a, b]), &[(a, &kpa)], "not all controllers"),
+            (
+                allof(&[a, b]),
+                &[(a, &kpa), (c, &kpc)],
+                "all of; account not a controller",
+            ),
+            (kofn(&[a, b, c], 2), &[(b, &kpb)], "below threshold"),
+            (
+                kofn(&[a, b], 1),
+                &[(c, &kpc)],
+                "k of n; account not a controller",
+            ),
         ];
 
         for (pol, set, description) in cases {
@@ -176,6 +245,38 @@ mod errors {
         RevoMod::unrevoke(Origin::signed(ABBA), unrevoke.clone(), ur_proof).unwrap();
     }
 
+    #[test]
+    fn duplicatesigner() {
+        if !in_ext() {
+            return ext().execute_with(duplicatesigner);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        let ar = AddRegistry {
+            id: RGA,
+            registry: Registry {
+                policy: kofn(&[DIDA, DIDB], 2),
+                add_only: false,
+            },
+        };
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+
+        let rev = RevokeRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: once(Default::default()).collect(),
+        };
+        // The same signature can't be counted twice towards the threshold
+        let proof = get_pauth(&rev, &[(DIDA, &kpa)]);
+        let proof = vec![proof[0].clone(), proof[0].clone()];
+        assert_noop!(
+            RevoMod::revoke(Origin::signed(ABBA), rev, proof),
+            RevErr::<Test>::DuplicateSigner
+        );
+    }
+
     #[test]
     fn regexists() {
         if !in_ext() {
@@ -262,6 +363,220 @@ mod errors {
         assert_noop!(RevoMod::new_registry(Origin::signed(ABBA), ar), err);
     }
 
+    #[test]
+    fn invalidstatuslistsize() {
+        if !in_ext() {
+            return ext().execute_with(invalidstatuslistsize);
+        }
+
+        for size in vec![
+            0,
+            MIN_STATUS_LIST_SIZE - 8,
+            MIN_STATUS_LIST_SIZE + 1,
+            MaxStatusListSize::get() + 8,
+        ] {
+            let add = AddStatusListRegistry {
+                id: RGA,
+                registry: Registry {
+                    policy: oneof(&[DIDA]),
+                    add_only: false,
+                },
+                size,
+            };
+            assert_noop!(
+                RevoMod::new_status_list_registry(Origin::signed(ABBA), add),
+                RevErr::<Test>::InvalidStatusListSize
+            );
+        }
+    }
+
+    #[test]
+    fn invalidstatuslistrange() {
+        if !in_ext() {
+            return ext().execute_with(invalidstatuslistrange);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        add_status_list_registry(RGA, oneof(&[DIDA]), false);
+
+        for ranges in vec![
+            vec![(1, 1)],
+            vec![(2, 1)],
+            vec![(0, 1), (MIN_STATUS_LIST_SIZE, MIN_STATUS_LIST_SIZE + 1)],
+            vec![(0, MIN_STATUS_LIST_SIZE + 1)],
+        ] {
+            let update = status_list_update(RGA, &ranges, true);
+            let proof = get_pauth(&update, &[(DIDA, &kpa)]);
+            assert_noop!(
+                RevoMod::update_status_list(Origin::signed(ABBA), update, proof),
+                RevErr::<Test>::InvalidStatusListRange
+            );
+        }
+    }
+
+    #[test]
+    fn malformedstatuslist() {
+        if !in_ext() {
+            return ext().execute_with(malformedstatuslist);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        add_status_list_registry(RGA, oneof(&[DIDA]), false);
+        StatusLists::mutate(RGA, |status_list| {
+            let encoded_list = &mut status_list.as_mut().unwrap().encoded_list.0;
+            let last = encoded_list.len() - 1;
+            encoded_list[last] ^= 1;
+        });
+
+        let update = status_list_update(RGA, &[(0, 1)], true);
+        let proof = get_pauth(&update, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::update_status_list(Origin::signed(ABBA), update, proof),
+            RevErr::<Test>::MalformedStatusList
+        );
+    }
+
+    #[test]
+    fn notstatuslist() {
+        if !in_ext() {
+            return ext().execute_with(notstatuslist);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        let ar = AddRegistry {
+            id: RGA,
+            registry: Registry {
+                policy: oneof(&[DIDA]),
+                add_only: false,
+            },
+        };
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+
+        let update = status_list_update(RGA, &[(0, 1)], true);
+        let proof = get_pauth(&update, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::update_status_list(Origin::signed(ABBA), update, proof),
+            RevErr::<Test>::NotStatusList
+        );
+    }
+
+    #[test]
+    fn statuslistregistry() {
+        if !in_ext() {
+            return ext().execute_with(statuslistregistry);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        add_status_list_registry(RGA, oneof(&[DIDA]), false);
+
+        let revoke = RevokeRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: once(Default::default()).collect(),
+        };
+        let proof = get_pauth(&revoke, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::revoke(Origin::signed(ABBA), revoke, proof),
+            RevErr::<Test>::StatusListRegistry
+        );
+
+        let unrevoke = UnRevokeRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: once(Default::default()).collect(),
+        };
+        let proof = get_pauth(&unrevoke, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::unrevoke(Origin::signed(ABBA), unrevoke, proof),
+            RevErr::<Test>::StatusListRegistry
+        );
+
+        let set_status = SetRevocationStatusRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: once(Default::default()).collect(),
+            status: CredentialStatus::Suspended,
+            reason: None,
+        };
+        let proof = get_pauth(&set_status, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::set_revocation_status(Origin::signed(ABBA), set_status, proof),
+            RevErr::<Test>::StatusListRegistry
+        );
+
+        let lift = LiftSuspensionRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: once(Default::default()).collect(),
+        };
+        let proof = get_pauth(&lift, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::lift_suspension(Origin::signed(ABBA), lift, proof),
+            RevErr::<Test>::StatusListRegistry
+        );
+    }
+
+    #[test]
+    fn credentialrevoked() {
+        if !in_ext() {
+            return ext().execute_with(credentialrevoked);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        let ar = AddRegistry {
+            id: RGA,
+            registry: Registry {
+                policy: oneof(&[DIDA]),
+                add_only: false,
+            },
+        };
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+
+        let revoke = RevokeRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: once(RA).collect(),
+        };
+        let proof = get_pauth(&revoke, &[(DIDA, &kpa)]);
+        RevoMod::revoke(Origin::signed(ABBA), revoke, proof).unwrap();
+
+        run_to_block(11);
+
+        let set_status = SetRevocationStatusRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: vec![RA, RB].into_iter().collect(),
+            status: CredentialStatus::Suspended,
+            reason: Some(1),
+        };
+        let proof = get_pauth(&set_status, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::set_revocation_status(Origin::signed(ABBA), set_status, proof),
+            RevErr::<Test>::CredentialRevoked
+        );
+
+        let lift = LiftSuspensionRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: once(RA).collect(),
+        };
+        let proof = get_pauth(&lift, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::lift_suspension(Origin::signed(ABBA), lift, proof),
+            RevErr::<Test>::CredentialRevoked
+        );
+    }
+
     #[test]
     fn emtpy_payload() {
         if !in_ext() {
@@ -399,6 +714,205 @@ mod errors {
             RevoMod::remove_registry(Origin::signed(ABBA), remove, proof),
             err
         );
+
+        let update = UpdateRegistryPolicyRaw {
+            _marker: PhantomData,
+            registry_id,
+            policy: oneof(&[DIDA]),
+            add_only: false,
+        };
+        let proof = get_pauth(&update, &[(DIDA, &kpa)]);
+        assert_eq!(
+            RevoMod::update_registry_policy(Origin::signed(ABBA), update, proof),
+            err
+        );
+    }
+
+    #[test]
+    fn revoke_with_deadline() {
+        if !in_ext() {
+            return ext().execute_with(revoke_with_deadline);
+        }
+
+        let policy = oneof(&[DIDA]);
+        let registry_id = RGA;
+        let add_only = true;
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+
+        let ar = AddRegistry {
+            id: registry_id,
+            registry: Registry { policy, add_only },
+        };
+
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+
+        let revoke = |revoke_ids: &[RevokeId], replay_id, expires_at| {
+            WithDeadline::new(
+                RevokeRaw {
+                    _marker: PhantomData,
+                    registry_id,
+                    revoke_ids: revoke_ids.iter().cloned().collect(),
+                },
+                replay_id,
+                expires_at,
+            )
+        };
+
+        // Several actions signed in parallel can be submitted in any order without touching the nonce
+        let first = revoke(&[RA], [1; 32], 15);
+        let second = revoke(&[RB], [2; 32], 20);
+        let first_proof = get_pauth_with_deadline(&first, &[(DIDA, &kpa)]);
+        let second_proof = get_pauth_with_deadline(&second, &[(DIDA, &kpa)]);
+        let old_nonces = get_nonces(&[(DIDA, &kpa)]);
+        RevoMod::revoke_with_deadline(Origin::signed(ABBA), second.clone(), second_proof.clone())
+            .unwrap();
+        RevoMod::revoke_with_deadline(Origin::signed(ABBA), first.clone(), first_proof.clone())
+            .unwrap();
+        assert!(Revocations::contains_key(registry_id, RA));
+        assert!(Revocations::contains_key(registry_id, RB));
+        assert_eq!(old_nonces, get_nonces(&[(DIDA, &kpa)]));
+
+        // Replay ids can't be reused until the actions expire
+        assert_noop!(
+            RevoMod::revoke_with_deadline(Origin::signed(ABBA), first, first_proof),
+            did::Error::<Test>::ReplayIdAlreadyUsed
+        );
+        let reused = revoke(&[random()], [2; 32], 18);
+        let reused_proof = get_pauth_with_deadline(&reused, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::revoke_with_deadline(Origin::signed(ABBA), reused, reused_proof),
+            did::Error::<Test>::ReplayIdAlreadyUsed
+        );
+
+        let too_long = revoke(&[random()], [3; 32], 10 + MaxActionLifetime::get() + 1);
+        let too_long_proof = get_pauth_with_deadline(&too_long, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::revoke_with_deadline(Origin::signed(ABBA), too_long, too_long_proof),
+            did::Error::<Test>::ActionLifetimeTooLong
+        );
+
+        let mut tampered = revoke(&[random()], [4; 32], 15);
+        let tampered_proof = get_pauth_with_deadline(&tampered, &[(DIDA, &kpa)]);
+        tampered.expires_at = 16;
+        assert_noop!(
+            RevoMod::revoke_with_deadline(Origin::signed(ABBA), tampered, tampered_proof),
+            RevErr::<Test>::NotAuthorized
+        );
+
+        // Expired actions are rejected and their replay ids are removed after expiry
+        for block in 11..=16 {
+            run_to_block(block);
+            DIDModule::on_initialize(block);
+        }
+        assert!(DIDModule::replay_id_expiry(DIDA, [1; 32]).is_none());
+        assert_eq!(DIDModule::replay_id_expiry(DIDA, [2; 32]), Some(20));
+        assert_noop!(
+            RevoMod::revoke_with_deadline(Origin::signed(ABBA), second, second_proof),
+            did::Error::<Test>::ReplayIdAlreadyUsed
+        );
+        let expired = revoke(&[random()], [1; 32], 15);
+        let expired_proof = get_pauth_with_deadline(&expired, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::revoke_with_deadline(Origin::signed(ABBA), expired, expired_proof),
+            did::Error::<Test>::ActionExpired
+        );
+
+        // Replay id can be used again once the previous action using it has expired
+        let reused = revoke(&[RC], [1; 32], 20);
+        let reused_proof = get_pauth_with_deadline(&reused, &[(DIDA, &kpa)]);
+        RevoMod::revoke_with_deadline(Origin::signed(ABBA), reused, reused_proof).unwrap();
+        assert!(Revocations::contains_key(registry_id, RC));
+    }
+
+    #[test]
+    fn unrevoke_with_deadline() {
+        if !in_ext() {
+            return ext().execute_with(unrevoke_with_deadline);
+        }
+
+        let policy = oneof(&[DIDA]);
+        let registry_id = RGA;
+        let add_only = false;
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+
+        let ar = AddRegistry {
+            id: registry_id,
+            registry: Registry { policy, add_only },
+        };
+
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+
+        let revoke = WithDeadline::new(
+            RevokeRaw {
+                _marker: PhantomData,
+                registry_id,
+                revoke_ids: once(RA).collect(),
+            },
+            [1; 32],
+            15,
+        );
+        let proof = get_pauth_with_deadline(&revoke, &[(DIDA, &kpa)]);
+        RevoMod::revoke_with_deadline(Origin::signed(ABBA), revoke, proof).unwrap();
+        assert!(Revocations::contains_key(registry_id, RA));
+
+        let unrevoke = WithDeadline::new(
+            UnRevokeRaw {
+                _marker: PhantomData,
+                registry_id,
+                revoke_ids: once(RA).collect(),
+            },
+            [2; 32],
+            15,
+        );
+        let proof = get_pauth_with_deadline(&unrevoke, &[(DIDA, &kpa)]);
+        let old_nonces = get_nonces(&[(DIDA, &kpa)]);
+        RevoMod::unrevoke_with_deadline(Origin::signed(ABBA), unrevoke, proof).unwrap();
+        assert!(!Revocations::contains_key(registry_id, RA));
+        assert_eq!(old_nonces, get_nonces(&[(DIDA, &kpa)]));
+    }
+
+    #[test]
+    fn remove_registry_with_deadline() {
+        if !in_ext() {
+            return ext().execute_with(remove_registry_with_deadline);
+        }
+
+        let policy = oneof(&[DIDA]);
+        let registry_id = RGA;
+        let add_only = false;
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+
+        let ar = AddRegistry {
+            id: registry_id,
+            registry: Registry { policy, add_only },
+        };
+
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+        assert!(Registries::contains_key(registry_id));
+
+        let rem = WithDeadline::new(
+            RemoveRegistryRaw {
+                _marker: PhantomData,
+                registry_id,
+            },
+            [1; 32],
+            15,
+        );
+        let proof = get_pauth_with_deadline(&rem, &[(DIDA, &kpa)]);
+        let old_nonces = get_nonces(&[(DIDA, &kpa)]);
+        RevoMod::remove_registry_with_deadline(Origin::signed(ABBA), rem, proof).unwrap();
+        assert_eq!(old_nonces, get_nonces(&[(DIDA, &kpa)]));
+
+        assert!(!Registries::contains_key(registry_id));
     }
 
     // Untested variants will be a match error.
@@ -413,6 +927,13 @@ mod errors {
             | RevErr::IncorrectNonce
             | RevErr::AddOnly
             | RevErr::EmptyPayload
+            | RevErr::DuplicateSigner
+            | RevErr::InvalidStatusListSize
+            | RevErr::InvalidStatusListRange
+            | RevErr::MalformedStatusList
+            | RevErr::NotStatusList
+            | RevErr::StatusListRegistry
+            | RevErr::CredentialRevoked
             | RevErr::TooManyControllers => {}
         }
     }
@@ -429,7 +950,7 @@ mod calls {
     // Cannot do `use super::super::*` as that would import `Call` as `Call` which conflicts with `Call` in `test_common`
     use super::super::{Call as RevCall, Registries, Revocations};
     use alloc::collections::BTreeSet;
-    use frame_support::{StorageDoubleMap, StorageMap};
+    use frame_support::{traits::OnInitialize, StorageDoubleMap, StorageMap};
 
     #[test]
     fn new_registry() {
@@ -624,6 +1145,349 @@ mod calls {
         assert!(!Registries::contains_key(registry_id));
     }
 
+    #[test]
+    fn update_registry_policy() {
+        if !in_ext() {
+            return ext().execute_with(update_registry_policy);
+        }
+
+        run_to_block(10);
+
+        let registry_id = RGA;
+        let (kpa, kpb) = (create_did(DIDA), create_did(DIDB));
+
+        let ar = AddRegistry {
+            id: registry_id,
+            registry: Registry {
+                policy: oneof(&[DIDA]),
+                add_only: false,
+            },
+        };
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+
+        // The current controller hands the registry over to both DIDs and makes it add-only
+        let update = UpdateRegistryPolicyRaw {
+            _marker: PhantomData,
+            registry_id,
+            policy: allof(&[DIDA, DIDB]),
+            add_only: true,
+        };
+        let proof = get_pauth(&update, &[(DIDA, &kpa)]);
+        let old_nonces = get_nonces(&[(DIDA, &kpa)]);
+        RevoMod::update_registry_policy(Origin::signed(ABBA), update, proof).unwrap();
+        check_nonce_increase(old_nonces, &[(DIDA, &kpa)]);
+        assert_eq!(
+            Registries::get(registry_id),
+            Some(Registry {
+                policy: allof(&[DIDA, DIDB]),
+                add_only: true,
+            })
+        );
+
+        run_to_block(11);
+
+        // The previous policy doesn't authorize updates anymore
+        let update = UpdateRegistryPolicyRaw {
+            _marker: PhantomData,
+            registry_id,
+            policy: oneof(&[DIDB]),
+            add_only: true,
+        };
+        let proof = get_pauth(&update, &[(DIDA, &kpa)]);
+        assert_noop!(
+            RevoMod::update_registry_policy(Origin::signed(ABBA), update.clone(), proof),
+            RevErr::<Test>::NotAuthorized
+        );
+
+        let signers = [(DIDA, &kpa), (DIDB, &kpb)];
+        let proof = get_pauth(&update, &signers);
+        let old_nonces = get_nonces(&signers);
+        RevoMod::update_registry_policy(Origin::signed(ABBA), update, proof).unwrap();
+        check_nonce_increase(old_nonces, &signers);
+        assert_eq!(
+            Registries::get(registry_id),
+            Some(Registry {
+                policy: oneof(&[DIDB]),
+                add_only: true,
+            })
+        );
+    }
+
+    #[test]
+    fn new_status_list_registry() {
+        if !in_ext() {
+            return ext().execute_with(new_status_list_registry);
+        }
+
+        let add = AddStatusListRegistry {
+            id: RGA,
+            registry: Registry {
+                policy: oneof(&[DIDA]),
+                add_only: false,
+            },
+            size: MIN_STATUS_LIST_SIZE,
+        };
+        RevoMod::new_status_list_registry(Origin::signed(ABBA), add.clone()).unwrap();
+        assert_eq!(Registries::get(RGA), Some(add.registry));
+
+        // The bitstring is stored compressed
+        let status_list = RevoMod::get_status_list(RGA).unwrap();
+        assert_eq!(status_list.size, MIN_STATUS_LIST_SIZE);
+        assert_eq!(&status_list.encoded_list.0[..2], &[0x1f, 0x8b]);
+        assert!(status_list.encoded_list.0.len() < MIN_STATUS_LIST_SIZE as usize / 8);
+        assert_eq!(
+            status_list.bits(),
+            Some(vec![0; MIN_STATUS_LIST_SIZE as usize / 8])
+        );
+    }
+
+    #[test]
+    fn update_status_list() {
+        if !in_ext() {
+            return ext().execute_with(update_status_list);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        add_status_list_registry(RGA, oneof(&[DIDA]), false);
+
+        let update = status_list_update(RGA, &[(3, 10), (20, 21), (8, 12)], true);
+        let proof = get_pauth(&update, &[(DIDA, &kpa)]);
+        let old_nonces = get_nonces(&[(DIDA, &kpa)]);
+        RevoMod::update_status_list(Origin::signed(ABBA), update, proof).unwrap();
+        check_nonce_increase(old_nonces, &[(DIDA, &kpa)]);
+
+        let status_list = RevoMod::get_status_list(RGA).unwrap();
+        let bits = status_list.bits().unwrap();
+        assert_eq!(&bits[..3], &[0b0001_1111, 0b1111_0000, 0b0000_1000]);
+        assert!(bits[3..].iter().all(|&byte| byte == 0));
+        for index in 0..32 {
+            let revoked = (3..12).contains(&index) || index == 20;
+            assert_eq!(status_list.is_set(index), Some(revoked));
+        }
+        assert_eq!(status_list.is_set(MIN_STATUS_LIST_SIZE), None);
+
+        run_to_block(11);
+
+        // Entries can be cleared in a registry which isn't add-only
+        let update = status_list_update(RGA, &[(0, 9)], false);
+        let proof = get_pauth(&update, &[(DIDA, &kpa)]);
+        RevoMod::update_status_list(Origin::signed(ABBA), update, proof).unwrap();
+
+        let status_list = RevoMod::get_status_list(RGA).unwrap();
+        for index in 0..32 {
+            let revoked = (9..12).contains(&index) || index == 20;
+            assert_eq!(status_list.is_set(index), Some(revoked));
+        }
+
+        // Status list is removed along with the registry
+        let rem = RemoveRegistryRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+        };
+        let proof = get_pauth(&rem, &[(DIDA, &kpa)]);
+        RevoMod::remove_registry(Origin::signed(ABBA), rem, proof).unwrap();
+        assert_eq!(RevoMod::get_status_list(RGA), None);
+    }
+
+    #[test]
+    fn set_revocation_status() {
+        if !in_ext() {
+            return ext().execute_with(set_revocation_status);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        let ar = AddRegistry {
+            id: RGA,
+            registry: Registry {
+                policy: oneof(&[DIDA]),
+                add_only: true,
+            },
+        };
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+
+        // Credentials can be suspended in an add-only registry
+        let set_status = SetRevocationStatusRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: vec![RA, RB].into_iter().collect(),
+            status: CredentialStatus::Suspended,
+            reason: Some(3),
+        };
+        let proof = get_pauth(&set_status, &[(DIDA, &kpa)]);
+        let old_nonces = get_nonces(&[(DIDA, &kpa)]);
+        RevoMod::set_revocation_status(Origin::signed(ABBA), set_status, proof).unwrap();
+        check_nonce_increase(old_nonces, &[(DIDA, &kpa)]);
+        for id in &[RA, RB] {
+            assert_eq!(
+                RevoMod::revocation_status(RGA, id),
+                Some(RevocationStatus {
+                    status: CredentialStatus::Suspended,
+                    reason: Some(3),
+                    changed_at: 10,
+                })
+            );
+        }
+
+        run_to_block(11);
+
+        // A suspended credential can be revoked permanently
+        let set_status = SetRevocationStatusRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: once(RA).collect(),
+            status: CredentialStatus::Revoked,
+            reason: Some(5),
+        };
+        let proof = get_pauth(&set_status, &[(DIDA, &kpa)]);
+        RevoMod::set_revocation_status(Origin::signed(ABBA), set_status, proof).unwrap();
+        let revoked = RevocationStatus {
+            status: CredentialStatus::Revoked,
+            reason: Some(5),
+            changed_at: 11,
+        };
+        assert_eq!(RevoMod::revocation_status(RGA, RA), Some(revoked.clone()));
+
+        run_to_block(12);
+
+        // Revoking again keeps the original reason and block
+        let set_status = SetRevocationStatusRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: vec![RA, RB].into_iter().collect(),
+            status: CredentialStatus::Revoked,
+            reason: None,
+        };
+        let proof = get_pauth(&set_status, &[(DIDA, &kpa)]);
+        RevoMod::set_revocation_status(Origin::signed(ABBA), set_status, proof).unwrap();
+        assert_eq!(RevoMod::revocation_status(RGA, RA), Some(revoked));
+        assert_eq!(
+            RevoMod::revocation_status(RGA, RB),
+            Some(RevocationStatus {
+                status: CredentialStatus::Revoked,
+                reason: None,
+                changed_at: 12,
+            })
+        );
+    }
+
+    #[test]
+    fn lift_suspension() {
+        if !in_ext() {
+            return ext().execute_with(lift_suspension);
+        }
+
+        run_to_block(10);
+
+        let kpa = create_did(DIDA);
+        let ar = AddRegistry {
+            id: RGA,
+            registry: Registry {
+                policy: oneof(&[DIDA]),
+                add_only: true,
+            },
+        };
+        RevoMod::new_registry(Origin::signed(ABBA), ar).unwrap();
+
+        let set_status = SetRevocationStatusRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: vec![RA, RB].into_iter().collect(),
+            status: CredentialStatus::Suspended,
+            reason: None,
+        };
+        let proof = get_pauth(&set_status, &[(DIDA, &kpa)]);
+        RevoMod::set_revocation_status(Origin::signed(ABBA), set_status, proof).unwrap();
+
+        run_to_block(11);
+
+        // Suspension can be lifted in an add-only registry, lifting is idempotent
+        let lift = LiftSuspensionRaw {
+            _marker: PhantomData,
+            registry_id: RGA,
+            revoke_ids: vec![RA, RC].into_iter().collect(),
+        };
+        let proof = get_pauth(&lift, &[(DIDA, &kpa)]);
+        let old_nonces = get_nonces(&[(DIDA, &kpa)]);
+        RevoMod::lift_suspension(Origin::signed(ABBA), lift, proof).unwrap();
+        check_nonce_increase(old_nonces, &[(DIDA, &kpa)]);
+        assert_eq!(RevoMod::revocation_status(RGA, RA), None);
+        assert_eq!(RevoMod::revocation_status(RGA, RC), None);
+        assert_eq!(
+            RevoMod::revocation_status(RGA, RB),
+            Some(RevocationStatus {
+                status: CredentialStatus::Suspended,
+                reason: None,
+                changed_at: 10,
+            })
+        );
+        // Suspended credentials aren't revoked
+        assert!(!Revocations::contains_key(RGA, RB));
+    }
+
+    #[test]
+    fn with_deadline_weights() {
+        if !in_ext() {
+            return ext().execute_with(with_deadline_weights);
+        }
+        use frame_support::weights::GetDispatchInfo;
+
+        let kpa = create_did(DIDA);
+        let kpb = create_did(DIDB);
+
+        let revoke = WithDeadline::new(
+            RevokeRaw {
+                _marker: PhantomData,
+                registry_id: RGA,
+                revoke_ids: once(RA).collect(),
+            },
+            [1; 32],
+            15,
+        );
+        let unrevoke = revoke.clone().map(
+            |RevokeRaw {
+                 registry_id,
+                 revoke_ids,
+                 ..
+             }| UnRevokeRaw {
+                _marker: PhantomData,
+                registry_id,
+                revoke_ids,
+            },
+        );
+        let removal = revoke
+            .clone()
+            .map(|RevokeRaw { registry_id, .. }| RemoveRegistryRaw {
+                _marker: PhantomData,
+                registry_id,
+            });
+        let weights = |proof: Vec<DidSignature<Did>>| {
+            vec![
+                RevCall::<Test>::revoke_with_deadline(revoke.clone(), proof.clone()),
+                RevCall::<Test>::unrevoke_with_deadline(unrevoke.clone(), proof.clone()),
+                RevCall::<Test>::remove_registry_with_deadline(removal.clone(), proof),
+            ]
+            .into_iter()
+            .map(|call| call.get_dispatch_info().weight)
+            .collect::<Vec<_>>()
+        };
+
+        // Weight is computed for a proof without signatures and grows with each signature
+        let no_sigs = weights(vec![]);
+        let one_sig = weights(get_pauth_with_deadline(&revoke, &[(DIDA, &kpa)]));
+        let two_sigs = weights(get_pauth_with_deadline(
+            &revoke,
+            &[(DIDA, &kpa), (DIDB, &kpb)],
+        ));
+        for ((no_sigs, one_sig), two_sigs) in no_sigs.into_iter().zip(one_sig).zip(two_sigs) {
+            assert!(no_sigs < one_sig);
+            assert!(one_sig < two_sigs);
+        }
+    }
+
     // Untested variants will be a match error.
     // To fix the match error, write a test for the variant then update the test.
     fn _all_included(dummy: RevCall<Test>) {
@@ -632,13 +1496,22 @@ mod calls {
             | RevCall::revoke(_, _)
             | RevCall::unrevoke(_, _)
             | RevCall::remove_registry(_, _)
+            | RevCall::revoke_with_deadline(_, _)
+            | RevCall::unrevoke_with_deadline(_, _)
+            | RevCall::remove_registry_with_deadline(_, _)
+            | RevCall::update_registry_policy(_, _)
+            | RevCall::new_status_list_registry(_)
+            | RevCall::update_status_list(_, _)
+            | RevCall::set_revocation_status(_, _)
+            | RevCall::lift_suspension(_, _)
             | RevCall::__PhantomItem(_, _) => {}
         }
     }
 }
 
 mod test {
-    use frame_support::StorageMap;
+    use alloc::collections::BTreeSet;
+    use frame_support::{StorageDoubleMap, StorageMap};
     use sp_runtime::DispatchError;
     // Cannot do `use super::*` as that would import `Call` as `Call` which conflicts with `Call` in `test_common`
     use super::*;
@@ -673,6 +1546,28 @@ mod test {
             (line!(), oneof(&[a, b]), &[(a, &kpa), (b, &kpb)], false), // two signers
             (line!(), oneof(&[a]), &[], false), // one controller; no sigs
             (line!(), oneof(&[a, b]), &[], false), // two controllers; no sigs
+            (line!(), allof(&[a]), &[(a, &kpa)], true),
+            (line!(), allof(&[a, b]), &[(a, &kpa), (b, &kpb)], true),
+            (line!(), allof(&[a, b]), &[(b, &kpb), (a, &kpa)], true),
+            (line!(), allof(&[a, b]), &[(a, &kpa)], false), // not all controllers
+            (line!(), allof(&[a, b]), &[(a, &kpa), (c, &kpc)], false), // account not a controller
+            (
+                line!(),
+                allof(&[a, b]),
+                &[(a, &kpa), (b, &kpb), (c, &kpc)],
+                false,
+            ), // extra signer
+            (line!(), kofn(&[a, b, c], 2), &[(a, &kpa), (c, &kpc)], true),
+            (
+                line!(),
+                kofn(&[a, b, c], 2),
+                &[(a, &kpa), (b, &kpb), (c, &kpc)],
+                true,
+            ),
+            (line!(), kofn(&[a, b], 1), &[(b, &kpb)], true),
+            (line!(), kofn(&[a, b, c], 2), &[(a, &kpa)], false), // below threshold
+            (line!(), kofn(&[a, b], 2), &[(a, &kpa), (c, &kpc)], false), // account not a controller
+            (line!(), kofn(&[a, b], 1), &[], false),             // no sigs
         ];
         for (i, (line_no, policy, signers, expect_success)) in cases.into_iter().enumerate() {
             eprintln!("running case from line {}", line_no);
@@ -699,6 +1594,23 @@ mod test {
         }
     }
 
+    #[test]
+    /// Registries created before the `AllOf` and `KOfN` policies were introduced keep decoding as `OneOf`.
+    fn oneof_policy_encoding() {
+        let dids: BTreeSet<Did> = vec![DIDA, DIDB].into_iter().collect();
+        let mut encoded = vec![0u8];
+        encoded.extend(dids.encode());
+        encoded.push(1);
+
+        assert_eq!(
+            Registry::decode(&mut &encoded[..]).unwrap(),
+            Registry {
+                policy: Policy::OneOf(dids),
+                add_only: true,
+            }
+        );
+    }
+
     #[test]
     /// Exercises the revocation registry convenience getter, get_revocation_registry.
     fn get_revocation_registry() {
@@ -728,6 +1640,8 @@ mod test {
             return ext().execute_with(get_revocation_status);
         }
 
+        run_to_block(10);
+
         let policy = oneof(&[DIDA]);
         let registry_id = RGA;
         let add_only = false;
@@ -751,5 +1665,32 @@ mod test {
         assert_eq!(RevoMod::get_revocation_status(registry_id, revid), None);
         RevoMod::revoke(Origin::signed(ABBA), revoke, proof).unwrap();
         assert_eq!(RevoMod::get_revocation_status(registry_id, revid), Some(()));
+        assert_eq!(
+            RevoMod::revocation_status(registry_id, revid),
+            Some(RevocationStatus {
+                status: CredentialStatus::Revoked,
+                reason: None,
+                changed_at: 10,
+            })
+        );
+    }
+
+    #[test]
+    /// Revocations made before the statuses were tracked are reported as revoked without a reason.
+    fn revocation_status_without_tracked_status() {
+        if !in_ext() {
+            return ext().execute_with(revocation_status_without_tracked_status);
+        }
+
+        Revocations::insert(RGA, RA, ());
+        assert_eq!(
+            RevoMod::revocation_status(RGA, RA),
+            Some(RevocationStatus {
+                status: CredentialStatus::Revoked,
+                reason: None,
+                changed_at: 0,
+            })
+        );
+        assert_eq!(RevoMod::revocation_status(RGA, RB), None);
     }
 }
@@ -31,12 +31,15 @@ pub trait WeightInfo {
     fn revoke_sr25519(r: u32) -> Weight;
     fn revoke_ed25519(r: u32) -> Weight;
     fn revoke_secp256k1(r: u32) -> Weight;
+    fn revoke_secp256r1(r: u32) -> Weight;
     fn unrevoke_sr25519(r: u32) -> Weight;
     fn unrevoke_ed25519(r: u32) -> Weight;
     fn unrevoke_secp256k1(r: u32) -> Weight;
+    fn unrevoke_secp256r1(r: u32) -> Weight;
     fn remove_registry_sr25519() -> Weight;
     fn remove_registry_ed25519() -> Weight;
     fn remove_registry_secp256k1() -> Weight;
+    fn remove_registry_secp256r1() -> Weight;
     fn new_registry(c: u32) -> Weight;
 }
 
@@ -67,6 +70,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
     }
+    fn revoke_secp256r1(r: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (148_000_000 as Weight)
+            .saturating_add((707_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
     fn unrevoke_sr25519(r: u32) -> Weight {
         (67_695_000 as Weight)
             // Standard Error: 1_000
@@ -91,6 +102,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
             .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
     }
+    fn unrevoke_secp256r1(r: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (166_568_000 as Weight)
+            .saturating_add((704_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
     fn remove_registry_sr25519() -> Weight {
         (128_526_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
@@ -106,6 +125,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(103 as Weight))
     }
+    fn remove_registry_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (230_576_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(103 as Weight))
+    }
     fn new_registry(c: u32) -> Weight {
         (9_069_000 as Weight)
             // Standard Error: 1_000
@@ -141,6 +166,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
     }
+    fn revoke_secp256r1(r: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (148_000_000 as Weight)
+            .saturating_add((707_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
     fn unrevoke_sr25519(r: u32) -> Weight {
         (67_695_000 as Weight)
             // Standard Error: 1_000
@@ -165,6 +198,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
             .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
     }
+    fn unrevoke_secp256r1(r: u32) -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (166_568_000 as Weight)
+            .saturating_add((704_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
     fn remove_registry_sr25519() -> Weight {
         (128_526_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
@@ -180,6 +221,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(103 as Weight))
     }
+    fn remove_registry_secp256r1() -> Weight {
+        // Unbenchmarked placeholder: the secp256k1 weight until the secp256r1 benchmark is run
+        (230_576_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(103 as Weight))
+    }
     fn new_registry(c: u32) -> Weight {
         (9_069_000 as Weight)
             // Standard Error: 1_000
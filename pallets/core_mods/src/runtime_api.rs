@@ -1,16 +1,40 @@
 use crate::{
     accumulator, bbs_plus,
     did::{self, Config},
-    util::IncId,
+    keys_and_sigs::PublicKey,
+    revoke,
+    util::{IncId, WrappedBytes},
 };
 use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 sp_api::decl_runtime_apis! {
+    /// Version 2 adds DID resolution, the paginated and indexed DID queries and the revocation status queries.
+    #[api_version(2)]
     pub trait CoreModsApi<T: Config> {
         fn did_details(id: did::Did, params: Option<did::AggregatedDidDetailsRequestParams>) -> Option<did::AggregatedDidDetailsResponse<T>>;
 
         fn did_list_details(dids: Vec<did::Did>, params: Option<did::AggregatedDidDetailsRequestParams>) -> Vec<Option<did::AggregatedDidDetailsResponse<T>>>;
 
+        fn resolve_did(did: did::Did) -> Option<did::DidResolution<T>>;
+
+        fn resolve_did_at(did: did::Did, query: did::DidVersionQuery<T>) -> Option<did::HistoricalDidResolution<T>>;
+
+        fn did_recovery(did: did::Did) -> Option<did::DidRecoveryStatus<T>>;
+
+        fn dids_by_account(account: T::AccountId) -> Vec<did::Did>;
+
+        fn dids_by_public_key(public_key: PublicKey) -> Vec<(did::Did, IncId)>;
+
+        fn did_keys(did: did::Did, cursor: Option<IncId>, limit: u32) -> did::Page<IncId, did::DidKeyWithId>;
+
+        fn did_controllers(did: did::Did, cursor: Option<did::Controller>, limit: u32) -> did::Page<did::Controller, did::Controller>;
+
+        fn did_service_endpoints(did: did::Did, cursor: Option<WrappedBytes>, limit: u32) -> did::Page<WrappedBytes, did::ServiceEndpointWithId>;
+
+        fn did_list(did_type: Option<did::DidType>, cursor: Option<did::Did>, limit: u32) -> did::Page<did::Did, did::Did>;
+
+        fn did_controller_paths(did: did::Did) -> Vec<did::ControllerPath>;
+
         fn bbs_plus_public_key_with_params(id: bbs_plus::BBSPlusPublicKeyStorageKey) -> Option<bbs_plus::BBSPlusPublicKeyWithParams>;
 
         fn bbs_plus_params_by_did(owner: bbs_plus::BBSPlusParamsOwner) -> BTreeMap<IncId, bbs_plus::BBSPlusParameters>;
@@ -20,5 +44,9 @@ sp_api::decl_runtime_apis! {
         fn accumulator_public_key_with_params(id: accumulator::AccumPublicKeyStorageKey) -> Option<accumulator::AccumPublicKeyWithParams>;
 
         fn accumulator_with_public_key_and_params(id: accumulator::AccumulatorId) -> Option<(Vec<u8>, Option<accumulator::AccumPublicKeyWithParams>)>;
+
+        fn status_list(id: revoke::RegistryId) -> Option<revoke::StatusList>;
+
+        fn revocation_status(registry_id: revoke::RegistryId, revoke_id: revoke::RevokeId) -> Option<revoke::RevocationStatus<T>>;
     }
 }
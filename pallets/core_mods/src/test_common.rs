@@ -124,6 +124,7 @@ impl From<accumulator::Event> for TestEvent {
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const MaxControllers: u32 = 15;
+    pub const MaxStatusListSize: u32 = 2 * revoke::MIN_STATUS_LIST_SIZE;
     pub const ByteReadWeight: Weight = 10;
 }
 
@@ -216,11 +217,32 @@ impl crate::did::Config for Test {
     type MaxServiceEndpointOrigins = MaxServiceEndpointOrigins;
     type MaxServiceEndpointOriginSize = MaxServiceEndpointOriginSize;
     type ServiceEndpointOriginPerByteWeight = ServiceEndpointOriginPerByteWeight;
+    type MaxServiceEndpointTypes = MaxServiceEndpointTypes;
+    type MaxServiceEndpointTypeSize = MaxServiceEndpointTypeSize;
+    type ServiceEndpointTypePerByteWeight = ServiceEndpointTypePerByteWeight;
+    type MaxServiceEndpointProperties = MaxServiceEndpointProperties;
+    type MaxServiceEndpointPropertyValues = MaxServiceEndpointPropertyValues;
+    type MaxServiceEndpointPropertySize = MaxServiceEndpointPropertySize;
+    type ServiceEndpointPropertyPerByteWeight = ServiceEndpointPropertyPerByteWeight;
+    type MaxRecoveryGuardians = MaxRecoveryGuardians;
+    type MinRecoveryDelay = MinRecoveryDelay;
+    type MaxActionLifetime = MaxActionLifetime;
+    type MaxReplayIdRemovalsPerBlock = MaxReplayIdRemovalsPerBlock;
+    type Currency = Balances;
+    type StorageItemDeposit = StorageItemDeposit;
+    type StorageByteDeposit = StorageByteDeposit;
+    type MaxControllerDepth = MaxControllerDepth;
+    type PreventControllerCycles = PreventControllerCycles;
+    type MaxControllerGraphReads = MaxControllerGraphReads;
+    type MaxDidDocumentItems = MaxDidDocumentItems;
+    type DidVersionRetentionPeriod = DidVersionRetentionPeriod;
+    type WebAuthnRpIdHash = WebAuthnRpIdHash;
 }
 
 impl crate::revoke::Config for Test {
     type Event = TestEvent;
     type MaxControllers = MaxControllers;
+    type MaxStatusListSize = MaxStatusListSize;
 }
 
 parameter_types! {
@@ -241,7 +263,29 @@ parameter_types! {
     pub const MaxServiceEndpointOrigins: u16 = 20;
     pub const MaxServiceEndpointOriginSize: u16 = 256;
     pub const ServiceEndpointOriginPerByteWeight: Weight = 10;
-}
+    pub const MaxServiceEndpointTypes: u16 = 4;
+    pub const MaxServiceEndpointTypeSize: u16 = 64;
+    pub const ServiceEndpointTypePerByteWeight: Weight = 10;
+    pub const MaxServiceEndpointProperties: u16 = 4;
+    pub const MaxServiceEndpointPropertyValues: u16 = 4;
+    pub const MaxServiceEndpointPropertySize: u16 = 128;
+    pub const ServiceEndpointPropertyPerByteWeight: Weight = 10;
+    pub const MaxRecoveryGuardians: u16 = 5;
+    pub const MinRecoveryDelay: u64 = 5;
+    pub const MaxActionLifetime: u64 = 10;
+    pub const MaxReplayIdRemovalsPerBlock: u32 = 4;
+    pub const StorageItemDeposit: u64 = 10;
+    pub const StorageByteDeposit: u64 = 1;
+    pub const MaxControllerDepth: u16 = 4;
+    pub const PreventControllerCycles: bool = true;
+    pub const MaxControllerGraphReads: u32 = 16;
+    pub const MaxDidDocumentItems: u32 = 32;
+    pub const DidVersionRetentionPeriod: u64 = 10;
+    pub WebAuthnRpIdHash: [u8; 32] = sp_io::hashing::sha2_256(b"dock.io");
+}
+
+/// Free balance of each of the test accounts, enough to cover the storage deposits of the tests.
+pub const INITIAL_BALANCE: u64 = 1_000_000;
 
 impl crate::anchor::Config for Test {
     type Event = TestEvent;
@@ -304,10 +348,16 @@ pub fn meta_in_ext() {
 }
 
 pub fn ext() -> sp_io::TestExternalities {
-    let mut ret: sp_io::TestExternalities = system::GenesisConfig::default()
+    let mut storage = system::GenesisConfig::default()
         .build_storage::<Test>()
-        .unwrap()
-        .into();
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (0..10).map(|account| (account, INITIAL_BALANCE)).collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ret: sp_io::TestExternalities = storage.into();
     ret.execute_with(|| {
         system::Module::<Test>::initialize(
             &1, // system module will not store events if block_number == 0
@@ -324,6 +374,19 @@ pub fn oneof(dids: &[Did]) -> Policy {
     Policy::OneOf(dids.iter().cloned().collect())
 }
 
+/// create an AllOf policy
+pub fn allof(dids: &[Did]) -> Policy {
+    Policy::AllOf(dids.iter().cloned().collect())
+}
+
+/// create a KOfN policy
+pub fn kofn(dids: &[Did], threshold: u32) -> Policy {
+    Policy::KOfN {
+        dids: dids.iter().cloned().collect(),
+        threshold,
+    }
+}
+
 /// generate a random keypair
 pub fn gen_kp() -> sr25519::Pair {
     sr25519::Pair::generate_with_phrase(None).0
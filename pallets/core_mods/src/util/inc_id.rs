@@ -32,6 +32,12 @@ impl From<u32> for IncId {
     }
 }
 
+impl From<IncId> for u32 {
+    fn from(IncId(val): IncId) -> u32 {
+        val
+    }
+}
+
 impl From<u16> for IncId {
     fn from(val: u16) -> IncId {
         IncId(val.into())
@@ -237,6 +237,10 @@ macro_rules! with_pair {
                 let $pair = $crate::def_pair!(secp256k1, $seed);
                 $($body)+
             }
+            3 => {
+                let $pair = $crate::def_pair!(secp256r1, $seed);
+                $($body)+
+            }
             _ => unimplemented!()
         }
     }
@@ -287,6 +291,9 @@ macro_rules! def_pair {
     (secp256k1, $seed: expr) => {
         $crate::keys_and_sigs::get_secp256k1_keypair_1($seed)
     };
+    (secp256r1, $seed: expr) => {
+        $crate::keys_and_sigs::get_secp256r1_keypair_1($seed)
+    };
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -297,7 +304,8 @@ macro_rules! bench_with_all_pairs {
             $(
                 $bench_name_sr25519: ident for sr25519,
                 $bench_name_ed25519: ident for ed25519,
-                $bench_name_secp256k1: ident for secp256k1
+                $bench_name_secp256k1: ident for secp256k1,
+                $bench_name_secp256r1: ident for secp256r1
                 {
                     $({ $($init: tt)* })?
                     let $pair: ident as Pair;
@@ -337,6 +345,14 @@ macro_rules! bench_with_all_pairs {
                     let $pair = $crate::def_pair!(secp256k1, &[2; 32]);
                     $($body)+
                 }: $call_tt($($call_e),+) verify { $($verification)* }
+
+                $bench_name_secp256r1 {
+                    $($($init)*)*
+                    #[allow(unused_imports)]
+                    use sp_core::Pair;
+                    let $pair = $crate::def_pair!(secp256r1, &[5; 32]);
+                    $($body)+
+                }: $call_tt($($call_e),+) verify { $($verification)* }
             )+
 
             $($($other)*)?
@@ -3,6 +3,7 @@ pub mod bytes;
 pub mod hex;
 pub mod inc_id;
 pub mod macros;
+pub mod with_deadline;
 pub mod with_nonce;
 pub mod wrapped_action_with_nonce;
 
@@ -11,5 +12,6 @@ pub use bytes::*;
 pub use hex::*;
 pub use inc_id::*;
 pub use macros::*;
+pub use with_deadline::*;
 pub use with_nonce::*;
 pub use wrapped_action_with_nonce::*;
@@ -0,0 +1,101 @@
+use crate::{util::WrappedBytes, Action, StateChange, ToStateChange};
+use codec::{Decode, Encode};
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::borrow::Cow;
+
+/// Random identifier protecting an action signed by a DID from replays.
+pub type ReplayId = [u8; 32];
+
+/// Wraps an action signed by a DID which is protected from replays using a random id and the block number after
+/// which the action expires instead of the signer's nonce. This allows the same DID to have several independent
+/// actions in flight. Nonce of the wrapped action isn't checked and should be set to zero.
+#[derive(Encode, Decode, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: Sized, A: serde::Serialize",
+        deserialize = "T: Sized, A: serde::Deserialize<'de>"
+    ))
+)]
+pub struct WithDeadline<T: frame_system::Config, A> {
+    pub action: A,
+    /// Random id which can be used by the signer only once until the action expires.
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::hex"))]
+    pub replay_id: ReplayId,
+    /// Last block number (inclusive) at which the action can be executed.
+    pub expires_at: T::BlockNumber,
+}
+
+/// Payload signed by a DID to execute an action protected by `WithDeadline`. Contains the encoded state change of
+/// the wrapped action along with the replay protection params.
+#[derive(Encode, Decode, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ActionWithDeadline<T: frame_system::Config> {
+    pub state_change: WrappedBytes,
+    #[cfg_attr(feature = "serde", serde(with = "crate::util::hex"))]
+    pub replay_id: ReplayId,
+    pub expires_at: T::BlockNumber,
+}
+
+impl<T: frame_system::Config, A> WithDeadline<T, A> {
+    /// Wraps the action with the supplied replay protection params.
+    pub fn new(action: A, replay_id: ReplayId, expires_at: T::BlockNumber) -> Self {
+        Self {
+            action,
+            replay_id,
+            expires_at,
+        }
+    }
+
+    /// Transforms the wrapped action keeping the replay protection params.
+    pub fn map<B, F: FnOnce(A) -> B>(self, f: F) -> WithDeadline<T, B> {
+        let Self {
+            action,
+            replay_id,
+            expires_at,
+        } = self;
+
+        WithDeadline::new(f(action), replay_id, expires_at)
+    }
+
+    /// Weight of checking the replay id and marking it as used until the action expires.
+    pub fn replay_protection_weight() -> Weight {
+        T::DbWeight::get().reads_writes(1, 2)
+    }
+}
+
+impl<T: frame_system::Config, A: ToStateChange<T>> WithDeadline<T, A> {
+    /// Builds the payload to be signed by the DID.
+    pub fn signed_payload(&self) -> ActionWithDeadline<T> {
+        ActionWithDeadline {
+            state_change: self.action.to_state_change().encode().into(),
+            replay_id: self.replay_id,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+impl<T: frame_system::Config, A: Action<T>> Action<T> for WithDeadline<T, A> {
+    type Target = A::Target;
+
+    fn target(&self) -> Self::Target {
+        self.action.target()
+    }
+
+    fn len(&self) -> u32 {
+        self.action.len()
+    }
+}
+
+impl<T: frame_system::Config, A: ToStateChange<T>> ToStateChange<T> for WithDeadline<T, A> {
+    fn to_state_change(&self) -> StateChange<'_, T> {
+        StateChange::ActionWithDeadline(Cow::Owned(self.signed_payload()))
+    }
+
+    fn into_state_change(self) -> StateChange<'static, T> {
+        StateChange::ActionWithDeadline(Cow::Owned(self.signed_payload()))
+    }
+}
@@ -1,27 +1,32 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use common::{arith_utils::DivCeil, traits::PriceProvider};
 use core::fmt::Debug;
-use core_mods::{anchor, attest, blob, did, revoke};
+use core_mods::{anchor, attest, blob, did, did::Did, revoke, revoke::DidSigs, Action};
 use frame_support::{
-    decl_error, decl_module,
+    decl_error, decl_event, decl_module, decl_storage,
     dispatch::{
-        DispatchError, DispatchErrorWithPostInfo, DispatchResultWithPostInfo, PostDispatchInfo,
-        UnfilteredDispatchable,
+        DispatchError, DispatchErrorWithPostInfo, DispatchResult, DispatchResultWithPostInfo,
+        PostDispatchInfo, UnfilteredDispatchable,
     },
-    fail,
-    traits::{Currency, ExistenceRequirement, Get, IsSubType, WithdrawReasons},
+    ensure, fail,
+    traits::{Currency, ExistenceRequirement, Get, IsSubType, ReservableCurrency, WithdrawReasons},
     weights::{GetDispatchInfo, Pays, Weight},
     Parameter,
 };
 use frame_system::{self as system, ensure_signed};
-use sp_std::boxed::Box;
+use sp_runtime::{
+    traits::{CheckedSub, Saturating, Zero},
+    RuntimeDebug,
+};
+use sp_std::{boxed::Box, collections::btree_set::BTreeSet};
 
-// #[cfg(test)]
-// mod test_mock;
-// #[cfg(test)]
-// #[allow(non_snake_case)]
-// mod tests;
+#[cfg(test)]
+mod test_mock;
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests;
 
 /// The pallet's configuration trait
 /// Configure the pallet by specifying the parameters and types on which it depends.
@@ -36,8 +41,10 @@ pub trait Config:
 {
     /// Config option for updating the DockFiatRate
     type PriceProvider: common::traits::PriceProvider;
-    /// The module's Currency type definition
-    type Currency: Currency<Self::AccountId>;
+    /// The module's Currency type definition. Allowances given by sponsors are reserved from their balances.
+    type Currency: ReservableCurrency<Self::AccountId>;
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
 
     /// The outer call (dispatchable) that this module is called with. It is possible to use another type here, but
     /// it's expected that your runtime::Call will be used.
@@ -56,10 +63,56 @@ pub trait Config:
     type MinDockFiatRate: Get<u32>;
 }
 
+/// Allowance given by a sponsor to a DID to pay the fees of the DID's actions.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Sponsorship<Balance, BlockNumber> {
+    /// Amount which can still be spent on the fees of the DID's actions. It is reserved from the sponsor's balance.
+    pub remaining: Balance,
+    /// Last block number (inclusive) at which the allowance can be used.
+    pub expires_at: BlockNumber,
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        AccountId = <T as system::Config>::AccountId,
+        Balance = BalanceOf<T>,
+        BlockNumber = <T as system::Config>::BlockNumber,
+    {
+        /// Sponsor set the allowance of the DID which expires after the given block
+        SponsorshipSet(AccountId, Did, Balance, BlockNumber),
+        /// Sponsor removed the allowance of the DID and its remaining amount was unreserved
+        SponsorshipRemoved(AccountId, Did, Balance),
+        /// Sponsor paid the fee of the DID's action, the remaining allowance follows the fee
+        SponsoredFeePaid(AccountId, Did, Balance, Balance),
+    }
+);
+
 decl_error! {
     pub enum Error for Module<T: Config> where T: Debug {
         /// Call is not among the core_mods ones that should go through fiat_filter
         UnexpectedCall,
+        /// Call isn't authorized by a single DID and thus can't be sponsored
+        NotSponsorable,
+        /// Call isn't among the DID-signed calls which can be sponsored, see `sponsored_did`
+        UnsupportedSponsoredCall,
+        /// No DIDs were provided
+        NoDidsProvided,
+        /// The sponsor hasn't given an allowance to the DID
+        NoSponsorship,
+        /// The allowance has expired
+        SponsorshipExpired,
+        /// The fee of the call is greater than the remaining allowance
+        AllowanceExceeded,
+        /// The sender can't afford the fee which is charged from it if the sponsored call fails
+        CannotPayFailedCallFee,
+    }
+}
+
+decl_storage! {
+    trait Store for Module<T: Config> as FiatFilterModule where T: Debug {
+        /// Stores allowances given by sponsors to DIDs as (sponsor, DID) -> Sponsorship.
+        pub Sponsorships get(fn sponsorship): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) Did => Option<Sponsorship<BalanceOf<T>, T::BlockNumber>>;
     }
 }
 
@@ -75,11 +128,46 @@ decl_module! {
         // Errors must be initialized if they are used by the pallet.
         type Error = Error<T>;
 
+        fn deposit_event() = default;
+
         /// Execute a Call. Must be a core_mods call (DID, Blob, Anchor, Revoke/Unrevoke, Attest)
         #[weight = 10_000]
         pub fn execute_call(origin, call: Box<<T as Config>::Call>) -> DispatchResultWithPostInfo {
             Ok(Self::execute_call_(origin, &call)?)
         }
+
+        /// Execute a call authorized by a DID paying its fee from the allowance given to this DID by `sponsor`.
+        /// The sponsor pays only for the successfully executed calls, the fee of a failed call is charged from
+        /// the sender, so a sender which can't afford it is rejected before dispatching the call. Only some of the calls authorized by a single DID can be sponsored, see `sponsored_did`.
+        /// The weight is the weight of the call with the reads and writes of the sponsorship on top of it.
+        #[weight = <Module<T>>::sponsored_call_weight(call)]
+        pub fn execute_sponsored_call(origin, sponsor: T::AccountId, call: Box<<T as Config>::Call>) -> DispatchResultWithPostInfo {
+            Ok(Self::execute_sponsored_call_(origin, sponsor, &call)?)
+        }
+
+        /// Give each of the supplied DIDs an allowance of `allowance` which can be used until `expires_at` block
+        /// (inclusive) to pay the fees of its actions. The allowances are reserved from the sender's balance and
+        /// replace the allowances previously given by the sender to these DIDs.
+        #[weight = T::DbWeight::get().reads_writes(dids.len() as Weight + 1, dids.len() as Weight + 1)]
+        pub fn set_sponsorship(
+            origin,
+            dids: BTreeSet<Did>,
+            allowance: BalanceOf<T>,
+            expires_at: T::BlockNumber,
+        ) -> DispatchResult {
+            let sponsor = ensure_signed(origin)?;
+
+            Self::set_sponsorship_(sponsor, dids, allowance, expires_at)
+        }
+
+        /// Remove allowances given by the sender to the supplied DIDs unreserving their remaining amounts. Expired
+        /// allowances have to be removed this way as well.
+        #[weight = T::DbWeight::get().reads_writes(dids.len() as Weight + 1, dids.len() as Weight + 1)]
+        pub fn remove_sponsorship(origin, dids: BTreeSet<Did>) -> DispatchResult {
+            let sponsor = ensure_signed(origin)?;
+
+            Self::remove_sponsorship_(sponsor, dids)
+        }
     }
 }
 
@@ -99,6 +187,7 @@ pub mod fiat_rate {
     pub const PRICE_ANCHOR_OP_PER_BYTE: u32 = 3438;
     pub const PRICE_REVOKE_REGISTRY_CREATE: u32 = 130_000_000;
     pub const PRICE_REVOKE_REGISTRY_REMOVE: u32 = 170_000_000;
+    pub const PRICE_REVOKE_REGISTRY_POLICY_UPDATE: u32 = PRICE_REVOKE_REGISTRY_CREATE;
     pub const PRICE_REVOKE_OP_CONST_FACTOR: u32 = 150_000_000;
     pub const PRICE_REVOKE_PER_REVOCATION: u32 = 30_000_000;
     pub const PRICE_BLOB_OP_BASE: u32 = 110_000_000;
@@ -117,10 +206,21 @@ impl<T: Config + Debug> Module<T> {
             Some(did::Call::new_offchain(_, _)) => return Ok(PRICE_OFFCHAIN_DID_CREATE),
             // TODO: This needs to be revisited as it should depend on the number of keys and controllers
             Some(did::Call::new_onchain(_, _, __)) => return Ok(PRICE_ONCHAIN_DID_CREATE),
+            // TODO: This needs to be revisited as it should depend on the number of keys and controllers
+            Some(did::Call::convert_offchain_to_onchain(_, _, _)) => {
+                return Ok(PRICE_ONCHAIN_DID_CREATE)
+            }
             // TODO: This needs to be revisited as it should depend on the number of keys
             Some(did::Call::add_keys(_, _)) => return Ok(PRICE_DID_KEY_UPDATE),
             // TODO: This needs to be revisited as it should depend on the number of keys
+            Some(did::Call::rotate_keys(_, _)) => return Ok(PRICE_DID_KEY_UPDATE),
+            // TODO: This needs to be revisited as it should depend on the number of keys
             Some(did::Call::add_controllers(_, _)) => return Ok(PRICE_DID_KEY_UPDATE),
+            Some(did::Call::initiate_recovery(_, _)) => return Ok(PRICE_DID_KEY_UPDATE),
+            Some(did::Call::execute_recovery(_)) => return Ok(PRICE_DID_KEY_UPDATE),
+            Some(did::Call::batch_update(batch, _)) => {
+                return Ok(PRICE_DID_KEY_UPDATE.saturating_mul(batch.operations.len() as u32))
+            }
             Some(did::Call::remove_offchain_did(_)) => return Ok(PRICE_OFFCHAIN_DID_REMOVE),
             Some(did::Call::remove_onchain_did(_, _)) => return Ok(PRICE_ONCHAIN_DID_REMOVE),
             _ => {}
@@ -139,15 +239,32 @@ impl<T: Config + Debug> Module<T> {
                 );
                 return Ok(price);
             }
+            Some(blob::Call::new_with_deadline(blob, _sig)) => {
+                let size: u32 = blob.action.blob.blob.len() as u32;
+                let price = PRICE_BLOB_OP_BASE.saturating_add(
+                    (size.div_ceil(100)).saturating_mul(PRICE_ATTEST_OP_PER_100_BYTES),
+                );
+                return Ok(price);
+            }
             _ => {}
         };
         match call.is_sub_type() {
-            Some(revoke::Call::new_registry(_add_registry)) => {
+            Some(revoke::Call::new_registry(_add_registry))
+            | Some(revoke::Call::new_status_list_registry(_add_registry)) => {
                 return Ok(PRICE_REVOKE_REGISTRY_CREATE)
             }
-            Some(revoke::Call::remove_registry(_rm, _proof)) => {
+            Some(revoke::Call::remove_registry(_rm, _proof))
+            | Some(revoke::Call::remove_registry_with_deadline(_rm, _proof)) => {
                 return Ok(PRICE_REVOKE_REGISTRY_REMOVE)
             }
+            Some(revoke::Call::update_registry_policy(_update, _proof)) => {
+                return Ok(PRICE_REVOKE_REGISTRY_POLICY_UPDATE)
+            }
+            Some(revoke::Call::update_status_list(update, _proof)) => {
+                return Ok(PRICE_REVOKE_PER_REVOCATION
+                    .saturating_mul(update.ranges.len() as u32)
+                    .saturating_add(PRICE_REVOKE_OP_CONST_FACTOR));
+            }
 
             Some(revoke::Call::revoke(revocation, _proof)) => {
                 return Ok(PRICE_REVOKE_PER_REVOCATION
@@ -159,6 +276,26 @@ impl<T: Config + Debug> Module<T> {
                     .saturating_mul(unrevoke.revoke_ids.len() as u32)
                     .saturating_add(PRICE_REVOKE_OP_CONST_FACTOR));
             }
+            Some(revoke::Call::revoke_with_deadline(revocation, _proof)) => {
+                return Ok(PRICE_REVOKE_PER_REVOCATION
+                    .saturating_mul(revocation.action.revoke_ids.len() as u32)
+                    .saturating_add(PRICE_REVOKE_OP_CONST_FACTOR));
+            }
+            Some(revoke::Call::unrevoke_with_deadline(unrevoke, _proof)) => {
+                return Ok(PRICE_REVOKE_PER_REVOCATION
+                    .saturating_mul(unrevoke.action.revoke_ids.len() as u32)
+                    .saturating_add(PRICE_REVOKE_OP_CONST_FACTOR));
+            }
+            Some(revoke::Call::set_revocation_status(set_status, _proof)) => {
+                return Ok(PRICE_REVOKE_PER_REVOCATION
+                    .saturating_mul(set_status.revoke_ids.len() as u32)
+                    .saturating_add(PRICE_REVOKE_OP_CONST_FACTOR));
+            }
+            Some(revoke::Call::lift_suspension(lift, _proof)) => {
+                return Ok(PRICE_REVOKE_PER_REVOCATION
+                    .saturating_mul(lift.revoke_ids.len() as u32)
+                    .saturating_add(PRICE_REVOKE_OP_CONST_FACTOR));
+            }
             _ => {}
         };
         match call.is_sub_type() {
@@ -214,6 +351,16 @@ impl<T: Config + Debug> Module<T> {
         Ok(())
     }
 
+    /// Ensures that the fee can be charged from the account leaving it alive.
+    fn ensure_can_pay_fees_(who: &T::AccountId, amount: BalanceOf<T>) -> Result<(), DispatchError> {
+        let new_balance = T::Currency::free_balance(who)
+            .checked_sub(&amount)
+            .filter(|balance| *balance >= T::Currency::minimum_balance())
+            .ok_or(Error::<T>::CannotPayFailedCallFee)?;
+
+        T::Currency::ensure_can_withdraw(who, amount, WithdrawReasons::FEE, new_balance)
+    }
+
     fn execute_call_(origin: T::Origin, call: &<T as Config>::Call) -> DispatchResultWithPostInfo {
         // check signature before charging any fees
         let sender = ensure_signed(origin.clone())?;
@@ -252,4 +399,207 @@ impl<T: Config + Debug> Module<T> {
             }),
         };
     }
+
+    /// Returns the DID whose allowance pays the fee of the given call. Updates of an on-chain DID are paid from
+    /// the allowance of the updated DID while other calls are paid from the allowance of their signer.
+    ///
+    /// As the sponsor pays the fee which `execute_call` would charge, only the DID-signed calls priced by this
+    /// module can be sponsored. These are `add_keys`, `rotate_keys`, `add_controllers`, `batch_update` and
+    /// `remove_onchain_did` of the DID module, the creation of a blob, the calls of the revoke module taking a proof
+    /// and the attestations. Other calls, including the remaining DID updates such as service endpoints, recovery
+    /// and account links, and the BBS+ and accumulator calls fail with `UnsupportedSponsoredCall`. Calls authorized
+    /// by several DIDs or by none fail with `NotSponsorable`.
+    pub fn sponsored_did(call: &<T as Config>::Call) -> Result<Did, DispatchError> {
+        match call.is_sub_type() {
+            Some(did::Call::add_keys(keys, _)) => return Ok(keys.target()),
+            Some(did::Call::rotate_keys(keys, _)) => return Ok(keys.target()),
+            Some(did::Call::add_controllers(controllers, _)) => return Ok(controllers.target()),
+            Some(did::Call::batch_update(batch, _)) => return Ok(batch.target()),
+            Some(did::Call::remove_onchain_did(removal, _)) => return Ok(removal.target()),
+            _ => {}
+        };
+        match call.is_sub_type() {
+            Some(blob::Call::new(_, sig)) | Some(blob::Call::new_with_deadline(_, sig)) => {
+                return Ok(sig.did.into())
+            }
+            _ => {}
+        };
+        match call.is_sub_type() {
+            Some(revoke::Call::revoke(_, proof))
+            | Some(revoke::Call::unrevoke(_, proof))
+            | Some(revoke::Call::remove_registry(_, proof))
+            | Some(revoke::Call::update_registry_policy(_, proof))
+            | Some(revoke::Call::update_status_list(_, proof))
+            | Some(revoke::Call::set_revocation_status(_, proof))
+            | Some(revoke::Call::lift_suspension(_, proof)) => {
+                return match &proof[..] {
+                    [DidSigs { sig, .. }] => Ok(sig.did),
+                    _ => fail!(Error::<T>::NotSponsorable),
+                }
+            }
+            Some(revoke::Call::revoke_with_deadline(_, proof))
+            | Some(revoke::Call::unrevoke_with_deadline(_, proof))
+            | Some(revoke::Call::remove_registry_with_deadline(_, proof)) => {
+                return match &proof[..] {
+                    [sig] => Ok(sig.did),
+                    _ => fail!(Error::<T>::NotSponsorable),
+                }
+            }
+            _ => {}
+        };
+        match call.is_sub_type() {
+            Some(attest::Call::set_claim(_, sig)) => return Ok(sig.did.into()),
+            _ => {}
+        }
+
+        fail!(Error::<T>::UnsupportedSponsoredCall)
+    }
+
+    /// Weight of `execute_sponsored_call` dispatching the given call. The reads are of the sponsorship, the price
+    /// and the sponsor's balance while the writes are of the sponsorship and the sponsor's balance.
+    fn sponsored_call_weight(call: &<T as Config>::Call) -> Weight {
+        call.get_dispatch_info()
+            .weight
+            .saturating_add(T::DbWeight::get().reads_writes(3, 2))
+    }
+
+    fn execute_sponsored_call_(
+        origin: T::Origin,
+        sponsor: T::AccountId,
+        call: &<T as Config>::Call,
+    ) -> DispatchResultWithPostInfo {
+        let sender = ensure_signed(origin.clone())?;
+        let call_info = call.get_dispatch_info();
+
+        let did = Self::sponsored_did(call)?;
+        let Sponsorship {
+            remaining,
+            expires_at,
+        } = Self::sponsorship(&sponsor, did).ok_or(Error::<T>::NoSponsorship)?;
+        ensure!(
+            expires_at >= <system::Module<T>>::block_number(),
+            Error::<T>::SponsorshipExpired
+        );
+
+        let (fee_dock, weight_get_price) = Self::get_call_fee_dock_(&call)?;
+        ensure!(fee_dock <= remaining, Error::<T>::AllowanceExceeded);
+        Self::ensure_can_pay_fees_(&sender, fee_dock)?;
+
+        // The actual weight of the call is returned along with the weight of the sponsorship
+        let post_info = |call_post_info: PostDispatchInfo| PostDispatchInfo {
+            actual_weight: Some(
+                call_post_info
+                    .calc_actual_weight(&call_info)
+                    .saturating_add(weight_get_price)
+                    .saturating_add(T::DbWeight::get().reads_writes(2, 2)),
+            ),
+            pays_fee: Pays::No,
+        };
+        match call.clone().dispatch_bypass_filter(origin) {
+            Ok(call_post_info) => {
+                let remaining = remaining.saturating_sub(fee_dock);
+                let _ = T::Currency::slash_reserved(&sponsor, fee_dock);
+                Sponsorships::<T>::insert(
+                    &sponsor,
+                    did,
+                    Sponsorship {
+                        remaining,
+                        expires_at,
+                    },
+                );
+
+                Self::deposit_event(RawEvent::SponsoredFeePaid(
+                    sponsor, did, fee_dock, remaining,
+                ));
+                Ok(post_info(call_post_info))
+            }
+            Err(e) => {
+                // The sender pays for the failed calls so that the allowance can't be drained by submitting
+                // calls which weren't authorized by the DID. In case the call spent the sender's balance before
+                // failing, the transaction fee is paid instead. The error of the call is returned in both cases.
+                let pays_fee = match Self::charge_fees_(sender, fee_dock) {
+                    Ok(()) => Pays::No,
+                    Err(_) => Pays::Yes,
+                };
+
+                Err(DispatchErrorWithPostInfo {
+                    post_info: PostDispatchInfo {
+                        pays_fee,
+                        ..post_info(e.post_info)
+                    },
+                    error: e.error,
+                })
+            }
+        }
+    }
+
+    fn set_sponsorship_(
+        sponsor: T::AccountId,
+        dids: BTreeSet<Did>,
+        allowance: BalanceOf<T>,
+        expires_at: T::BlockNumber,
+    ) -> DispatchResult {
+        ensure!(!dids.is_empty(), Error::<T>::NoDidsProvided);
+        ensure!(
+            expires_at >= <system::Module<T>>::block_number(),
+            Error::<T>::SponsorshipExpired
+        );
+
+        let reserved = dids
+            .iter()
+            .filter_map(|did| Self::sponsorship(&sponsor, did))
+            .fold(
+                BalanceOf::<T>::zero(),
+                |acc, Sponsorship { remaining, .. }| acc.saturating_add(remaining),
+            );
+        let to_reserve = allowance.saturating_mul((dids.len() as u32).into());
+        if to_reserve > reserved {
+            T::Currency::reserve(&sponsor, to_reserve - reserved)?;
+        } else {
+            T::Currency::unreserve(&sponsor, reserved - to_reserve);
+        }
+
+        for did in dids {
+            Sponsorships::<T>::insert(
+                &sponsor,
+                did,
+                Sponsorship {
+                    remaining: allowance,
+                    expires_at,
+                },
+            );
+
+            Self::deposit_event(RawEvent::SponsorshipSet(
+                sponsor.clone(),
+                did,
+                allowance,
+                expires_at,
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn remove_sponsorship_(sponsor: T::AccountId, dids: BTreeSet<Did>) -> DispatchResult {
+        ensure!(!dids.is_empty(), Error::<T>::NoDidsProvided);
+        ensure!(
+            dids.iter()
+                .all(|did| Sponsorships::<T>::contains_key(&sponsor, did)),
+            Error::<T>::NoSponsorship
+        );
+
+        for did in dids {
+            if let Some(Sponsorship { remaining, .. }) = Sponsorships::<T>::take(&sponsor, did) {
+                T::Currency::unreserve(&sponsor, remaining);
+
+                Self::deposit_event(RawEvent::SponsorshipRemoved(
+                    sponsor.clone(),
+                    did,
+                    remaining,
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
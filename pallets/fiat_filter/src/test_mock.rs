@@ -1,8 +1,12 @@
 use crate as fiat_filter;
 pub use crate::{Config, Error, Module};
 use codec::Encode;
-use core_mods::StateChange;
-use core_mods::{anchor, attest, blob, did, revoke};
+use core_mods::{
+    anchor, attest, blob,
+    did::{self, Did, DidKey, DidSignature},
+    keys_and_sigs::{PublicKey, SigValue},
+    revoke, util, ToStateChange,
+};
 use frame_support::assert_ok;
 use frame_support::dispatch::DispatchResultWithPostInfo;
 use frame_support::parameter_types;
@@ -35,13 +39,13 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Module, Call, Config, Storage, Event<T>},
-        Balances: pallet_balances::{Module, Call, Storage},
+        Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
         DIDMod: did::{Module, Call, Storage, Event, Config},
-        RevokeMod: revoke::{Module, Call, Storage},
+        RevokeMod: revoke::{Module, Call, Storage, Event},
         BlobMod: blob::{Module, Call, Storage},
         AnchorMod: anchor::{Module, Call, Storage, Event<T>},
         AttestMod: attest::{Module, Call, Storage},
-        FiatFilterModule: fiat_filter::{Module, Call},
+        FiatFilterModule: fiat_filter::{Module, Call, Storage, Event<T>},
     }
 );
 
@@ -52,6 +56,7 @@ impl Config for TestRt {
     type PriceProvider = TestPriceProvider;
     type Call = Call;
     type Currency = Balances;
+    type Event = Event;
     type MinDockFiatRate = MinDockFiatRate;
 }
 
@@ -69,7 +74,7 @@ impl system::Config for TestRt {
     type AccountId = u64;
     type Lookup = IdentityLookup<Self::AccountId>;
     type Header = Header;
-    type Event = ();
+    type Event = Event;
     type BlockHashCount = BlockHashCount;
     type DbWeight = ();
     type BlockWeights = ();
@@ -89,30 +94,95 @@ parameter_types! {
 impl pallet_balances::Config for TestRt {
     type MaxLocks = ();
     type Balance = u64;
-    type Event = ();
+    type Event = Event;
     type DustRemoval = ();
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
 }
-impl anchor::Trait for TestRt {
-    type Event = ();
+impl anchor::Config for TestRt {
+    type Event = Event;
+}
+
+parameter_types! {
+    pub const MaxDidDocRefSize: u16 = 128;
+    pub const DidDocRefPerByteWeight: Weight = 10;
+    pub const MaxServiceEndpointIdSize: u16 = 256;
+    pub const ServiceEndpointIdPerByteWeight: Weight = 10;
+    pub const MaxServiceEndpointOrigins: u16 = 20;
+    pub const MaxServiceEndpointOriginSize: u16 = 256;
+    pub const ServiceEndpointOriginPerByteWeight: Weight = 10;
+    pub const MaxServiceEndpointTypes: u16 = 4;
+    pub const MaxServiceEndpointTypeSize: u16 = 64;
+    pub const ServiceEndpointTypePerByteWeight: Weight = 10;
+    pub const MaxServiceEndpointProperties: u16 = 4;
+    pub const MaxServiceEndpointPropertyValues: u16 = 4;
+    pub const MaxServiceEndpointPropertySize: u16 = 128;
+    pub const ServiceEndpointPropertyPerByteWeight: Weight = 10;
+    pub const MaxRecoveryGuardians: u16 = 5;
+    pub const MinRecoveryDelay: u64 = 5;
+    pub const MaxActionLifetime: u64 = 10;
+    pub const MaxReplayIdRemovalsPerBlock: u32 = 4;
+    pub const StorageItemDeposit: u64 = 10;
+    pub const StorageByteDeposit: u64 = 1;
+    pub const MaxControllerDepth: u16 = 4;
+    pub const PreventControllerCycles: bool = true;
+    pub const MaxControllerGraphReads: u32 = 16;
+    pub const MaxDidDocumentItems: u32 = 32;
+    pub const DidVersionRetentionPeriod: u64 = 10;
+    pub WebAuthnRpIdHash: [u8; 32] = sp_io::hashing::sha2_256(b"dock.io");
 }
-impl did::Trait for TestRt {
-    type Event = ();
+impl did::Config for TestRt {
+    type Event = Event;
+    type MaxDidDocRefSize = MaxDidDocRefSize;
+    type DidDocRefPerByteWeight = DidDocRefPerByteWeight;
+    type MaxServiceEndpointIdSize = MaxServiceEndpointIdSize;
+    type ServiceEndpointIdPerByteWeight = ServiceEndpointIdPerByteWeight;
+    type MaxServiceEndpointOrigins = MaxServiceEndpointOrigins;
+    type MaxServiceEndpointOriginSize = MaxServiceEndpointOriginSize;
+    type ServiceEndpointOriginPerByteWeight = ServiceEndpointOriginPerByteWeight;
+    type MaxServiceEndpointTypes = MaxServiceEndpointTypes;
+    type MaxServiceEndpointTypeSize = MaxServiceEndpointTypeSize;
+    type ServiceEndpointTypePerByteWeight = ServiceEndpointTypePerByteWeight;
+    type MaxServiceEndpointProperties = MaxServiceEndpointProperties;
+    type MaxServiceEndpointPropertyValues = MaxServiceEndpointPropertyValues;
+    type MaxServiceEndpointPropertySize = MaxServiceEndpointPropertySize;
+    type ServiceEndpointPropertyPerByteWeight = ServiceEndpointPropertyPerByteWeight;
+    type MaxRecoveryGuardians = MaxRecoveryGuardians;
+    type MinRecoveryDelay = MinRecoveryDelay;
+    type MaxActionLifetime = MaxActionLifetime;
+    type MaxReplayIdRemovalsPerBlock = MaxReplayIdRemovalsPerBlock;
+    type Currency = Balances;
+    type StorageItemDeposit = StorageItemDeposit;
+    type StorageByteDeposit = StorageByteDeposit;
+    type MaxControllerDepth = MaxControllerDepth;
+    type PreventControllerCycles = PreventControllerCycles;
+    type MaxControllerGraphReads = MaxControllerGraphReads;
+    type MaxDidDocumentItems = MaxDidDocumentItems;
+    type DidVersionRetentionPeriod = DidVersionRetentionPeriod;
+    type WebAuthnRpIdHash = WebAuthnRpIdHash;
+}
+
+parameter_types! {
+    pub const MaxControllers: u32 = 15;
+    pub const MaxStatusListSize: u32 = 2 * revoke::MIN_STATUS_LIST_SIZE;
+}
+impl revoke::Config for TestRt {
+    type Event = Event;
+    type MaxControllers = MaxControllers;
+    type MaxStatusListSize = MaxStatusListSize;
 }
-impl revoke::Trait for TestRt {}
 
 parameter_types! {
     pub const MaxBlobSize: u32 = 1024;
     pub const StorageWeight: Weight = 1100;
 }
-impl blob::Trait for TestRt {
+impl blob::Config for TestRt {
     type MaxBlobSize = MaxBlobSize;
     type StorageWeight = StorageWeight;
 }
 
-impl attest::Trait for TestRt {
+impl attest::Config for TestRt {
     type StorageWeight = StorageWeight;
 }
 
@@ -144,16 +214,26 @@ impl common::traits::PriceProvider for TestPriceProvider {
 
 pub const ALICE: u64 = 100;
 pub const BOB: u64 = 200;
+pub const SPONSOR: u64 = 300;
+pub const INITIAL_BALANCE: u64 = 100_000_000_000;
 // Build genesis storage according to the mock runtime.
 pub fn ext() -> sp_io::TestExternalities {
-    let t = system::GenesisConfig::default()
+    let mut t = system::GenesisConfig::default()
         .build_storage::<TestRt>()
         .unwrap();
-    let mut ext = sp_io::TestExternalities::new(t);
+    pallet_balances::GenesisConfig::<TestRt> {
+        balances: vec![
+            (ALICE, INITIAL_BALANCE),
+            (BOB, INITIAL_BALANCE),
+            (SPONSOR, INITIAL_BALANCE),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
 
-    ext.execute_with(|| {
-        let _ = <TestRt as Config>::Currency::deposit_creating(&ALICE, 100_000_000_000);
-    });
+    let mut ext = sp_io::TestExternalities::new(t);
+    // system module will not store events if block_number == 0
+    ext.execute_with(|| System::set_block_number(1));
     ext
 }
 
@@ -168,27 +248,48 @@ pub fn block_no() -> u64 {
 // Create did for `did`. Return the randomly generated signing key.
 // The did public key is controlled by some non-existent account (normally a security
 // concern), but that doesn't matter for our purposes.
-pub fn create_did(origin: u64, did: did::Did) -> sr25519::Pair {
+pub fn create_did(origin: u64, did: Did) -> sr25519::Pair {
     let kp = gen_kp();
-    let pubkey_bytes = did::Bytes32 {
-        value: kp.public().0,
-    };
-    let didpubkey = did::PublicKey::Sr25519(pubkey_bytes);
-    let key_detail = did::KeyDetail::new(did, didpubkey);
-    did::Module::<TestRt>::new(Origin::signed(origin), did, key_detail).unwrap();
+    did::Module::<TestRt>::new_onchain(
+        Origin::signed(origin),
+        did,
+        vec![DidKey::new_with_all_relationships(PublicKey::sr25519(
+            kp.public().0,
+        ))],
+        Default::default(),
+    )
+    .unwrap();
     kp
 }
 /// create a did with a random id and random signing key
-pub fn newdid(origin: u64) -> (did::Did, sr25519::Pair) {
-    let d: did::Did = rand::random();
+pub fn newdid(origin: u64) -> (Did, sr25519::Pair) {
+    let d = Did(rand::random());
     (d, create_did(origin, d))
 }
+/// get the nonce which has to be used by the next action of the DID
+pub fn next_nonce(did: &Did) -> u64 {
+    did::Module::<TestRt>::onchain_did_details(did)
+        .unwrap()
+        .next_nonce()
+}
 
-pub fn sign(payload: &StateChange, keypair: &sr25519::Pair) -> did::SigValue {
-    did::SigValue::Sr25519(did::Bytes64 {
-        value: keypair.sign(&payload.encode()).0,
+pub fn sign<A: ToStateChange<TestRt>>(payload: &A, keypair: &sr25519::Pair) -> SigValue {
+    SigValue::Sr25519(util::Bytes64 {
+        value: keypair.sign(&payload.to_state_change().encode()).0,
     })
 }
+/// sign the payload with the first key of the DID
+pub fn did_sig<A: ToStateChange<TestRt>, D: Into<Did>>(
+    payload: &A,
+    keypair: &sr25519::Pair,
+    did: D,
+) -> DidSignature<D> {
+    DidSignature {
+        did,
+        key_id: 1u32.into(),
+        sig: sign(payload, keypair),
+    }
+}
 
 /// create a random byte array with set len
 pub fn random_bytes(len: usize) -> Vec<u8> {
@@ -197,10 +298,11 @@ pub fn random_bytes(len: usize) -> Vec<u8> {
     ret
 }
 
+// Total balance is compared as the storage deposits of the calls are reserved rather than charged.
 pub fn measure_fees(call: Call) -> (u32, DispatchResultWithPostInfo) {
-    let balance_pre = <TestRt as Config>::Currency::free_balance(ALICE);
+    let balance_pre = <TestRt as Config>::Currency::total_balance(&ALICE);
     let executed = FiatFilterModule::execute_call(Origin::signed(ALICE), Box::new(call.clone()));
-    let balance_post = <TestRt as Config>::Currency::free_balance(ALICE);
+    let balance_post = <TestRt as Config>::Currency::total_balance(&ALICE);
     let fee_microdock = (balance_pre - balance_post) as u32;
     return (fee_microdock, executed);
 }
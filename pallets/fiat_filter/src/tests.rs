@@ -1,68 +1,52 @@
 use crate::fiat_rate::*;
 use crate::test_mock::*;
-use codec::Encode;
 use common::arith_utils::DivCeil;
-use core_mods::StateChange;
+use core_mods::util::WithNonce;
 use core_mods::{anchor, attest, blob, did, revoke};
 use frame_support::traits::Currency;
 use frame_support::weights::Pays;
 use frame_support::{assert_noop, assert_ok};
 use frame_system::RawOrigin;
 use rand::random;
-use sp_core::{sr25519, Pair};
 
 mod tests_did_calls {
-    use sp_std::collections::btree_set::BTreeSet;
     use super::*;
-    use core_mods::keys_and_sigs::{PublicKey, SigValue};
-    use core_mods::util::Bytes32;
-    use did::{DidKey, DidRemoval, DID_BYTE_SIZE};
-
-    type DidMod = did::Module<TestRt>;
+    use core_mods::keys_and_sigs::PublicKey;
+    use did::{AddKeys, Controller, Did, DidKey, DidRemoval};
+    use sp_core::Pair;
+    use sp_std::collections::btree_set::BTreeSet;
 
     #[test]
     fn call_did_new() {
         ext().execute_with(|| {
-            let d: did::Did = rand::random();
+            let d = Did(rand::random());
             let kp = gen_kp();
             let key = DidKey::new_with_all_relationships(PublicKey::sr25519(kp.public().0));
 
-            let call = Call::DIDMod(did::Call::<TestRt>::new_onchain(d.clone(), vec![key], BTreeSet::new()));
+            let call = Call::DIDMod(did::Call::<TestRt>::new_onchain(
+                d.clone(),
+                vec![key],
+                BTreeSet::new(),
+            ));
             let expected_fees = PRICE_ONCHAIN_DID_CREATE / TestPriceProvider::get().unwrap();
             let (_fee_microdock, _executed) = exec_assert_fees(call, expected_fees);
         });
     }
     #[test]
-    fn call_did_update_key__OK() {
+    fn call_did_add_keys__OK() {
         ext().execute_with(|| {
-            let did_alice = [1; DID_BYTE_SIZE];
-            let (pair_1, _, _) = sr25519::Pair::generate_with_phrase(None);
-            let pk_1 = pair_1.public().0;
-            let key = DidKey::new_with_all_relationships(PublicKey::sr25519(pk_1));
-
-            // Add a DID
-            let new_res = DidMod::new_onchain(Origin::signed(ALICE), did_alice.clone(), vec![key], BTreeSet::new());
-            assert_ok!(new_res);
-
-            let (_current_detail, modified_in_block) = DidMod::get_key_detail(&did_alice).unwrap();
-
-            // Correctly update DID's key.
-            // Prepare a key update
-            let (pair_2, _, _) = sr25519::Pair::generate_with_phrase(None);
-            let pk_2 = pair_2.public().0;
-            let key_update = KeyUpdate::new(
-                did_alice.clone(),
-                PublicKey::Sr25519(Bytes32 { value: pk_2 }),
-                None,
-                modified_in_block as u32,
-            );
-            let sig_value = pair_1
-                .sign(&StateChange::KeyUpdate(key_update.clone()).encode())
-                .0;
-            let sig = SigValue::Sr25519(did::Bytes64 { value: sig_value });
+            let (did_alice, kp) = newdid(ALICE);
+            let key = DidKey::new_with_all_relationships(PublicKey::sr25519(gen_kp().public().0));
+
+            // Signing with the current key to add the new key
+            let add_keys = AddKeys {
+                did: did_alice,
+                keys: vec![key],
+                nonce: next_nonce(&did_alice),
+            };
+            let sig = did_sig(&add_keys, &kp, Controller(did_alice));
 
-            // Signing with the current key (`pair_1`) to update to the new key (`pair_2`)
-            let call = Call::DIDMod(did::Call::<TestRt>::update_key(key_update, sig));
+            let call = Call::DIDMod(did::Call::<TestRt>::add_keys(add_keys, vec![sig]));
             let expected_fees = PRICE_DID_KEY_UPDATE / TestPriceProvider::get().unwrap();
             let (_fee_microdock, _executed) = exec_assert_fees(call, expected_fees);
         });
@@ -71,16 +55,17 @@ mod tests_did_calls {
     fn call_did_remove() {
         ext().execute_with(|| {
             let (did_alice, kp) = newdid(ALICE);
-            let blockno = block_no() as u32;
 
-            let to_remove = DidRemoval::new(did_alice.clone(), blockno);
-            let sig = SigValue::Sr25519(Bytes64 {
-                value: kp
-                    .sign(&StateChange::DIDRemoval(to_remove.clone()).encode())
-                    .0,
-            });
+            let to_remove = DidRemoval {
+                did: did_alice,
+                nonce: next_nonce(&did_alice),
+            };
+            let sig = did_sig(&to_remove, &kp, Controller(did_alice));
 
-            let call = Call::DIDMod(did::Call::<TestRt>::remove(to_remove, sig));
+            let call = Call::DIDMod(did::Call::<TestRt>::remove_onchain_did(
+                to_remove,
+                vec![sig],
+            ));
             let expected_fees = PRICE_ONCHAIN_DID_REMOVE / TestPriceProvider::get().unwrap();
             let (_fee_microdock, _executed) = exec_assert_fees(call, expected_fees);
         });
@@ -102,11 +87,10 @@ fn call_anchor_deploy() {
 
 mod test_attest_calls {
     use super::*;
+    use attest::{Attestation, Attester, SetAttestationClaim};
 
     #[test]
     fn call_attest__iri_none() {
-        use attest::Attestation;
-
         ext().execute_with(|| {
             let (attester, kp) = newdid(ALICE);
             let att = Attestation {
@@ -115,9 +99,13 @@ mod test_attest_calls {
             };
             let size_attested = att.iri.clone().unwrap_or([1].to_vec()).len() as u32;
             assert_eq!(size_attested, 1);
-            let sig = sign(&StateChange::Attestation((attester, att.clone())), &kp);
+            let claim = SetAttestationClaim {
+                attest: att,
+                nonce: next_nonce(&attester),
+            };
+            let sig = did_sig(&claim, &kp, Attester(attester));
 
-            let call = Call::AttestMod(attest::Call::<TestRt>::set_claim(attester, att, sig));
+            let call = Call::AttestMod(attest::Call::<TestRt>::set_claim(claim, sig));
             let expected_fees_nusd =
                 PRICE_ATTEST_OP_BASE + size_attested.div_ceil(100) * PRICE_ATTEST_OP_PER_100_BYTES;
             let expected_fees_microdock = expected_fees_nusd / TestPriceProvider::get().unwrap();
@@ -127,8 +115,6 @@ mod test_attest_calls {
 
     #[test]
     fn call_attest__iri_some() {
-        use attest::Attestation;
-
         ext().execute_with(|| {
             let (attester, kp) = newdid(ALICE);
             let att = Attestation {
@@ -137,9 +123,13 @@ mod test_attest_calls {
             };
             let size_attested = att.iri.clone().unwrap_or([1].to_vec()).len() as u32;
             assert_eq!(size_attested, 18);
-            let sig = sign(&StateChange::Attestation((attester, att.clone())), &kp);
+            let claim = SetAttestationClaim {
+                attest: att,
+                nonce: next_nonce(&attester),
+            };
+            let sig = did_sig(&claim, &kp, Attester(attester));
 
-            let call = Call::AttestMod(attest::Call::<TestRt>::set_claim(attester, att, sig));
+            let call = Call::AttestMod(attest::Call::<TestRt>::set_claim(claim, sig));
             let expected_fees_nusd =
                 PRICE_ATTEST_OP_BASE + size_attested.div_ceil(100) * PRICE_ATTEST_OP_PER_100_BYTES;
             let expected_fees_microdock = expected_fees_nusd / TestPriceProvider::get().unwrap();
@@ -148,21 +138,28 @@ mod test_attest_calls {
     }
 }
 
+/// Creates a call adding a blob of `size` random bytes authored by `author` and signed with `kp`.
+fn new_blob_call(author: did::Did, kp: &sp_core::sr25519::Pair, size: usize) -> Call {
+    use blob::{AddBlob, Blob, BlobOwner};
+
+    let blob = AddBlob {
+        blob: Blob {
+            id: rand::random(),
+            blob: random_bytes(size),
+        },
+        nonce: next_nonce(&author),
+    };
+    let sig = did_sig(&blob, kp, BlobOwner(author));
+
+    Call::BlobMod(blob::Call::<TestRt>::new(blob, sig))
+}
+
 #[test]
 fn call_blob_new() {
-    use blob::{Blob, BlobId};
     ext().execute_with(|| {
-        let id: BlobId = rand::random();
-        let noise = random_bytes(999);
         let (author, author_kp) = newdid(ALICE);
-        let blob = Blob {
-            id,
-            blob: noise.clone(),
-            author,
-        };
-        let sig = sign(&StateChange::Blob(blob.clone()), &author_kp);
-
-        let call = Call::BlobMod(blob::Call::<TestRt>::new(blob, sig));
+
+        let call = new_blob_call(author, &author_kp, 999);
         let blob_size = 999;
         let expected_fees_nusd =
             PRICE_BLOB_OP_BASE + blob_size.div_ceil(100) * PRICE_BLOB_OP_PER_100_BYTES;
@@ -173,33 +170,57 @@ fn call_blob_new() {
 
 mod tests_revoke_calls {
     use super::*;
+    use core::marker::PhantomData;
     use did::Did;
-    use revoke::{Policy, Registry, RegistryId, RemoveRegistry, Revoke, RevokeId, UnRevoke};
+    use revoke::{
+        AddRegistry, DidSigs, Policy, Registry, RegistryId, RemoveRegistryRaw, RevokeId, RevokeRaw,
+        UnRevokeRaw,
+    };
+    use sp_core::sr25519;
 
     pub const REV_ID: RevokeId = [7u8; 32];
 
     pub fn policy_oneof(dids: &[Did]) -> Policy {
         Policy::OneOf(dids.iter().cloned().collect())
     }
-    pub fn new_reg(did: Did) -> (RegistryId, Registry) {
-        pub const REG_ID: RegistryId = [3u8; 32];
-        let reg = Registry {
-            policy: policy_oneof(&[did]),
-            add_only: false,
-        };
-        let created = RevokeMod::new_registry(Origin::signed(ALICE), REG_ID, reg.clone());
+    pub fn new_reg(policy: Policy) -> RegistryId {
+        let id = random();
+        let created = RevokeMod::new_registry(
+            Origin::signed(ALICE),
+            AddRegistry {
+                id,
+                registry: Registry {
+                    policy,
+                    add_only: false,
+                },
+            },
+        );
         assert_ok!(created);
-        (REG_ID, reg)
+        id
+    }
+    /// Signs the action with the next nonce of the DID
+    pub fn did_sigs<A>(action: &A, did: Did, kp: &sr25519::Pair) -> DidSigs<TestRt>
+    where
+        A: Clone,
+        WithNonce<TestRt, A>: core_mods::ToStateChange<TestRt>,
+    {
+        let nonce = next_nonce(&did);
+        let sig = did_sig(
+            &WithNonce::<TestRt, _>::new_with_nonce(action.clone(), nonce),
+            kp,
+            did,
+        );
+
+        DidSigs { sig, nonce }
     }
 
     #[test]
     fn call_revoke_revoke() {
         ext().execute_with(|| {
             let (did_alice, kp_alice) = newdid(ALICE);
-            let (reg_id, _reg) = new_reg(did_alice);
+            let reg_id = new_reg(policy_oneof(&[did_alice]));
 
             let cases: &[&[RevokeId]] = &[
-                &[],
                 &[random()],
                 &[random(), random()],
                 &[random(), random(), random()],
@@ -207,17 +228,13 @@ mod tests_revoke_calls {
                 &[REV_ID], // Test idempotence, step 2
             ];
             for ids in cases {
-                let revoke = Revoke {
+                let revoke = RevokeRaw {
                     registry_id: reg_id,
                     revoke_ids: ids.iter().cloned().collect(),
-                    last_modified: block_no() as u32,
+                    _marker: PhantomData,
                 };
                 let revocation_size = ids.len() as u32;
-                let proof = std::iter::once((
-                    did_alice,
-                    sign(&StateChange::Revoke(revoke.clone()), &kp_alice),
-                ))
-                .collect();
+                let proof = vec![did_sigs(&revoke, did_alice, &kp_alice)];
 
                 let call = Call::RevokeMod(revoke::Call::<TestRt>::revoke(revoke, proof));
                 let expected_fees_nusd =
@@ -239,11 +256,9 @@ mod tests_revoke_calls {
     fn call_revoke_unrevoke() {
         ext().execute_with(|| {
             let (did_alice, kp_alice) = newdid(ALICE);
-            let (reg_id, _reg) = new_reg(did_alice);
-            let last_modified = block_no() as u32;
+            let reg_id = new_reg(policy_oneof(&[did_alice]));
 
             let cases: &[&[RevokeId]] = &[
-                &[],
                 &[random()],
                 &[random(), random()],
                 &[random(), random(), random()],
@@ -254,40 +269,32 @@ mod tests_revoke_calls {
                 for id in ids.iter() {
                     // assert not revoked
                     let revoke_status = RevokeMod::get_revocation_status(reg_id, id);
-                    assert_eq!(revoke_status, None);
+                    assert!(revoke_status.is_none());
                 }
 
                 // 1. revoke
-                let revoke = Revoke {
+                let revoke = RevokeRaw {
                     registry_id: reg_id,
                     revoke_ids: ids.iter().cloned().collect(),
-                    last_modified,
+                    _marker: PhantomData,
                 };
-                let proof = std::iter::once((
-                    did_alice,
-                    sign(&StateChange::Revoke(revoke.clone()), &kp_alice),
-                ))
-                .collect();
+                let proof = vec![did_sigs(&revoke, did_alice, &kp_alice)];
                 let revoke_res = RevokeMod::revoke(Origin::signed(ALICE), revoke.clone(), proof);
                 assert_ok!(revoke_res);
                 // assert revoked
                 for id in ids.iter() {
                     let revoke_status = RevokeMod::get_revocation_status(reg_id, id);
-                    assert_eq!(revoke_status, Some(()));
+                    assert!(revoke_status.is_some());
                 }
 
                 // 2. unrevoke
-                let unrevoke = UnRevoke {
+                let unrevoke = UnRevokeRaw {
                     registry_id: reg_id,
                     revoke_ids: revoke.revoke_ids.clone(),
-                    last_modified,
+                    _marker: PhantomData,
                 };
                 let unrevoke_size = unrevoke.revoke_ids.len() as u32;
-                let proof = std::iter::once((
-                    did_alice,
-                    sign(&StateChange::UnRevoke(unrevoke.clone()), &kp_alice),
-                ))
-                .collect();
+                let proof = vec![did_sigs(&unrevoke, did_alice, &kp_alice)];
 
                 let call = Call::RevokeMod(revoke::Call::<TestRt>::unrevoke(unrevoke, proof));
                 let expected_fees_nusd =
@@ -298,7 +305,7 @@ mod tests_revoke_calls {
                 // assert unrevoked
                 for id in ids.iter() {
                     let revoke_status = RevokeMod::get_revocation_status(reg_id, id);
-                    assert_eq!(revoke_status, None);
+                    assert!(revoke_status.is_none());
                 }
             }
         });
@@ -320,21 +327,20 @@ mod tests_revoke_calls {
                 let reg_id = random();
                 let reg = Registry { policy, add_only };
 
-                let got_reg = <revoke::Module<TestRt>>::get_revocation_registry(reg_id);
+                let got_reg = RevokeMod::get_revocation_registry(reg_id);
                 assert!(got_reg.is_none());
 
-                let call =
-                    Call::RevokeMod(revoke::Call::<TestRt>::new_registry(reg_id, reg.clone()));
+                let call = Call::RevokeMod(revoke::Call::<TestRt>::new_registry(AddRegistry {
+                    id: reg_id,
+                    registry: reg.clone(),
+                }));
 
                 let expected_fees =
                     PRICE_REVOKE_REGISTRY_CREATE / TestPriceProvider::get().unwrap();
                 let (_fee_microdock, _executed) = exec_assert_fees(call, expected_fees);
 
-                let got_reg = <revoke::Module<TestRt>>::get_revocation_registry(reg_id);
-                assert!(got_reg.is_some());
-                let (created_reg, created_bloc) = got_reg.unwrap();
-                assert_eq!(created_reg, reg);
-                assert_eq!(created_bloc, block_no());
+                let got_reg = RevokeMod::get_revocation_registry(reg_id);
+                assert_eq!(got_reg, Some(reg));
             }
         });
     }
@@ -342,19 +348,14 @@ mod tests_revoke_calls {
     fn call_revoke_remove_registry() {
         ext().execute_with(|| {
             let (did_alice, kp_alice) = newdid(ALICE);
-            let (reg_id, _reg) = new_reg(did_alice);
-            let last_modified = block_no() as u32;
+            let reg_id = new_reg(policy_oneof(&[did_alice]));
 
             // destroy reg
-            let rem = RemoveRegistry {
+            let rem = RemoveRegistryRaw {
                 registry_id: reg_id,
-                last_modified,
+                _marker: PhantomData,
             };
-            let proof = std::iter::once((
-                did_alice,
-                sign(&StateChange::RemoveRegistry(rem.clone()), &kp_alice),
-            ))
-            .collect();
+            let proof = vec![did_sigs(&rem, did_alice, &kp_alice)];
 
             let call = Call::RevokeMod(revoke::Call::<TestRt>::remove_registry(rem, proof));
 
@@ -368,6 +369,385 @@ mod tests_revoke_calls {
     }
 }
 
+mod tests_sponsorship {
+    use super::tests_revoke_calls::{did_sigs, new_reg};
+    use super::*;
+    use crate::{Config, Sponsorship};
+    use core::marker::PhantomData;
+    use core_mods::util::WithDeadline;
+    use did::Did;
+    use frame_support::traits::{Get, ReservableCurrency};
+    use frame_support::weights::GetDispatchInfo;
+    use revoke::{Policy, RevokeRaw};
+    use sp_std::collections::btree_set::BTreeSet;
+    use std::iter::once;
+
+    fn reserved(who: u64) -> u64 {
+        <TestRt as Config>::Currency::reserved_balance(&who)
+    }
+
+    fn total(who: u64) -> u64 {
+        <TestRt as Config>::Currency::total_balance(&who)
+    }
+
+    fn fee_of(call: &Call) -> u64 {
+        FiatFilterModule::get_call_fee_dock_(call).unwrap().0
+    }
+
+    #[test]
+    fn set_sponsorship__replace_allowances() {
+        ext().execute_with(|| {
+            let dids: BTreeSet<Did> = (0..3).map(|_| Did(random())).collect();
+            let two: BTreeSet<Did> = dids.iter().take(2).cloned().collect();
+
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                dids.clone(),
+                1_000,
+                10
+            ));
+            assert_eq!(reserved(SPONSOR), 3_000);
+
+            // A larger allowance reserves only the difference
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                two.clone(),
+                5_000,
+                10
+            ));
+            assert_eq!(reserved(SPONSOR), 1_000 + 2 * 5_000);
+
+            // A smaller allowance unreserves the difference
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                two.clone(),
+                200,
+                12
+            ));
+            assert_eq!(reserved(SPONSOR), 1_000 + 2 * 200);
+            assert_eq!(total(SPONSOR), INITIAL_BALANCE);
+            for did in &two {
+                assert_eq!(
+                    FiatFilterModule::sponsorship(SPONSOR, did),
+                    Some(Sponsorship {
+                        remaining: 200,
+                        expires_at: 12
+                    })
+                );
+            }
+
+            // Allowances which the sponsor can't afford aren't set
+            assert!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                dids.clone(),
+                INITIAL_BALANCE,
+                10
+            )
+            .is_err());
+            assert_eq!(reserved(SPONSOR), 1_000 + 2 * 200);
+
+            assert_noop!(
+                FiatFilterModule::set_sponsorship(Origin::signed(SPONSOR), dids.clone(), 100, 0),
+                Error::<TestRt>::SponsorshipExpired
+            );
+            assert_noop!(
+                FiatFilterModule::set_sponsorship(
+                    Origin::signed(SPONSOR),
+                    BTreeSet::new(),
+                    100,
+                    10
+                ),
+                Error::<TestRt>::NoDidsProvided
+            );
+
+            // Allowances given by another sponsor can't be removed
+            assert_noop!(
+                FiatFilterModule::remove_sponsorship(Origin::signed(BOB), two.clone()),
+                Error::<TestRt>::NoSponsorship
+            );
+
+            assert_ok!(FiatFilterModule::remove_sponsorship(
+                Origin::signed(SPONSOR),
+                dids.clone()
+            ));
+            assert_eq!(reserved(SPONSOR), 0);
+            assert_eq!(
+                <TestRt as Config>::Currency::free_balance(SPONSOR),
+                INITIAL_BALANCE
+            );
+            for did in &dids {
+                assert_eq!(FiatFilterModule::sponsorship(SPONSOR, did), None);
+            }
+        });
+    }
+
+    #[test]
+    fn execute_sponsored_call__expiry_and_allowance() {
+        ext().execute_with(|| {
+            let (author, kp) = newdid(ALICE);
+            let call = new_blob_call(author, &kp, 10);
+            let fee = fee_of(&call);
+
+            assert_noop!(
+                FiatFilterModule::execute_sponsored_call(
+                    Origin::signed(BOB),
+                    SPONSOR,
+                    Box::new(call.clone())
+                ),
+                Error::<TestRt>::NoSponsorship
+            );
+
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                once(author).collect(),
+                fee - 1,
+                5
+            ));
+            assert_noop!(
+                FiatFilterModule::execute_sponsored_call(
+                    Origin::signed(BOB),
+                    SPONSOR,
+                    Box::new(call.clone())
+                ),
+                Error::<TestRt>::AllowanceExceeded
+            );
+
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                once(author).collect(),
+                2 * fee,
+                5
+            ));
+            let bob_balance = total(BOB);
+            let executed = FiatFilterModule::execute_sponsored_call(
+                Origin::signed(BOB),
+                SPONSOR,
+                Box::new(call),
+            );
+            assert_eq!(executed.unwrap().pays_fee, Pays::No);
+            // The fee is paid from the reserved allowance
+            assert_eq!(total(BOB), bob_balance);
+            assert_eq!(total(SPONSOR), INITIAL_BALANCE - fee);
+            assert_eq!(reserved(SPONSOR), fee);
+            assert_eq!(
+                FiatFilterModule::sponsorship(SPONSOR, author),
+                Some(Sponsorship {
+                    remaining: fee,
+                    expires_at: 5
+                })
+            );
+
+            // The allowance can be used until the expiry block inclusive
+            System::set_block_number(6);
+            let call = new_blob_call(author, &kp, 10);
+            assert_noop!(
+                FiatFilterModule::execute_sponsored_call(
+                    Origin::signed(BOB),
+                    SPONSOR,
+                    Box::new(call)
+                ),
+                Error::<TestRt>::SponsorshipExpired
+            );
+
+            // Expired allowance is still removed by its sponsor
+            assert_ok!(FiatFilterModule::remove_sponsorship(
+                Origin::signed(SPONSOR),
+                once(author).collect()
+            ));
+            assert_eq!(reserved(SPONSOR), 0);
+            assert_eq!(total(SPONSOR), INITIAL_BALANCE - fee);
+        });
+    }
+
+    #[test]
+    fn execute_sponsored_call__weight_of_call() {
+        ext().execute_with(|| {
+            let (author, kp) = newdid(ALICE);
+            let call = new_blob_call(author, &kp, 1000);
+            let call_weight = call.get_dispatch_info().weight;
+
+            // The declared weight covers the sponsored call
+            let sponsored =
+                crate::Call::<TestRt>::execute_sponsored_call(SPONSOR, Box::new(call.clone()));
+            assert_eq!(
+                sponsored.get_dispatch_info().weight,
+                call_weight + <TestRt as frame_system::Config>::DbWeight::get().reads_writes(3, 2)
+            );
+
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                once(author).collect(),
+                fee_of(&call),
+                5
+            ));
+            let executed = FiatFilterModule::execute_sponsored_call(
+                Origin::signed(BOB),
+                SPONSOR,
+                Box::new(call),
+            )
+            .unwrap();
+            assert!(executed.actual_weight.unwrap() >= call_weight);
+        });
+    }
+
+    #[test]
+    fn execute_sponsored_call__failed_call_charges_sender() {
+        ext().execute_with(|| {
+            let (author, _) = newdid(ALICE);
+            // Signed by a key which doesn't belong to the author
+            let call = new_blob_call(author, &gen_kp(), 10);
+            let fee = fee_of(&call);
+            let sponsorship = Sponsorship {
+                remaining: 3 * fee,
+                expires_at: 10,
+            };
+
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                once(author).collect(),
+                3 * fee,
+                10
+            ));
+
+            let bob_balance = total(BOB);
+            let err = FiatFilterModule::execute_sponsored_call(
+                Origin::signed(BOB),
+                SPONSOR,
+                Box::new(call.clone()),
+            )
+            .unwrap_err();
+            assert_eq!(err.error, did::Error::<TestRt>::InvalidSignature.into());
+            assert_eq!(err.post_info.pays_fee, Pays::No);
+            assert_eq!(total(BOB), bob_balance - fee);
+            assert_eq!(
+                FiatFilterModule::sponsorship(SPONSOR, author),
+                Some(sponsorship.clone())
+            );
+            assert_eq!(reserved(SPONSOR), 3 * fee);
+
+            // The call isn't dispatched when the sender can't afford the fee of the call
+            let _ = <TestRt as Config>::Currency::make_free_balance_be(&BOB, fee);
+            assert_noop!(
+                FiatFilterModule::execute_sponsored_call(
+                    Origin::signed(BOB),
+                    SPONSOR,
+                    Box::new(call)
+                ),
+                Error::<TestRt>::CannotPayFailedCallFee
+            );
+            assert_eq!(total(BOB), fee);
+            assert_eq!(
+                FiatFilterModule::sponsorship(SPONSOR, author),
+                Some(sponsorship)
+            );
+            assert_eq!(reserved(SPONSOR), 3 * fee);
+        });
+    }
+
+    #[test]
+    fn execute_sponsored_call__unsupported_call() {
+        ext().execute_with(|| {
+            let (did_alice, kp) = newdid(ALICE);
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                once(did_alice).collect(),
+                1_000_000,
+                10
+            ));
+
+            // Signed by the DID but not priced by the module
+            let removal = did::RemoveKeys::<TestRt> {
+                did: did_alice,
+                keys: once(1u32.into()).collect(),
+                nonce: next_nonce(&did_alice),
+            };
+            let sig = did_sig(&removal, &kp, did::Controller(did_alice));
+            let calls = vec![
+                Call::DIDMod(did::Call::<TestRt>::remove_keys(removal, vec![sig])),
+                Call::AnchorMod(anchor::Call::<TestRt>::deploy(random_bytes(32))),
+            ];
+            for call in calls {
+                assert_noop!(
+                    FiatFilterModule::execute_sponsored_call(
+                        Origin::signed(BOB),
+                        SPONSOR,
+                        Box::new(call)
+                    ),
+                    Error::<TestRt>::UnsupportedSponsoredCall
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn execute_sponsored_call__multiple_signers_not_sponsorable() {
+        ext().execute_with(|| {
+            let (did_alice, kp_alice) = newdid(ALICE);
+            let (did_bob, kp_bob) = newdid(BOB);
+            let reg_id = new_reg(Policy::AllOf(
+                vec![did_alice, did_bob].into_iter().collect(),
+            ));
+            assert_ok!(FiatFilterModule::set_sponsorship(
+                Origin::signed(SPONSOR),
+                vec![did_alice, did_bob].into_iter().collect(),
+                1_000_000,
+                10
+            ));
+
+            let revoke = RevokeRaw {
+                registry_id: reg_id,
+                revoke_ids: once(random()).collect(),
+                _marker: PhantomData,
+            };
+            let proof = vec![
+                did_sigs(&revoke, did_alice, &kp_alice),
+                did_sigs(&revoke, did_bob, &kp_bob),
+            ];
+
+            // A single signature is sponsored by its signer
+            let call = Call::RevokeMod(revoke::Call::<TestRt>::revoke(
+                revoke.clone(),
+                proof[..1].to_vec(),
+            ));
+            assert_eq!(FiatFilterModule::sponsored_did(&call), Ok(did_alice));
+
+            let call = Call::RevokeMod(revoke::Call::<TestRt>::revoke(
+                revoke.clone(),
+                proof.clone(),
+            ));
+            assert_eq!(
+                FiatFilterModule::sponsored_did(&call),
+                Err(Error::<TestRt>::NotSponsorable.into())
+            );
+            assert_noop!(
+                FiatFilterModule::execute_sponsored_call(
+                    Origin::signed(BOB),
+                    SPONSOR,
+                    Box::new(call)
+                ),
+                Error::<TestRt>::NotSponsorable
+            );
+
+            // Neither are multiple signatures nor a proof without signatures
+            let proof: Vec<_> = proof.into_iter().map(|sigs| sigs.sig).collect();
+            for sigs in vec![proof, vec![]] {
+                let call = Call::RevokeMod(revoke::Call::<TestRt>::revoke_with_deadline(
+                    WithDeadline::new(revoke.clone(), random(), 10),
+                    sigs,
+                ));
+                assert_noop!(
+                    FiatFilterModule::execute_sponsored_call(
+                        Origin::signed(BOB),
+                        SPONSOR,
+                        Box::new(call)
+                    ),
+                    Error::<TestRt>::NotSponsorable
+                );
+            }
+        });
+    }
+}
+
 mod tests_fail_modes {
     use super::*;
     use crate::Config;
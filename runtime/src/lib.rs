@@ -111,7 +111,7 @@ use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
-use core_mods::util::IncId;
+use core_mods::util::{IncId, WrappedBytes};
 #[cfg(feature = "std")]
 pub use pallet_staking::StakerStatus;
 use sp_runtime::curve::PiecewiseLinear;
@@ -192,9 +192,9 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("dock-pos-dev-runtime"),
     impl_name: create_runtime_str!("Dock"),
     authoring_version: 1,
-    spec_version: 38,
+    spec_version: 39,
     impl_version: 1,
-    transaction_version: 1,
+    transaction_version: 2,
     apis: RUNTIME_API_VERSIONS,
 };
 
@@ -721,7 +721,27 @@ parameter_types! {
     pub const MaxServiceEndpointOrigins: u16 = 64;
     pub const MaxServiceEndpointOriginSize: u16 = 1025;
     pub const ServiceEndpointOriginPerByteWeight: Weight = 10;
+    pub const MaxServiceEndpointTypes: u16 = 8;
+    pub const MaxServiceEndpointTypeSize: u16 = 256;
+    pub const ServiceEndpointTypePerByteWeight: Weight = 10;
+    pub const MaxServiceEndpointProperties: u16 = 16;
+    pub const MaxServiceEndpointPropertyValues: u16 = 16;
+    pub const MaxServiceEndpointPropertySize: u16 = 1024;
+    pub const ServiceEndpointPropertyPerByteWeight: Weight = 10;
+    pub const MaxRecoveryGuardians: u16 = 16;
+    pub const MinRecoveryDelay: BlockNumber = 1 * DAYS;
+    pub const MaxActionLifetime: BlockNumber = 1 * DAYS;
+    pub const MaxReplayIdRemovalsPerBlock: u32 = 500;
+    pub const StorageItemDeposit: Balance = DOCK / 10;
+    pub const StorageByteDeposit: Balance = DOCK / 1000;
+    pub const MaxControllerDepth: u16 = 8;
+    pub const PreventControllerCycles: bool = true;
+    pub const MaxControllerGraphReads: u32 = 256;
+    pub const MaxDidDocumentItems: u32 = 128;
+    pub const DidVersionRetentionPeriod: BlockNumber = 30 * DAYS;
+    pub WebAuthnRpIdHash: [u8; 32] = sp_io::hashing::sha2_256(b"dock.io");
     pub const MaxControllers: u32 = 15;
+    pub const MaxStatusListSize: u32 = 8 * revoke::MIN_STATUS_LIST_SIZE;
 }
 
 impl did::Config for Runtime {
@@ -733,11 +753,32 @@ impl did::Config for Runtime {
     type MaxServiceEndpointOrigins = MaxServiceEndpointOrigins;
     type MaxServiceEndpointOriginSize = MaxServiceEndpointOriginSize;
     type ServiceEndpointOriginPerByteWeight = ServiceEndpointOriginPerByteWeight;
+    type MaxServiceEndpointTypes = MaxServiceEndpointTypes;
+    type MaxServiceEndpointTypeSize = MaxServiceEndpointTypeSize;
+    type ServiceEndpointTypePerByteWeight = ServiceEndpointTypePerByteWeight;
+    type MaxServiceEndpointProperties = MaxServiceEndpointProperties;
+    type MaxServiceEndpointPropertyValues = MaxServiceEndpointPropertyValues;
+    type MaxServiceEndpointPropertySize = MaxServiceEndpointPropertySize;
+    type ServiceEndpointPropertyPerByteWeight = ServiceEndpointPropertyPerByteWeight;
+    type MaxRecoveryGuardians = MaxRecoveryGuardians;
+    type MinRecoveryDelay = MinRecoveryDelay;
+    type MaxActionLifetime = MaxActionLifetime;
+    type MaxReplayIdRemovalsPerBlock = MaxReplayIdRemovalsPerBlock;
+    type Currency = balances::Module<Runtime>;
+    type StorageItemDeposit = StorageItemDeposit;
+    type StorageByteDeposit = StorageByteDeposit;
+    type MaxControllerDepth = MaxControllerDepth;
+    type PreventControllerCycles = PreventControllerCycles;
+    type MaxControllerGraphReads = MaxControllerGraphReads;
+    type MaxDidDocumentItems = MaxDidDocumentItems;
+    type DidVersionRetentionPeriod = DidVersionRetentionPeriod;
+    type WebAuthnRpIdHash = WebAuthnRpIdHash;
 }
 
 impl revoke::Config for Runtime {
     type Event = Event;
     type MaxControllers = MaxControllers;
+    type MaxStatusListSize = MaxStatusListSize;
 }
 
 impl bbs_plus::Config for Runtime {
@@ -1383,6 +1424,7 @@ impl fiat_filter::Config for Runtime {
     type Call = Call;
     type PriceProvider = price_feed::Module<Runtime>;
     type Currency = balances::Module<Runtime>;
+    type Event = Event;
     type MinDockFiatRate = MinDockFiatRate;
 }
 
@@ -1390,7 +1432,10 @@ pub struct BaseFilter;
 impl Filter<Call> for BaseFilter {
     fn filter(call: &Call) -> bool {
         match call {
-            // Disable fiat_filter for now
+            // Disable fiat_filter for now except for the sponsored calls and the management of sponsorships
+            Call::FiatFilterModule(fiat_filter::Call::execute_sponsored_call(..))
+            | Call::FiatFilterModule(fiat_filter::Call::set_sponsorship(..))
+            | Call::FiatFilterModule(fiat_filter::Call::remove_sponsorship(..)) => true,
             Call::FiatFilterModule(_) => false,
             _ => true,
         }
@@ -1431,7 +1476,7 @@ construct_runtime!(
         Ethereum: pallet_ethereum::{Module, Call, Storage, Event, Config, ValidateUnsigned},
         EVM: pallet_evm::{Module, Config, Call, Storage, Event<T>},
         PriceFeedModule: price_feed::{Module, Call, Storage, Event},
-        FiatFilterModule: fiat_filter::{Module, Call},
+        FiatFilterModule: fiat_filter::{Module, Call, Storage, Event<T>},
         AuthorityDiscovery: pallet_authority_discovery::{Module, Call, Config},
         Historical: pallet_session_historical::{Module},
         ImOnline: pallet_im_online::{Module, Call, Storage, Event<T>, ValidateUnsigned, Config<T>},
@@ -1855,6 +1900,46 @@ impl_runtime_apis! {
             dids.into_iter().map(|did| DIDModule::aggregate_did_details(&did, params)).collect()
         }
 
+        fn resolve_did(did: did::Did) -> Option<did::DidResolution<Runtime>> {
+            DIDModule::resolve_did(&did)
+        }
+
+        fn resolve_did_at(did: did::Did, query: did::DidVersionQuery<Runtime>) -> Option<did::HistoricalDidResolution<Runtime>> {
+            DIDModule::resolve_did_at(&did, query)
+        }
+
+        fn did_recovery(did: did::Did) -> Option<did::DidRecoveryStatus<Runtime>> {
+            DIDModule::did_recovery(&did)
+        }
+
+        fn dids_by_account(account: AccountId) -> Vec<did::Did> {
+            DIDModule::dids_by_account(&account)
+        }
+
+        fn dids_by_public_key(public_key: keys_and_sigs::PublicKey) -> Vec<(did::Did, IncId)> {
+            DIDModule::dids_by_public_key(&public_key)
+        }
+
+        fn did_keys(did: did::Did, cursor: Option<IncId>, limit: u32) -> did::Page<IncId, did::DidKeyWithId> {
+            DIDModule::did_keys_page(&did, cursor, limit)
+        }
+
+        fn did_controllers(did: did::Did, cursor: Option<did::Controller>, limit: u32) -> did::Page<did::Controller, did::Controller> {
+            DIDModule::did_controllers_page(&did, cursor, limit)
+        }
+
+        fn did_service_endpoints(did: did::Did, cursor: Option<WrappedBytes>, limit: u32) -> did::Page<WrappedBytes, did::ServiceEndpointWithId> {
+            DIDModule::did_service_endpoints_page(&did, cursor, limit)
+        }
+
+        fn did_list(did_type: Option<did::DidType>, cursor: Option<did::Did>, limit: u32) -> did::Page<did::Did, did::Did> {
+            DIDModule::dids_page(did_type, cursor, limit)
+        }
+
+        fn did_controller_paths(did: did::Did) -> Vec<did::ControllerPath> {
+            DIDModule::controller_paths(&did)
+        }
+
         fn bbs_plus_public_key_with_params(id: bbs_plus::BBSPlusPublicKeyStorageKey) -> Option<bbs_plus::BBSPlusPublicKeyWithParams> {
             BbsPlus::get_public_key_with_params(&id)
         }
@@ -1874,6 +1959,14 @@ impl_runtime_apis! {
         fn accumulator_with_public_key_and_params(id: accumulator::AccumulatorId) -> Option<(Vec<u8>, Option<accumulator::AccumPublicKeyWithParams>)> {
             Accumulator::get_accumulator_with_public_key_and_params(&id)
         }
+
+        fn status_list(id: revoke::RegistryId) -> Option<revoke::StatusList> {
+            Revoke::get_status_list(id)
+        }
+
+        fn revocation_status(registry_id: revoke::RegistryId, revoke_id: revoke::RevokeId) -> Option<revoke::RevocationStatus<Runtime>> {
+            Revoke::revocation_status(registry_id, revoke_id)
+        }
     }
 
     #[cfg(feature = "runtime-benchmarks")]